@@ -18,8 +18,10 @@ mod iter_simple_sparse_set;
 mod iter_simple_system;
 mod iter_simple_wide;
 mod iter_simple_wide_sparse_set;
+mod par_iter_simple;
 
 use heavy_compute::*;
+use par_iter_simple::*;
 
 criterion_group!(
     iterations_benches,
@@ -27,6 +29,7 @@ criterion_group!(
     iter_frag_sparse,
     iter_simple,
     heavy_compute,
+    par_iter_simple,
 );
 
 fn iter_simple(c: &mut Criterion) {
@@ -0,0 +1,99 @@
+use bevy_ecs::{prelude::*, query::BatchingStrategy};
+use bevy_tasks::{ComputeTaskPool, ParallelIterator, TaskPool};
+use criterion::Criterion;
+use glam::*;
+
+#[derive(Component, Copy, Clone)]
+struct Transform(Mat4);
+
+#[derive(Component, Copy, Clone)]
+struct Position(Vec3);
+
+fn setup() -> World {
+    ComputeTaskPool::init(TaskPool::default);
+
+    let mut world = World::default();
+    world.spawn_batch((0..10_000).map(|_| {
+        (
+            Transform(Mat4::from_axis_angle(Vec3::X, 1.2)),
+            Position(Vec3::X),
+        )
+    }));
+    world
+}
+
+fn bench_system<Param>(
+    group: &mut criterion::BenchmarkGroup<criterion::measurement::WallTime>,
+    name: &str,
+    system: impl IntoSystem<(), (), Param>,
+) {
+    let mut world = setup();
+    let mut system = IntoSystem::into_system(system);
+    system.initialize(&mut world);
+    system.update_archetype_component_access(&world);
+
+    group.bench_function(name, |b| b.iter(|| system.run((), &mut world)));
+}
+
+fn compute(pos: &mut Position, mat: &mut Transform) {
+    for _ in 0..10 {
+        mat.0 = mat.0.inverse();
+    }
+    pos.0 = mat.0.transform_vector3(pos.0);
+}
+
+pub fn par_iter_simple(c: &mut Criterion) {
+    let mut group = c.benchmark_group("par_iter_simple");
+    group.warm_up_time(std::time::Duration::from_millis(500));
+    group.measurement_time(std::time::Duration::from_secs(4));
+
+    for batch_size in [16, 128, 1024] {
+        bench_system(
+            &mut group,
+            &format!("par_for_each_mut_{}", batch_size),
+            move |mut query: Query<(&mut Position, &mut Transform)>| {
+                query.par_for_each_mut(batch_size, |(mut pos, mut mat)| {
+                    compute(&mut pos, &mut mat);
+                });
+            },
+        );
+    }
+    bench_system(
+        &mut group,
+        "par_iter_mut_auto",
+        |mut query: Query<(&mut Position, &mut Transform)>| {
+            query.par_iter_mut().for_each(|(mut pos, mut mat)| {
+                compute(&mut pos, &mut mat);
+            });
+        },
+    );
+    bench_system(
+        &mut group,
+        "par_iter_mut_auto_4_per_thread",
+        |mut query: Query<(&mut Position, &mut Transform)>| {
+            query
+                .par_iter_mut()
+                .batching_strategy(BatchingStrategy::new().batches_per_thread(4))
+                .for_each(|(mut pos, mut mat)| {
+                    compute(&mut pos, &mut mat);
+                });
+        },
+    );
+
+    bench_system(&mut group, "iter_collect", |query: Query<&Transform>| {
+        let determinants: Vec<f32> = query.iter().map(|mat| mat.0.determinant()).collect();
+        criterion::black_box(determinants);
+    });
+    bench_system(
+        &mut group,
+        "par_iter_collect",
+        |query: Query<&Transform>| {
+            let determinants: Vec<f32> = query
+                .par_iter()
+                .map(|mat| mat.0.determinant())
+                .collect(ComputeTaskPool::get());
+            criterion::black_box(determinants);
+        },
+    );
+    group.finish();
+}
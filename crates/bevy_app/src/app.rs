@@ -1,4 +1,7 @@
-use crate::{CoreStage, Plugin, PluginGroup, PluginGroupBuilder, StartupSchedule, StartupStage};
+use crate::{
+    CoreStage, Plugin, PluginDependencies, PluginError, PluginGroup, PluginGroupBuilder,
+    StartupSchedule, StartupStage,
+};
 pub use bevy_derive::AppLabel;
 use bevy_ecs::{
     event::{Event, Events},
@@ -10,8 +13,14 @@ use bevy_ecs::{
     system::Resource,
     world::World,
 };
-use bevy_utils::{tracing::debug, HashMap};
-use std::fmt::Debug;
+use bevy_utils::{
+    tracing::{debug, warn},
+    HashMap, HashSet,
+};
+use std::{
+    any::{Any, TypeId},
+    fmt::Debug,
+};
 
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::info_span;
@@ -62,6 +71,9 @@ pub struct App {
     /// A container of [`Stage`]s set to be run in a linear order.
     pub schedule: Schedule,
     sub_apps: HashMap<AppLabelId, SubApp>,
+    plugin_registry: Vec<Box<dyn Plugin>>,
+    plugin_ids: HashSet<TypeId>,
+    plugins_finished: bool,
 }
 
 /// Each `SubApp` has its own [`Schedule`] and [`World`], enabling a separation of concerns.
@@ -105,6 +117,9 @@ impl App {
             schedule: Default::default(),
             runner: Box::new(run_once),
             sub_apps: HashMap::default(),
+            plugin_registry: Vec::default(),
+            plugin_ids: HashSet::default(),
+            plugins_finished: false,
         }
     }
 
@@ -124,12 +139,13 @@ impl App {
 
     /// Starts the application by calling the app's [runner function](Self::set_runner).
     ///
-    /// Finalizes the [`App`] configuration. For general usage, see the example on the item
-    /// level documentation.
+    /// Finalizes the [`App`] configuration with [`finish_plugins`](Self::finish_plugins).
+    /// For general usage, see the example on the item level documentation.
     pub fn run(&mut self) {
         #[cfg(feature = "trace")]
         let _bevy_app_run_span = info_span!("bevy_app").entered();
 
+        self.finish_plugins();
         let mut app = std::mem::replace(self, App::empty());
         let runner = std::mem::replace(&mut app.runner, Box::new(run_once));
         (runner)(app);
@@ -778,15 +794,113 @@ impl App {
     /// # }
     /// App::new().add_plugin(bevy_log::LogPlugin::default());
     /// ```
+    ///
+    /// If the plugin is [unique](Plugin::is_unique) and was already added, a warning is logged
+    /// and it is skipped.
     pub fn add_plugin<T>(&mut self, plugin: T) -> &mut Self
     where
         T: Plugin,
     {
+        self.add_boxed_plugin(Box::new(plugin))
+    }
+
+    /// Adds a single boxed [`Plugin`]. See [`add_plugin`](Self::add_plugin).
+    ///
+    /// This is used for [`Plugin`]s whose type is not known at compile time, such as the ones
+    /// built by a [`PluginGroup`] or loaded dynamically.
+    ///
+    /// If the plugin is [unique](Plugin::is_unique) and was already added, a warning is logged
+    /// and it is skipped.
+    pub fn add_boxed_plugin(&mut self, plugin: Box<dyn Plugin>) -> &mut Self {
+        let type_id = Any::type_id(&*plugin);
+        if !self.plugin_ids.insert(type_id) && plugin.is_unique() {
+            warn!(
+                "{}, skipping it",
+                PluginError::Duplicate(plugin.name().to_string())
+            );
+            return self;
+        }
         debug!("added plugin: {}", plugin.name());
         plugin.build(self);
+        // Plugins added while building this one are registered first, which is what
+        // dependency validation expects.
+        self.plugin_registry.push(plugin);
         self
     }
 
+    /// Returns `true` if a [`Plugin`] of type `T` was added to this [`App`].
+    pub fn is_plugin_added<T: Plugin>(&self) -> bool {
+        self.plugin_ids.contains(&TypeId::of::<T>())
+    }
+
+    /// Checks that the [dependencies](Plugin::dependencies) of every added [`Plugin`] were
+    /// added, and that they were added before the [`Plugin`] depending on them.
+    pub fn validate_plugins(&self) -> Result<(), PluginError> {
+        let mut positions = HashMap::<TypeId, usize>::default();
+        for (index, plugin) in self.plugin_registry.iter().enumerate() {
+            positions.entry(Any::type_id(&**plugin)).or_insert(index);
+        }
+        for (index, plugin) in self.plugin_registry.iter().enumerate() {
+            let mut dependencies = PluginDependencies::default();
+            plugin.dependencies(&mut dependencies);
+            for dependency in dependencies.iter() {
+                match positions.get(&dependency.type_id) {
+                    Some(&position) if position > index => {
+                        return Err(PluginError::DependencyAddedAfter {
+                            plugin: plugin.name().to_string(),
+                            dependency: dependency.type_name,
+                        });
+                    }
+                    None if dependency.required => {
+                        return Err(PluginError::MissingDependency {
+                            plugin: plugin.name().to_string(),
+                            dependency: dependency.type_name,
+                        });
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Validates the added [`Plugin`]s, then runs [`Plugin::finish`] followed by
+    /// [`Plugin::cleanup`] on each of them, in the order they were added.
+    ///
+    /// [`Plugin`]s added by [`Plugin::finish`] are finished in turn, after the ones already added.
+    ///
+    /// This is called by [`App::run`], and only has an effect the first time it is called.
+    /// It can be called manually when updating the [`App`] without running it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`validate_plugins`](Self::validate_plugins) returns an error.
+    pub fn finish_plugins(&mut self) {
+        if self.plugins_finished {
+            return;
+        }
+        self.plugins_finished = true;
+        if let Err(error) = self.validate_plugins() {
+            panic!("{}", error);
+        }
+        let mut plugins = Vec::new();
+        loop {
+            let pending = std::mem::take(&mut self.plugin_registry);
+            if pending.is_empty() {
+                break;
+            }
+            for plugin in &pending {
+                plugin.finish(self);
+            }
+            plugins.extend(pending);
+        }
+        for plugin in &plugins {
+            plugin.cleanup(self);
+        }
+        let added_during_cleanup = std::mem::replace(&mut self.plugin_registry, plugins);
+        self.plugin_registry.extend(added_during_cleanup);
+    }
+
     /// Adds a group of [`Plugin`]s.
     ///
     /// [`Plugin`]s can be grouped into a set by using a [`PluginGroup`].
@@ -989,3 +1103,159 @@ fn run_once(mut app: App) {
 /// frame is over.
 #[derive(Debug, Clone, Default)]
 pub struct AppExit;
+
+#[cfg(test)]
+mod tests {
+    use crate::{App, Plugin, PluginDependencies, PluginError};
+
+    struct PluginA;
+    impl Plugin for PluginA {
+        fn build(&self, _app: &mut App) {}
+    }
+
+    struct PluginB;
+    impl Plugin for PluginB {
+        fn build(&self, _app: &mut App) {}
+        fn dependencies(&self, dependencies: &mut PluginDependencies) {
+            dependencies.require::<PluginA>();
+        }
+    }
+
+    struct PluginC;
+    impl Plugin for PluginC {
+        fn build(&self, app: &mut App) {
+            app.add_plugin(PluginA);
+        }
+        fn dependencies(&self, dependencies: &mut PluginDependencies) {
+            dependencies.require::<PluginA>();
+        }
+    }
+
+    struct PluginD;
+    impl Plugin for PluginD {
+        fn build(&self, _app: &mut App) {}
+        fn dependencies(&self, dependencies: &mut PluginDependencies) {
+            dependencies.optional::<PluginA>();
+        }
+    }
+
+    struct NonUniquePlugin;
+    impl Plugin for NonUniquePlugin {
+        fn build(&self, _app: &mut App) {}
+        fn is_unique(&self) -> bool {
+            false
+        }
+    }
+
+    #[derive(Default)]
+    struct Phases(Vec<&'static str>);
+
+    struct PhasesPlugin;
+    impl Plugin for PhasesPlugin {
+        fn build(&self, app: &mut App) {
+            app.init_resource::<Phases>();
+            app.world.resource_mut::<Phases>().0.push("build");
+        }
+        fn finish(&self, app: &mut App) {
+            app.world.resource_mut::<Phases>().0.push("finish");
+        }
+        fn cleanup(&self, app: &mut App) {
+            app.world.resource_mut::<Phases>().0.push("cleanup");
+        }
+    }
+
+    #[test]
+    fn dependencies_satisfied() {
+        let mut app = App::new();
+        app.add_plugin(PluginA)
+            .add_plugin(PluginB)
+            .add_plugin(PluginD);
+        assert_eq!(app.validate_plugins(), Ok(()));
+        assert!(app.is_plugin_added::<PluginB>());
+    }
+
+    #[test]
+    fn dependency_added_in_build() {
+        let mut app = App::new();
+        app.add_plugin(PluginC);
+        assert_eq!(app.validate_plugins(), Ok(()));
+    }
+
+    #[test]
+    fn missing_dependency() {
+        let mut app = App::new();
+        app.add_plugin(PluginB);
+        assert_eq!(
+            app.validate_plugins(),
+            Err(PluginError::MissingDependency {
+                plugin: std::any::type_name::<PluginB>().to_string(),
+                dependency: std::any::type_name::<PluginA>(),
+            })
+        );
+    }
+
+    #[test]
+    fn missing_optional_dependency() {
+        let mut app = App::new();
+        app.add_plugin(PluginD);
+        assert_eq!(app.validate_plugins(), Ok(()));
+    }
+
+    #[test]
+    fn dependency_added_after() {
+        let mut app = App::new();
+        app.add_plugin(PluginD).add_plugin(PluginA);
+        assert_eq!(
+            app.validate_plugins(),
+            Err(PluginError::DependencyAddedAfter {
+                plugin: std::any::type_name::<PluginD>().to_string(),
+                dependency: std::any::type_name::<PluginA>(),
+            })
+        );
+    }
+
+    #[test]
+    fn duplicate_plugin() {
+        let mut app = App::new();
+        app.add_plugin(PhasesPlugin).add_plugin(PhasesPlugin);
+        assert_eq!(app.world.resource::<Phases>().0, vec!["build"]);
+    }
+
+    #[test]
+    fn duplicate_non_unique_plugin() {
+        App::new()
+            .add_plugin(NonUniquePlugin)
+            .add_plugin(NonUniquePlugin);
+    }
+
+    #[test]
+    fn finish_and_cleanup() {
+        let mut app = App::new();
+        app.add_plugin(PhasesPlugin);
+        app.finish_plugins();
+        app.finish_plugins();
+        assert_eq!(
+            app.world.resource::<Phases>().0,
+            vec!["build", "finish", "cleanup"]
+        );
+    }
+
+    struct AddsInFinishPlugin;
+    impl Plugin for AddsInFinishPlugin {
+        fn build(&self, _app: &mut App) {}
+        fn finish(&self, app: &mut App) {
+            app.add_plugin(PhasesPlugin);
+        }
+    }
+
+    #[test]
+    fn plugin_added_in_finish_is_finished() {
+        let mut app = App::new();
+        app.add_plugin(AddsInFinishPlugin);
+        app.finish_plugins();
+        assert_eq!(
+            app.world.resource::<Phases>().0,
+            vec!["build", "finish", "cleanup"]
+        );
+    }
+}
@@ -1,17 +1,47 @@
 use crate::App;
-use std::any::Any;
+use std::any::{Any, TypeId};
+use thiserror::Error;
 
 /// A collection of Bevy app logic and configuration.
 ///
 /// Plugins configure an [`App`]. When an [`App`] registers a plugin,
 /// the plugin's [`Plugin::build`] function is run.
+///
+/// Once every plugin has been added, [`App::finish_plugins`] (called by [`App::run`])
+/// validates the declared [dependencies](Plugin::dependencies), then runs [`Plugin::finish`]
+/// and [`Plugin::cleanup`] on every plugin, in the order they were added.
 pub trait Plugin: Any + Send + Sync {
     /// Configures the [`App`] to which this plugin is added.
     fn build(&self, app: &mut App);
+
+    /// Finishes the configuration of the [`App`], once all plugins have been built.
+    ///
+    /// This is useful for plugins that need to access resources or sub apps that are
+    /// initialized by plugins added after them.
+    fn finish(&self, _app: &mut App) {}
+
+    /// Runs after [`Plugin::finish`] has been called on all plugins, to clean up anything
+    /// that was only needed during setup.
+    fn cleanup(&self, _app: &mut App) {}
+
     /// Configures a name for the [`Plugin`] which is primarily used for debugging.
     fn name(&self) -> &str {
         std::any::type_name::<Self>()
     }
+
+    /// If the plugin can be meaningfully instantiated several times in an [`App`],
+    /// override this method to return `false`.
+    ///
+    /// A unique plugin that was already added is skipped, with a warning, when it is added again.
+    fn is_unique(&self) -> bool {
+        true
+    }
+
+    /// Declares the [`Plugin`]s this plugin depends on.
+    ///
+    /// Dependencies must be added to the [`App`] before this plugin, and are checked by
+    /// [`App::validate_plugins`].
+    fn dependencies(&self, _dependencies: &mut PluginDependencies) {}
 }
 
 /// A type representing an unsafe function that returns a mutable pointer to a [`Plugin`].
@@ -19,3 +49,113 @@ pub trait Plugin: Any + Send + Sync {
 ///
 /// See `bevy_dynamic_plugin/src/loader.rs#dynamically_load_plugin`.
 pub type CreatePlugin = unsafe fn() -> *mut dyn Plugin;
+
+/// A type representing an unsafe function that returns the [`PluginAbi`] a dynamically loaded
+/// [`Plugin`] was compiled against.
+///
+/// Like the `_bevy_create_plugin` function, the `_bevy_plugin_abi` function is exported with the
+/// C calling convention, so that it can be called no matter how the library was compiled.
+///
+/// See `bevy_dynamic_plugin/src/loader.rs#load_dynamic_plugin`.
+// Both sides are Rust code, and the layout of `&str` and `TypeId` has to match for the check to
+// be meaningful anyway.
+#[allow(improper_ctypes_definitions)]
+pub type GetPluginAbi = unsafe extern "C" fn() -> PluginAbi;
+
+/// Identifies the build of Bevy a dynamically loaded [`Plugin`] was compiled against.
+///
+/// A dynamic plugin can only be loaded if its [`PluginAbi`] matches the one of the host program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(C)]
+pub struct PluginAbi {
+    /// The version of `bevy_app`.
+    pub bevy_version: &'static str,
+    /// The [`TypeId`] of [`App`], which differs between incompatible builds of Bevy.
+    pub app_type_id: TypeId,
+}
+
+impl PluginAbi {
+    /// The version of `bevy_app` this crate was compiled as.
+    pub const BEVY_VERSION: &'static str = env!("CARGO_PKG_VERSION");
+
+    /// Returns the [`PluginAbi`] of the calling crate.
+    #[inline]
+    pub fn current() -> Self {
+        Self {
+            bevy_version: Self::BEVY_VERSION,
+            app_type_id: TypeId::of::<App>(),
+        }
+    }
+}
+
+/// A [`Plugin`] that another [`Plugin`] depends on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PluginDependency {
+    /// The [`TypeId`] of the [`Plugin`] depended on.
+    pub type_id: TypeId,
+    /// The type name of the [`Plugin`] depended on, used when reporting errors.
+    pub type_name: &'static str,
+    /// Whether the dependency must be present in the [`App`].
+    ///
+    /// Optional dependencies are not required, but if present they must be added before
+    /// the depending [`Plugin`].
+    pub required: bool,
+}
+
+/// The dependencies declared by a [`Plugin`] in [`Plugin::dependencies`].
+#[derive(Debug, Default)]
+pub struct PluginDependencies {
+    dependencies: Vec<PluginDependency>,
+}
+
+impl PluginDependencies {
+    /// Declares that the plugin of type `T` must be added to the [`App`] before this one.
+    pub fn require<T: Plugin>(&mut self) -> &mut Self {
+        self.add::<T>(true)
+    }
+
+    /// Declares that the plugin of type `T`, if added to the [`App`], must be added before this one.
+    pub fn optional<T: Plugin>(&mut self) -> &mut Self {
+        self.add::<T>(false)
+    }
+
+    /// Iterates over the declared dependencies.
+    pub fn iter(&self) -> impl Iterator<Item = &PluginDependency> {
+        self.dependencies.iter()
+    }
+
+    fn add<T: Plugin>(&mut self, required: bool) -> &mut Self {
+        self.dependencies.push(PluginDependency {
+            type_id: TypeId::of::<T>(),
+            type_name: std::any::type_name::<T>(),
+            required,
+        });
+        self
+    }
+}
+
+/// An error found while validating the [`Plugin`]s of an [`App`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PluginError {
+    /// A required dependency was never added to the [`App`].
+    #[error("plugin `{plugin}` requires `{dependency}`, which was not added to the app")]
+    MissingDependency {
+        /// The name of the depending plugin.
+        plugin: String,
+        /// The type name of the missing dependency.
+        dependency: &'static str,
+    },
+    /// A dependency was added to the [`App`] after the plugin depending on it.
+    #[error("plugin `{plugin}` depends on `{dependency}`, which must be added before it")]
+    DependencyAddedAfter {
+        /// The name of the depending plugin.
+        plugin: String,
+        /// The type name of the dependency.
+        dependency: &'static str,
+    },
+    /// A [unique](Plugin::is_unique) plugin was added more than once.
+    ///
+    /// This is only reported as a warning, and the plugin is not built again.
+    #[error("plugin `{0}` was already added to the app")]
+    Duplicate(String),
+}
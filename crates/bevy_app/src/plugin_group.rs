@@ -1,5 +1,5 @@
 use crate::{App, Plugin};
-use bevy_utils::{tracing::debug, tracing::warn, HashMap};
+use bevy_utils::{tracing::warn, HashMap};
 use std::any::TypeId;
 
 /// Combines multiple [`Plugin`]s into a single unit.
@@ -127,12 +127,11 @@ impl PluginGroupBuilder {
 
     /// Consumes the [`PluginGroupBuilder`] and [builds](Plugin::build) the contained [`Plugin`]s
     /// in the order specified.
-    pub fn finish(self, app: &mut App) {
+    pub fn finish(mut self, app: &mut App) {
         for ty in &self.order {
-            if let Some(entry) = self.plugins.get(ty) {
+            if let Some(entry) = self.plugins.remove(ty) {
                 if entry.enabled {
-                    debug!("added plugin: {}", entry.plugin.name());
-                    entry.plugin.build(app);
+                    app.add_boxed_plugin(entry.plugin);
                 }
             }
         }
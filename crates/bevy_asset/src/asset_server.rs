@@ -1,17 +1,32 @@
+#[cfg(feature = "filesystem_watcher")]
+use crate::path::get_hasher;
 use crate::{
-    path::{AssetPath, AssetPathId, SourcePathId},
+    path::{is_valid_source_name, AssetPath, AssetPathId, SourcePathId},
+    progress::BytesProgress,
     Asset, AssetIo, AssetIoError, AssetLifecycle, AssetLifecycleChannel, AssetLifecycleEvent,
-    AssetLoader, Assets, Handle, HandleId, HandleUntyped, LabelId, LoadContext, LoadState,
-    RefChange, RefChangeChannel, SourceInfo, SourceMeta,
+    AssetLoader, AssetMetaError, AssetMetaFile, AssetReader, AssetSaver, Assets,
+    ErasedLoaderSettings, Handle, HandleId, HandleUntyped, LabelId, LoadContext, LoadState,
+    LoaderSettings, LoadingProgress, RefChange, RefChangeChannel, SourceInfo, SourceMeta,
 };
 use anyhow::Result;
 use bevy_ecs::system::{Res, ResMut};
 use bevy_log::warn;
+use bevy_reflect::TypeUuid;
 use bevy_tasks::IoTaskPool;
-use bevy_utils::{Entry, HashMap, Uuid};
+use bevy_utils::{Entry, HashMap, HashSet, Uuid};
 use crossbeam_channel::TryRecvError;
 use parking_lot::{Mutex, RwLock};
-use std::{path::Path, sync::Arc};
+#[cfg(feature = "filesystem_watcher")]
+use std::path::PathBuf;
+use std::{
+    any::Any,
+    io::{ErrorKind, Read},
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 use thiserror::Error;
 
 /// Errors that occur while loading assets with an `AssetServer`.
@@ -41,6 +56,35 @@ pub enum AssetServerError {
     /// Encountered an error while reading an asset from disk.
     #[error("encountered an error while reading an asset: {0}")]
     AssetIoError(#[from] AssetIoError),
+
+    /// No asset source was added with the specified name.
+    #[error("no asset source named `{0}`")]
+    MissingAssetSource(String),
+
+    /// The `.meta` file of an asset is invalid.
+    #[error("encountered an error while reading the meta file of an asset: {0}")]
+    AssetMetaError(#[from] AssetMetaError),
+
+    /// No asset saver was found for the type of the asset and the specified extensions.
+    #[error("no `AssetSaver` found for `{type_name}`{}", format_missing_asset_ext(.extensions))]
+    MissingAssetSaver {
+        /// The type of the asset to save.
+        type_name: &'static str,
+        /// The list of extensions detected on the path to save to.
+        extensions: Vec<String>,
+    },
+
+    /// Encountered an error while saving an asset.
+    #[error("encountered an error while saving an asset: {0}")]
+    AssetSaverError(anyhow::Error),
+
+    /// Assets can only be saved to paths without a label.
+    #[error("cannot save an asset to the labeled path {0:?}")]
+    SaveToLabeledPath(AssetPath<'static>),
+
+    /// The asset to save is not in its [`Assets`] collection.
+    #[error("the asset to save is not loaded")]
+    SavedAssetNotLoaded,
 }
 
 fn format_missing_asset_ext(exts: &[String]) -> String {
@@ -55,6 +99,19 @@ fn format_missing_asset_ext(exts: &[String]) -> String {
     }
 }
 
+/// An asset of an [`Assets`] collection, as listed by [`AssetServer::get_live_assets`].
+#[derive(Debug, Clone)]
+pub struct LiveAsset {
+    /// The handle id of the asset.
+    pub id: HandleId,
+    /// The path the asset was loaded from, if any.
+    pub path: Option<AssetPath<'static>>,
+    /// The number of strong handles keeping the asset loaded.
+    pub strong_handles: usize,
+    /// The estimated memory used by the asset, in bytes.
+    pub size_hint: usize,
+}
+
 #[derive(Default)]
 pub(crate) struct AssetRefCounter {
     pub(crate) channel: Arc<RefChangeChannel>,
@@ -66,13 +123,30 @@ pub(crate) struct AssetRefCounter {
 ///
 /// [`AssetServer`] is the public API for interacting with the asset server.
 pub struct AssetServerInternal {
-    pub(crate) asset_io: Box<dyn AssetIo>,
+    pub(crate) asset_io: Arc<dyn AssetIo>,
+    pub(crate) sources: RwLock<HashMap<String, Arc<dyn AssetIo>>>,
     pub(crate) asset_ref_counter: AssetRefCounter,
     pub(crate) asset_sources: Arc<RwLock<HashMap<SourcePathId, SourceInfo>>>,
+    /// The sources depending on each source, the reverse of [`SourceInfo::dependencies`].
+    ///
+    /// Always locked after `asset_sources`.
+    dependents: RwLock<HashMap<SourcePathId, HashSet<SourcePathId>>>,
     pub(crate) asset_lifecycles: Arc<RwLock<HashMap<Uuid, Box<dyn AssetLifecycle>>>>,
     loaders: RwLock<Vec<Arc<dyn AssetLoader>>>,
+    /// Whether the default source holds processed assets, read along with their `.meta` file.
+    processed: AtomicBool,
     extension_to_loader_index: RwLock<HashMap<String, usize>>,
     handle_to_path: Arc<RwLock<HashMap<HandleId, AssetPath<'static>>>>,
+    /// Loaded assets, by type, that wait for their dependencies to send an
+    /// [`AssetEvent::LoadedWithDependencies`](crate::AssetEvent::LoadedWithDependencies).
+    awaiting_dependencies: Mutex<HashMap<Uuid, HashSet<HandleId>>>,
+    /// Savers by asset type and extension, each an `Arc<dyn AssetSaver<Asset = T>>`.
+    savers: RwLock<HashMap<(Uuid, String), Box<dyn Any + Send + Sync>>>,
+    /// Assets queued up by [`AssetServer::save`], by asset type.
+    queued_saves: Mutex<HashMap<Uuid, Vec<(HandleId, AssetPath<'static>)>>>,
+    /// The hashes of the files written by [`AssetServer::save`], to not reload them.
+    #[cfg(feature = "filesystem_watcher")]
+    saved_files: Mutex<HashMap<(Option<String>, PathBuf), u64>>,
 }
 
 /// Loads assets from the filesystem in the background.
@@ -118,21 +192,86 @@ impl AssetServer {
         AssetServer {
             server: Arc::new(AssetServerInternal {
                 loaders: Default::default(),
+                processed: AtomicBool::new(false),
                 extension_to_loader_index: Default::default(),
                 asset_sources: Default::default(),
+                dependents: Default::default(),
                 asset_ref_counter: Default::default(),
                 handle_to_path: Default::default(),
+                awaiting_dependencies: Default::default(),
+                savers: Default::default(),
+                queued_saves: Default::default(),
+                #[cfg(feature = "filesystem_watcher")]
+                saved_files: Default::default(),
                 asset_lifecycles: Default::default(),
-                asset_io,
+                asset_io: asset_io.into(),
+                sources: Default::default(),
             }),
         }
     }
 
     /// Returns the associated asset I/O.
+    ///
+    /// This is the I/O of the default asset source, which loads the asset paths without a
+    /// `source://` prefix.
     pub fn asset_io(&self) -> &dyn AssetIo {
         &*self.server.asset_io
     }
 
+    /// Adds a named asset source, whose assets are loaded through `asset_io`.
+    ///
+    /// Assets are loaded from the source with paths of the `source://path#label` form. If a
+    /// source with the same name was added before, it is replaced.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` is not a [valid source name](crate::is_valid_source_name), as paths in
+    /// the source could not be parsed.
+    ///
+    /// ```rust,no_run
+    /// # use bevy_asset::{AssetServer, FileAssetIo, Handle};
+    /// # #[derive(Debug, bevy_reflect::TypeUuid)]
+    /// # #[uuid = "00000000-0000-0000-0000-000000000000"]
+    /// # struct Image;
+    /// # fn load(asset_server: &AssetServer) {
+    /// asset_server.add_source("mods", FileAssetIo::new("mods", false));
+    /// let handle: Handle<Image> = asset_server.load("mods://textures/rock.png");
+    /// # }
+    /// ```
+    pub fn add_source<T: AssetIo>(&self, name: impl Into<String>, asset_io: T) {
+        self.add_boxed_source(name, Box::new(asset_io));
+    }
+
+    /// Adds a named asset source with a boxed asset I/O.
+    ///
+    /// See [`add_source`](AssetServer::add_source).
+    pub fn add_boxed_source(&self, name: impl Into<String>, asset_io: Box<dyn AssetIo>) {
+        let name = name.into();
+        assert!(
+            is_valid_source_name(&name),
+            "`{}` is not a valid asset source name",
+            name
+        );
+        self.server.sources.write().insert(name, asset_io.into());
+    }
+
+    /// Returns the asset I/O of the named asset source, or of the default source if `None`.
+    pub fn get_source_io(
+        &self,
+        source: Option<&str>,
+    ) -> Result<Arc<dyn AssetIo>, AssetServerError> {
+        match source {
+            Some(name) => self
+                .server
+                .sources
+                .read()
+                .get(name)
+                .cloned()
+                .ok_or_else(|| AssetServerError::MissingAssetSource(name.to_string())),
+            None => Ok(self.server.asset_io.clone()),
+        }
+    }
+
     pub(crate) fn register_asset_type<T: Asset>(&self) -> Assets<T> {
         if self
             .server
@@ -169,13 +308,192 @@ impl AssetServer {
         loaders.push(Arc::new(loader));
     }
 
+    /// Adds the provided asset saver to the server.
+    ///
+    /// If `saver` has one or more supported extensions in conflict with savers of the same asset
+    /// type that came before it, it will replace them.
+    pub fn add_saver<T>(&self, saver: T)
+    where
+        T: AssetSaver,
+    {
+        let saver: Arc<dyn AssetSaver<Asset = T::Asset>> = Arc::new(saver);
+        let mut savers = self.server.savers.write();
+        for extension in saver.extensions() {
+            savers.insert(
+                (<T::Asset as TypeUuid>::TYPE_UUID, extension.to_string()),
+                Box::new(saver.clone()),
+            );
+        }
+    }
+
+    fn get_path_asset_saver<T: Asset>(
+        &self,
+        path: &Path,
+    ) -> Result<Arc<dyn AssetSaver<Asset = T>>, AssetServerError> {
+        let file_name = path
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .map(|file_name| file_name.to_lowercase())
+            .unwrap_or_default();
+
+        let savers = self.server.savers.read();
+        let mut exts = Vec::new();
+        let mut ext = file_name.as_str();
+        while let Some(idx) = ext.find('.') {
+            ext = &ext[idx + 1..];
+            exts.push(ext);
+            if let Some(saver) = savers.get(&(T::TYPE_UUID, ext.to_string())) {
+                return Ok(saver
+                    .downcast_ref::<Arc<dyn AssetSaver<Asset = T>>>()
+                    .expect("savers are stored by the type of their asset")
+                    .clone());
+            }
+        }
+        Err(AssetServerError::MissingAssetSaver {
+            type_name: std::any::type_name::<T>(),
+            extensions: exts.into_iter().map(String::from).collect(),
+        })
+    }
+
+    /// Queues the asset of the provided handle to be saved to the provided path, with the
+    /// [`AssetSaver`] of its type for the extension of the path.
+    ///
+    /// The path may be in a named asset source, as in `"mods://levels/1.scn.ron"`, whose
+    /// [`AssetIo`] must be writable. The asset is serialized at the end of the frame, and written
+    /// in the background. Saving a file to a [`FileAssetIo`](crate::FileAssetIo) source does not
+    /// hot-reload it, while other [`AssetIo`] implementations may still report it as changed.
+    pub fn save<'a, T: Asset>(&self, handle: &Handle<T>, path: impl Into<AssetPath<'a>>) {
+        self.server
+            .queued_saves
+            .lock()
+            .entry(T::TYPE_UUID)
+            .or_default()
+            .push((handle.id, path.into().to_owned()));
+    }
+
+    pub(crate) fn save_queued_assets<T: Asset>(&self, assets: &Assets<T>) {
+        let queued_saves = match self.server.queued_saves.lock().remove(&T::TYPE_UUID) {
+            Some(queued_saves) => queued_saves,
+            None => return,
+        };
+        for (handle_id, asset_path) in queued_saves {
+            let bytes = match assets.get(&Handle::weak(handle_id)) {
+                Some(asset) => self.serialize_asset(asset, &asset_path),
+                None => Err(AssetServerError::SavedAssetNotLoaded),
+            };
+            let bytes = match bytes {
+                Ok(bytes) => bytes,
+                Err(err) => {
+                    warn!("failed to save {:?}: {}", asset_path, err);
+                    continue;
+                }
+            };
+            let server = self.clone();
+            IoTaskPool::get()
+                .spawn(async move {
+                    if let Err(err) = server.write_asset(&asset_path, bytes).await {
+                        warn!("failed to save {:?}: {}", asset_path, err);
+                    }
+                })
+                .detach();
+        }
+    }
+
+    fn serialize_asset<T: Asset>(
+        &self,
+        asset: &T,
+        asset_path: &AssetPath,
+    ) -> Result<Vec<u8>, AssetServerError> {
+        if asset_path.label().is_some() {
+            return Err(AssetServerError::SaveToLabeledPath(asset_path.to_owned()));
+        }
+        self.get_path_asset_saver::<T>(asset_path.path())?
+            .save(asset)
+            .map_err(AssetServerError::AssetSaverError)
+    }
+
+    async fn write_asset(
+        &self,
+        asset_path: &AssetPath<'_>,
+        bytes: Vec<u8>,
+    ) -> Result<(), AssetServerError> {
+        let asset_io = self.get_source_io(asset_path.source())?;
+        // recorded before writing, as the watcher may see the change before the write returns
+        #[cfg(feature = "filesystem_watcher")]
+        let key = (
+            asset_path.source().map(ToString::to_string),
+            asset_path.path().to_owned(),
+        );
+        #[cfg(feature = "filesystem_watcher")]
+        self.server
+            .saved_files
+            .lock()
+            .insert(key.clone(), hash_content(&bytes));
+        let result = asset_io.write_path(asset_path.path(), bytes).await;
+        // a failed write must not hide the next change of the file
+        #[cfg(feature = "filesystem_watcher")]
+        if result.is_err() {
+            self.server.saved_files.lock().remove(&key);
+        }
+        Ok(result?)
+    }
+
+    /// Returns `true` if the file at `path` was written by [`AssetServer::save`] and was not
+    /// changed since, in which case there is no need to reload it.
+    ///
+    /// Only the watcher of [`FileAssetIo`](crate::FileAssetIo) checks this, other [`AssetIo`]
+    /// implementations report their own changes.
+    #[cfg(feature = "filesystem_watcher")]
+    pub(crate) fn is_saved_content(
+        &self,
+        source: Option<&str>,
+        path: &Path,
+        read_content: impl FnOnce() -> Option<Vec<u8>>,
+    ) -> bool {
+        let mut saved_files = self.server.saved_files.lock();
+        let key = (source.map(ToString::to_string), path.to_owned());
+        let hash = match saved_files.get(&key) {
+            Some(hash) => *hash,
+            None => return false,
+        };
+        if read_content().map_or(false, |bytes| hash_content(&bytes) == hash) {
+            true
+        } else {
+            saved_files.remove(&key);
+            false
+        }
+    }
+
     /// Enable watching of the filesystem for changes, if support is available, starting from after
     /// the point of calling this function.
+    ///
+    /// Changes are watched in every asset source.
     pub fn watch_for_changes(&self) -> Result<(), AssetServerError> {
         self.asset_io().watch_for_changes()?;
+        for asset_io in self.server.sources.read().values() {
+            asset_io.watch_for_changes()?;
+        }
         Ok(())
     }
 
+    /// Sets whether the default asset source holds the outputs of an
+    /// [`AssetPipeline`](crate::AssetPipeline), which are loaded with the settings of their
+    /// [`.meta` file](crate::AssetMetaFile).
+    ///
+    /// The `AssetPlugin` enables it in [`AssetServerMode::Processed`](crate::AssetServerMode).
+    /// Otherwise `.meta` files are not read, which spares a request per asset with network
+    /// asset I/O.
+    pub fn set_processed(&self, processed: bool) {
+        self.server.processed.store(processed, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if the default asset source holds processed assets.
+    ///
+    /// See [`AssetServer::set_processed`].
+    pub fn is_processed(&self) -> bool {
+        self.server.processed.load(Ordering::Relaxed)
+    }
+
     /// Gets a strong handle for an asset with the provided id.
     pub fn get_handle<T: Asset, I: Into<HandleId>>(&self, id: I) -> Handle<T> {
         let sender = self.server.asset_ref_counter.channel.sender.clone();
@@ -240,6 +558,41 @@ impl AssetServer {
             .cloned()
     }
 
+    /// Gets the number of strong handles to an asset from the provided handle.
+    ///
+    /// The count is updated when unused assets are marked by the [`free_unused_assets_system`],
+    /// once per frame.
+    pub fn get_strong_handle_count<H: Into<HandleId>>(&self, handle: H) -> usize {
+        self.server
+            .asset_ref_counter
+            .ref_counts
+            .read()
+            .get(&handle.into())
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Lists the assets of a collection, largest first, with their strong handle counts,
+    /// [estimated sizes](Assets::size_hint) and paths.
+    ///
+    /// This helps finding out which assets are leaked, for example by strong handles stored in
+    /// forgotten resources.
+    pub fn get_live_assets<T: Asset>(&self, assets: &Assets<T>) -> Vec<LiveAsset> {
+        let ref_counts = self.server.asset_ref_counter.ref_counts.read();
+        let handle_to_path = self.server.handle_to_path.read();
+        let mut live_assets: Vec<LiveAsset> = assets
+            .iter()
+            .map(|(id, asset)| LiveAsset {
+                id,
+                path: handle_to_path.get(&id).cloned(),
+                strong_handles: ref_counts.get(&id).copied().unwrap_or(0),
+                size_hint: assets.size_hint_of(asset),
+            })
+            .collect();
+        live_assets.sort_by_key(|live_asset| std::cmp::Reverse(live_asset.size_hint));
+        live_assets
+    }
+
     /// Gets the load state of an asset from the provided handle.
     pub fn get_load_state<H: Into<HandleId>>(&self, handle: H) -> LoadState {
         match handle.into() {
@@ -277,6 +630,90 @@ impl AssetServer {
         load_state
     }
 
+    /// Gets the load state of an asset along with the load state of all of its dependencies,
+    /// recursively.
+    ///
+    /// Dependencies are declared by loaders with [`LoadedAsset::with_dependency`](crate::LoadedAsset::with_dependency), so this method
+    /// only returns [`LoadState::Loaded`] once, for example, a glTF file and all the textures it
+    /// references are loaded. Once this happens, an [`AssetEvent::LoadedWithDependencies`](crate::AssetEvent::LoadedWithDependencies) is sent
+    /// for the asset.
+    ///
+    /// Assets with a [`HandleId::Id`] are added to their [`Assets`](crate::Assets) directly rather
+    /// than loaded, without dependencies, so they are always [`LoadState::Loaded`].
+    pub fn get_recursive_dependency_load_state<H: Into<HandleId>>(&self, handle: H) -> LoadState {
+        match handle.into() {
+            HandleId::AssetPathId(id) => {
+                let asset_sources = self.server.asset_sources.read();
+                let mut visited = HashSet::default();
+                visited.insert(id.source_path_id());
+                recursive_dependency_load_state(&asset_sources, id.source_path_id(), &mut visited)
+            }
+            HandleId::Id(_, _) => LoadState::Loaded,
+        }
+    }
+
+    /// Gets the loading progress of an asset and its recursive dependencies.
+    ///
+    /// See [`get_group_loading_progress`](AssetServer::get_group_loading_progress).
+    pub fn get_loading_progress<H: Into<HandleId>>(&self, handle: H) -> LoadingProgress {
+        self.get_group_loading_progress([handle.into()])
+    }
+
+    /// Gets the loading progress of a group of assets and their recursive dependencies, for
+    /// example to display a loading screen.
+    ///
+    /// Bytes are reported while large files are read when the [`AssetIo`] of their source
+    /// supports [streaming](AssetIo::stream_path). Assets with a [`HandleId::Id`] are not loaded
+    /// from a path, so they count as loaded assets without bytes.
+    pub fn get_group_loading_progress(
+        &self,
+        handles: impl IntoIterator<Item = HandleId>,
+    ) -> LoadingProgress {
+        let asset_sources = self.server.asset_sources.read();
+        let mut queue = Vec::new();
+        let mut added_assets = HashSet::default();
+        for handle_id in handles {
+            match handle_id {
+                HandleId::AssetPathId(id) => queue.push(id.source_path_id()),
+                HandleId::Id(_, _) => {
+                    added_assets.insert(handle_id);
+                }
+            }
+        }
+        let mut visited = HashSet::default();
+        let mut progress = LoadingProgress {
+            loaded: added_assets.len(),
+            total: added_assets.len(),
+            bytes_total: Some(0),
+            ..Default::default()
+        };
+        while let Some(id) = queue.pop() {
+            if !visited.insert(id) {
+                continue;
+            }
+            progress.total += 1;
+            let source_info = match asset_sources.get(&id) {
+                Some(source_info) => source_info,
+                None => {
+                    progress.bytes_total = None;
+                    continue;
+                }
+            };
+            match source_info.load_state {
+                LoadState::Loaded => progress.loaded += 1,
+                LoadState::Failed => progress.failed += 1,
+                _ => {}
+            }
+            progress.bytes_read += source_info.bytes.read();
+            progress.bytes_total = progress
+                .bytes_total
+                .zip(source_info.bytes.total())
+                .map(|(lhs, rhs)| lhs + rhs);
+            queue.extend(source_info.dependencies.iter().copied());
+        }
+        progress
+    }
+
     /// Queues an [`Asset`] at the provided relative path for asynchronous loading.
     ///
     /// The absolute path to the asset is `"ROOT/ASSET_FOLDER_NAME/path"`. Its extension is then
@@ -305,16 +742,62 @@ impl AssetServer {
         self.load_untyped(path).typed()
     }
 
+    /// Queues an [`Asset`] at the provided relative path for asynchronous loading, with
+    /// [`LoaderSettings`] for its [`AssetLoader`].
+    ///
+    /// The settings start from their default value and are configured by `settings`. They take
+    /// precedence over the loader settings of the [`.meta` file](crate::AssetMetaFile) of the
+    /// asset.
+    ///
+    /// The settings are part of the identity of the loaded asset: loading the same path with
+    /// different settings, or without settings, results in different assets.
+    ///
+    /// ```rust,no_run
+    /// # use bevy_asset::{AssetServer, Handle};
+    /// # use serde::{Deserialize, Serialize};
+    /// # #[derive(Debug, bevy_reflect::TypeUuid)]
+    /// # #[uuid = "00000000-0000-0000-0000-000000000000"]
+    /// # struct Image;
+    /// # #[derive(Debug, Clone, Default, Hash, Serialize, Deserialize)]
+    /// # struct ImageLoaderSettings { is_srgb: bool }
+    /// # fn load(asset_server: &AssetServer) {
+    /// let normal_map: Handle<Image> = asset_server
+    ///     .load_with_settings::<Image, ImageLoaderSettings>("normal.png", |settings| {
+    ///         settings.is_srgb = false;
+    ///     });
+    /// # }
+    /// ```
+    ///
+    /// See [`load`](AssetServer::load).
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
+    pub fn load_with_settings<'a, T: Asset, S: LoaderSettings>(
+        &self,
+        path: impl Into<AssetPath<'a>>,
+        settings: impl FnOnce(&mut S),
+    ) -> Handle<T> {
+        let mut value = S::default();
+        settings(&mut value);
+        let handle_id = self.load_untracked_with_settings(
+            path.into(),
+            false,
+            Some(ErasedLoaderSettings::new(value)),
+        );
+        self.get_handle(handle_id)
+    }
+
     async fn load_async(
         &self,
         asset_path: AssetPath<'_>,
         force: bool,
+        settings: Option<ErasedLoaderSettings>,
     ) -> Result<AssetPathId, AssetServerError> {
-        let asset_path_id: AssetPathId = asset_path.get_id();
+        let asset_path_id: AssetPathId = asset_path
+            .get_id()
+            .with_settings_hash(settings.as_ref().map(|settings| settings.hash));
 
         // load metadata and update source info. this is done in a scope to ensure we release the
         // locks before loading
-        let version = {
+        let (version, bytes_progress) = {
             let mut asset_sources = self.server.asset_sources.write();
             let source_info = match asset_sources.entry(asset_path_id.source_path_id()) {
                 Entry::Occupied(entry) => entry.into_mut(),
@@ -322,9 +805,13 @@ impl AssetServer {
                     asset_types: Default::default(),
                     committed_assets: Default::default(),
                     load_state: LoadState::NotLoaded,
+                    dependencies: Default::default(),
                     meta: None,
+                    source: asset_path.source().map(ToString::to_string),
                     path: asset_path.path().to_owned(),
                     version: 0,
+                    settings: settings.clone(),
+                    bytes: Default::default(),
                 }),
             };
 
@@ -342,7 +829,8 @@ impl AssetServer {
             source_info.committed_assets.clear();
             source_info.version += 1;
             source_info.meta = None;
-            source_info.version
+            source_info.bytes = Default::default();
+            (source_info.version, source_info.bytes.clone())
         };
 
         let set_asset_failed = || {
@@ -353,8 +841,42 @@ impl AssetServer {
             source_info.load_state = LoadState::Failed;
         };
 
-        // get the according asset loader
-        let asset_loader = match self.get_path_asset_loader(asset_path.path()) {
+        let asset_io = match self.get_source_io(asset_path.source()) {
+            Ok(asset_io) => asset_io,
+            Err(err) => {
+                set_asset_failed();
+                return Err(err);
+            }
+        };
+
+        // read the `.meta` file of the asset, if there is one. only processed assets have them,
+        // and named sources are never processed
+        let meta = if asset_path.source().is_none() && self.is_processed() {
+            let meta_path = AssetMetaFile::path_for(asset_path.path());
+            match asset_io.load_path(&meta_path).await {
+                Ok(bytes) => match AssetMetaFile::from_bytes(&bytes) {
+                    Ok(meta) => Some(meta),
+                    Err(err) => {
+                        set_asset_failed();
+                        return Err(AssetServerError::AssetMetaError(err));
+                    }
+                },
+                Err(AssetIoError::NotFound(_)) => None,
+                Err(err) => {
+                    set_asset_failed();
+                    return Err(AssetServerError::AssetIoError(err));
+                }
+            }
+        } else {
+            None
+        };
+
+        // get the according asset loader, which the `.meta` file can override
+        let asset_loader = match meta.as_ref().and_then(|meta| meta.loader.as_deref()) {
+            Some(extension) => self.get_asset_loader(extension),
+            None => self.get_path_asset_loader(asset_path.path()),
+        };
+        let asset_loader = match asset_loader {
             Ok(loader) => loader,
             Err(err) => {
                 set_asset_failed();
@@ -363,7 +885,7 @@ impl AssetServer {
         };
 
         // load the asset bytes
-        let bytes = match self.asset_io().load_path(asset_path.path()).await {
+        let bytes = match read_asset_bytes(&*asset_io, asset_path.path(), &bytes_progress).await {
             Ok(bytes) => bytes,
             Err(err) => {
                 set_asset_failed();
@@ -373,10 +895,12 @@ impl AssetServer {
 
         // load the asset source using the corresponding AssetLoader
         let mut load_context = LoadContext::new(
-            asset_path.path(),
+            &asset_path,
             &self.server.asset_ref_counter.channel,
-            self.asset_io(),
+            &*asset_io,
             version,
+            settings,
+            meta.and_then(|meta| meta.loader_settings),
         );
 
         if let Err(err) = asset_loader
@@ -405,6 +929,15 @@ impl AssetServer {
 
         // reset relevant SourceInfo fields
         source_info.committed_assets.clear();
+        let mut dependents = self.server.dependents.write();
+        for dependency in source_info.dependencies.drain() {
+            if let Some(dependency_dependents) = dependents.get_mut(&dependency) {
+                dependency_dependents.remove(&asset_path_id.source_path_id());
+                if dependency_dependents.is_empty() {
+                    dependents.remove(&dependency);
+                }
+            }
+        }
         // TODO: queue free old assets
         source_info.asset_types.clear();
 
@@ -418,13 +951,22 @@ impl AssetServer {
             let type_uuid = loaded_asset.value.as_ref().unwrap().type_uuid();
             source_info.asset_types.insert(label_id, type_uuid);
             for dependency in &loaded_asset.dependencies {
-                self.load_untracked(dependency.clone(), false);
+                // dependencies without a source are loaded from the source of this asset
+                let dependency = dependency.clone().or_source(asset_path.source());
+                let dependency_id = dependency.get_id().source_path_id();
+                if dependency_id != asset_path_id.source_path_id() {
+                    source_info.dependencies.insert(dependency_id);
+                    dependents
+                        .entry(dependency_id)
+                        .or_default()
+                        .insert(asset_path_id.source_path_id());
+                }
+                self.load_untracked(dependency, false);
             }
         }
+        drop(dependents);
 
-        self.asset_io()
-            .watch_path_for_changes(asset_path.path())
-            .unwrap();
+        asset_io.watch_path_for_changes(asset_path.path()).unwrap();
         self.create_assets_in_load_context(&mut load_context);
         Ok(asset_path_id)
     }
@@ -442,29 +984,92 @@ impl AssetServer {
     ///
     /// This is useful for custom hot-reloading or for supporting `watch_for_changes`
     /// in custom [`AssetIo`] implementations.
+    ///
+    /// The loads of the asset with different [`LoaderSettings`] are reloaded as well.
     pub fn reload_asset<'a, P: Into<AssetPath<'a>>>(&self, path: P) {
-        self.load_untracked(path.into(), true);
+        let path = path.into();
+        self.reload_path(path.source(), path.path());
     }
 
     pub(crate) fn load_untracked(&self, asset_path: AssetPath<'_>, force: bool) -> HandleId {
+        self.load_untracked_with_settings(asset_path, force, None)
+    }
+
+    fn load_untracked_with_settings(
+        &self,
+        asset_path: AssetPath<'_>,
+        force: bool,
+        settings: Option<ErasedLoaderSettings>,
+    ) -> HandleId {
+        let handle_id = asset_path
+            .get_id()
+            .with_settings_hash(settings.as_ref().map(|settings| settings.hash))
+            .into();
+
         let server = self.clone();
         let owned_path = asset_path.to_owned();
         IoTaskPool::get()
             .spawn(async move {
-                if let Err(err) = server.load_async(owned_path, force).await {
+                if let Err(err) = server.load_async(owned_path, force, settings).await {
                     warn!("{}", err);
                 }
             })
             .detach();
 
-        let handle_id = asset_path.get_id().into();
         self.server
             .handle_to_path
             .write()
             .entry(handle_id)
             .or_insert_with(|| asset_path.to_owned());
 
-        asset_path.into()
+        handle_id
+    }
+
+    /// Reloads the asset source at `path` in the named asset source, along with each of its
+    /// loads with different [`LoaderSettings`] and every source that depends on it, recursively.
+    pub(crate) fn reload_path(&self, source: Option<&str>, path: &Path) {
+        let asset_path = AssetPath::from(path).or_source(source);
+        let mut reloads = Vec::new();
+        {
+            let asset_sources = self.server.asset_sources.read();
+            let mut reloaded: HashSet<SourcePathId> = asset_sources
+                .iter()
+                .filter(|(_, source_info)| {
+                    source_info.path == path && source_info.source.as_deref() == source
+                })
+                .map(|(id, source_info)| {
+                    if let Some(settings) = &source_info.settings {
+                        reloads.push((asset_path.to_owned(), Some(settings.clone())));
+                    }
+                    *id
+                })
+                .collect();
+            reloaded.insert(asset_path.get_id().source_path_id());
+
+            let dependents = self.server.dependents.read();
+            let mut queue: Vec<_> = reloaded.iter().copied().collect();
+            while let Some(id) = queue.pop() {
+                for dependent_id in dependents.get(&id).into_iter().flatten() {
+                    let source_info = match asset_sources.get(dependent_id) {
+                        Some(source_info) => source_info,
+                        None => continue,
+                    };
+                    if reloaded.insert(*dependent_id) {
+                        queue.push(*dependent_id);
+                        let mut dependent_path = AssetPath::new(source_info.path.clone(), None);
+                        if let Some(source) = &source_info.source {
+                            dependent_path = dependent_path.with_source(source.clone());
+                        }
+                        reloads.push((dependent_path, source_info.settings.clone()));
+                    }
+                }
+            }
+        }
+
+        self.load_untracked(asset_path, true);
+        for (asset_path, settings) in reloads {
+            self.load_untracked_with_settings(asset_path, true, settings);
+        }
     }
 
     /// Loads assets from the specified folder recursively.
@@ -480,28 +1085,57 @@ impl AssetServer {
         &self,
         path: P,
     ) -> Result<Vec<HandleUntyped>, AssetServerError> {
-        let path = path.as_ref();
-        if !self.asset_io().is_dir(path) {
+        let mut handles = Vec::new();
+        self.load_folder_recursive(self.asset_io(), None, path.as_ref(), &mut handles)?;
+        Ok(handles)
+    }
+
+    /// Loads assets from the specified folder of the named asset `source` recursively.
+    ///
+    /// # Errors
+    ///
+    /// - If no asset source was added with this name, it will fail with
+    /// [`AssetServerError::MissingAssetSource`].
+    /// - Otherwise, it fails like [`AssetServer::load_folder`].
+    #[must_use = "not using the returned strong handles may result in the unexpected release of the assets"]
+    pub fn load_folder_from_source<P: AsRef<Path>>(
+        &self,
+        source: &str,
+        path: P,
+    ) -> Result<Vec<HandleUntyped>, AssetServerError> {
+        let asset_io = self.get_source_io(Some(source))?;
+        let mut handles = Vec::new();
+        self.load_folder_recursive(&*asset_io, Some(source), path.as_ref(), &mut handles)?;
+        Ok(handles)
+    }
+
+    fn load_folder_recursive(
+        &self,
+        asset_io: &dyn AssetIo,
+        source: Option<&str>,
+        path: &Path,
+        handles: &mut Vec<HandleUntyped>,
+    ) -> Result<(), AssetServerError> {
+        if !asset_io.is_dir(path) {
             return Err(AssetServerError::AssetFolderNotADirectory(
                 path.to_str().unwrap().to_string(),
             ));
         }
 
-        let mut handles = Vec::new();
-        for child_path in self.asset_io().read_directory(path.as_ref())? {
-            if self.asset_io().is_dir(&child_path) {
-                handles.extend(self.load_folder(&child_path)?);
+        for child_path in asset_io.read_directory(path)? {
+            if asset_io.is_dir(&child_path) {
+                self.load_folder_recursive(asset_io, source, &child_path, handles)?;
             } else {
                 if self.get_path_asset_loader(&child_path).is_err() {
                     continue;
                 }
                 let handle =
-                    self.load_untyped(child_path.to_str().expect("Path should be a valid string."));
+                    self.load_untyped(AssetPath::from(child_path.as_path()).or_source(source));
                 handles.push(handle);
             }
         }
 
-        Ok(handles)
+        Ok(())
     }
 
     /// Frees unused assets, unloading them from memory.
@@ -561,15 +1195,22 @@ impl AssetServer {
 
     fn create_assets_in_load_context(&self, load_context: &mut LoadContext) {
         let asset_lifecycles = self.server.asset_lifecycles.read();
+        let settings_hash = load_context.settings.as_ref().map(|settings| settings.hash);
         for (label, asset) in &mut load_context.labeled_assets {
             let asset_value = asset
                 .value
                 .take()
                 .expect("Asset should exist at this point.");
             if let Some(asset_lifecycle) = asset_lifecycles.get(&asset_value.type_uuid()) {
-                let asset_path =
-                    AssetPath::new_ref(load_context.path, label.as_ref().map(|l| l.as_str()));
-                asset_lifecycle.create_asset(asset_path.into(), asset_value, load_context.version);
+                let asset_path_id = AssetPath::new_ref(load_context.path, label.as_deref())
+                    .or_source(load_context.source)
+                    .get_id()
+                    .with_settings_hash(settings_hash);
+                asset_lifecycle.create_asset(
+                    asset_path_id.into(),
+                    asset_value,
+                    load_context.version,
+                );
             } else {
                 panic!(
                     "Failed to find AssetLifecycle for label '{:?}', which has an asset type {} (UUID {:?}). \
@@ -588,6 +1229,13 @@ impl AssetServer {
         let asset_lifecycles = self.server.asset_lifecycles.read();
         let asset_lifecycle = asset_lifecycles.get(&T::TYPE_UUID).unwrap();
         let mut asset_sources_guard = None;
+        // taken out of the server to not hold its lock along with the lock of the sources
+        let mut awaiting_dependencies = self
+            .server
+            .awaiting_dependencies
+            .lock()
+            .remove(&T::TYPE_UUID)
+            .unwrap_or_default();
         let channel = asset_lifecycle
             .downcast_ref::<AssetLifecycleChannel<T>>()
             .unwrap();
@@ -610,6 +1258,7 @@ impl AssetServer {
                     }
 
                     assets.set_untracked(result.id, *result.asset);
+                    awaiting_dependencies.insert(result.id);
                 }
                 Ok(AssetLifecycleEvent::Free(handle_id)) => {
                     if let HandleId::AssetPathId(id) = handle_id {
@@ -620,6 +1269,7 @@ impl AssetServer {
                             source_info.load_state = LoadState::Unloaded;
                         }
                     }
+                    awaiting_dependencies.remove(&handle_id);
                     assets.remove(handle_id);
                 }
                 Err(TryRecvError::Empty) => {
@@ -628,7 +1278,97 @@ impl AssetServer {
                 Err(TryRecvError::Disconnected) => panic!("AssetChannel disconnected."),
             }
         }
+        drop(asset_sources_guard);
+
+        // send events for the assets whose dependencies finished loading
+        if awaiting_dependencies.is_empty() {
+            return;
+        }
+        awaiting_dependencies.retain(|handle_id| {
+            match self.get_recursive_dependency_load_state(*handle_id) {
+                LoadState::Loaded => {
+                    assets.send_loaded_with_dependencies(*handle_id);
+                    false
+                }
+                LoadState::Failed => false,
+                _ => true,
+            }
+        });
+        self.server
+            .awaiting_dependencies
+            .lock()
+            .insert(T::TYPE_UUID, awaiting_dependencies);
+    }
+}
+
+/// Reads the file at `path`, streaming it when supported to report the bytes read.
+async fn read_asset_bytes(
+    asset_io: &dyn AssetIo,
+    path: &Path,
+    progress: &BytesProgress,
+) -> Result<Vec<u8>, AssetIoError> {
+    let AssetReader { mut reader, len } = match asset_io.stream_path(path) {
+        Some(reader) => reader?,
+        None => {
+            let bytes = asset_io.load_path(path).await?;
+            progress.set_total(bytes.len() as u64);
+            progress.add_read(bytes.len() as u64);
+            return Ok(bytes);
+        }
+    };
+    if let Some(len) = len {
+        progress.set_total(len);
+    }
+    let mut bytes = Vec::with_capacity(len.unwrap_or_default() as usize);
+    let mut chunk = vec![0; 64 * 1024];
+    loop {
+        let read = match reader.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err.into()),
+        };
+        bytes.extend_from_slice(&chunk[..read]);
+        progress.add_read(read as u64);
+    }
+    progress.set_total(bytes.len() as u64);
+    Ok(bytes)
+}
+
+#[cfg(feature = "filesystem_watcher")]
+fn hash_content(bytes: &[u8]) -> u64 {
+    use std::hash::Hasher;
+    let mut hasher = get_hasher();
+    hasher.write(bytes);
+    hasher.finish()
+}
+
+fn recursive_dependency_load_state(
+    asset_sources: &HashMap<SourcePathId, SourceInfo>,
+    id: SourcePathId,
+    visited: &mut HashSet<SourcePathId>,
+) -> LoadState {
+    let source_info = match asset_sources.get(&id) {
+        Some(source_info) => source_info,
+        None => return LoadState::NotLoaded,
+    };
+    if source_info.load_state != LoadState::Loaded {
+        return source_info.load_state;
+    }
+
+    let mut load_state = LoadState::Loaded;
+    for dependency in &source_info.dependencies {
+        // dependency cycles are allowed, each source is only visited once
+        if !visited.insert(*dependency) {
+            continue;
+        }
+        match recursive_dependency_load_state(asset_sources, *dependency, visited) {
+            LoadState::Loaded => continue,
+            LoadState::Loading => load_state = LoadState::Loading,
+            load_state => return load_state,
+        }
     }
+    load_state
 }
 
 fn free_unused_assets_system_impl(asset_server: &AssetServer) {
@@ -700,6 +1440,63 @@ mod test {
         }
     }
 
+    #[derive(Debug, TypeUuid)]
+    #[uuid = "3b1cbb5c-8d0c-4b0b-9b4e-3c1f0bf0a8d2"]
+    struct ScaleAsset(u32);
+
+    #[derive(Debug, Clone, Default, Hash, serde::Serialize, serde::Deserialize)]
+    struct ScaleSettings {
+        scale: u32,
+    }
+
+    struct ScaleLoader;
+    impl AssetLoader for ScaleLoader {
+        fn load<'a>(
+            &'a self,
+            _: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            Box::pin(async move {
+                let settings = ctx.settings::<ScaleSettings>()?;
+                ctx.set_default_asset(LoadedAsset::new(ScaleAsset(settings.scale)));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["scale"]
+        }
+    }
+
+    struct FakeDependentLoader;
+    impl AssetLoader for FakeDependentLoader {
+        fn load<'a>(
+            &'a self,
+            _: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            ctx.set_default_asset(LoadedAsset::new(PngAsset).with_dependency("fake.png".into()));
+            Box::pin(async move { Ok(()) })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["dependent"]
+        }
+    }
+
+    struct ScaleSaver;
+    impl AssetSaver for ScaleSaver {
+        type Asset = ScaleAsset;
+
+        fn save(&self, asset: &ScaleAsset) -> Result<Vec<u8>, anyhow::Error> {
+            Ok(asset.0.to_string().into_bytes())
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["scale"]
+        }
+    }
+
     fn setup(asset_path: impl AsRef<Path>) -> AssetServer {
         use crate::FileAssetIo;
         IoTaskPool::init(Default::default);
@@ -798,7 +1595,7 @@ mod test {
         let path: AssetPath = "file.not-a-real-extension".into();
         let handle = asset_server.get_handle_untyped(path.get_id());
 
-        let err = futures_lite::future::block_on(asset_server.load_async(path.clone(), true))
+        let err = futures_lite::future::block_on(asset_server.load_async(path.clone(), true, None))
             .unwrap_err();
         assert!(match err {
             AssetServerError::MissingAssetLoader { extensions } => {
@@ -818,7 +1615,7 @@ mod test {
         let path: AssetPath = "an/invalid/path.png".into();
         let handle = asset_server.get_handle_untyped(path.get_id());
 
-        let err = futures_lite::future::block_on(asset_server.load_async(path.clone(), true))
+        let err = futures_lite::future::block_on(asset_server.load_async(path.clone(), true, None))
             .unwrap_err();
         assert!(matches!(err, AssetServerError::AssetIoError(_)));
 
@@ -834,7 +1631,7 @@ mod test {
         let path: AssetPath = "fake.fail".into();
         let handle = asset_server.get_handle_untyped(path.get_id());
 
-        let err = futures_lite::future::block_on(asset_server.load_async(path.clone(), true))
+        let err = futures_lite::future::block_on(asset_server.load_async(path.clone(), true, None))
             .unwrap_err();
         assert!(matches!(err, AssetServerError::AssetLoaderError(_)));
 
@@ -858,8 +1655,9 @@ mod test {
 
         fn load_asset(path: AssetPath, world: &World) -> HandleUntyped {
             let asset_server = world.resource::<AssetServer>();
-            let id = futures_lite::future::block_on(asset_server.load_async(path.clone(), true))
-                .unwrap();
+            let id =
+                futures_lite::future::block_on(asset_server.load_async(path.clone(), true, None))
+                    .unwrap();
             asset_server.get_handle_untyped(id)
         }
 
@@ -918,6 +1716,432 @@ mod test {
         assert!(get_asset(&handle, &app.world).is_some());
     }
 
+    #[test]
+    fn settings_are_part_of_asset_identity() {
+        let server = setup(".");
+        let plain: Handle<ScaleAsset> = server.load("file.scale");
+        let two: Handle<ScaleAsset> =
+            server.load_with_settings::<ScaleAsset, ScaleSettings>("file.scale", |s| s.scale = 2);
+        let other_two: Handle<ScaleAsset> =
+            server.load_with_settings::<ScaleAsset, ScaleSettings>("file.scale", |s| s.scale = 2);
+        let three: Handle<ScaleAsset> =
+            server.load_with_settings::<ScaleAsset, ScaleSettings>("file.scale", |s| s.scale = 3);
+
+        assert_ne!(plain.id, two.id);
+        assert_eq!(two.id, other_two.id);
+        assert_ne!(two.id, three.id);
+        assert_eq!(
+            server.get_handle_path(&three).unwrap().path(),
+            Path::new("file.scale")
+        );
+    }
+
+    #[test]
+    fn meta_files_are_only_read_when_processed() {
+        let dir = create_dir_and_file("file.scale");
+        std::fs::write(dir.path().join("file.scale.meta"), "not a meta file").unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(ScaleLoader);
+        let assets = asset_server.register_asset_type::<ScaleAsset>();
+
+        let mut app = App::new();
+        app.insert_resource(assets);
+        app.insert_resource(asset_server.clone());
+        app.add_system(update_asset_storage_system::<ScaleAsset>);
+
+        let id = futures_lite::future::block_on(asset_server.load_async(
+            "file.scale".into(),
+            true,
+            None,
+        ))
+        .unwrap();
+        let handle = asset_server.get_handle::<ScaleAsset, _>(id);
+        app.update();
+        let assets = app.world.resource::<Assets<ScaleAsset>>();
+        assert_eq!(assets.get(&handle).unwrap().0, 0);
+
+        asset_server.set_processed(true);
+        let result = futures_lite::future::block_on(asset_server.load_async(
+            "file.scale".into(),
+            true,
+            None,
+        ));
+        assert!(matches!(result, Err(AssetServerError::AssetMetaError(_))));
+    }
+
+    #[test]
+    fn call_site_settings_override_meta_settings() {
+        let dir = create_dir_and_file("file.scale");
+        std::fs::write(
+            dir.path().join("file.scale.meta"),
+            "(loader_settings: Some(\"(scale: 4)\"))",
+        )
+        .unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.set_processed(true);
+        asset_server.add_loader(ScaleLoader);
+        let assets = asset_server.register_asset_type::<ScaleAsset>();
+
+        let mut app = App::new();
+        app.insert_resource(assets);
+        app.insert_resource(asset_server.clone());
+        app.add_system(update_asset_storage_system::<ScaleAsset>);
+
+        let from_meta = futures_lite::future::block_on(asset_server.load_async(
+            "file.scale".into(),
+            true,
+            None,
+        ))
+        .unwrap();
+        let from_call_site = futures_lite::future::block_on(asset_server.load_async(
+            "file.scale".into(),
+            true,
+            Some(ErasedLoaderSettings::new(ScaleSettings { scale: 7 })),
+        ))
+        .unwrap();
+        let from_meta = asset_server.get_handle::<ScaleAsset, _>(from_meta);
+        let from_call_site = asset_server.get_handle::<ScaleAsset, _>(from_call_site);
+
+        app.update();
+        let assets = app.world.resource::<Assets<ScaleAsset>>();
+        assert_eq!(assets.get(&from_meta).unwrap().0, 4);
+        assert_eq!(assets.get(&from_call_site).unwrap().0, 7);
+        assert_eq!(
+            asset_server.get_load_state(&from_call_site),
+            LoadState::Loaded
+        );
+    }
+
+    #[test]
+    fn named_asset_sources() {
+        use crate::FileAssetIo;
+
+        let default_dir = tempfile::tempdir().unwrap();
+        let mods_dir = create_dir_and_file("fake.png");
+        std::fs::write(mods_dir.path().join("fake.dependent"), &[]).unwrap();
+        let asset_server = setup(default_dir.path());
+        asset_server.add_source("mods", FileAssetIo::new(mods_dir.path(), false));
+        asset_server.add_loader(FakePngLoader);
+        asset_server.add_loader(FakeDependentLoader);
+        let assets = asset_server.register_asset_type::<PngAsset>();
+
+        let mut app = App::new();
+        app.insert_resource(assets);
+        app.insert_resource(asset_server.clone());
+        app.add_system(update_asset_storage_system::<PngAsset>);
+
+        // the asset only exists in the `mods` source
+        let err =
+            futures_lite::future::block_on(asset_server.load_async("fake.png".into(), true, None))
+                .unwrap_err();
+        assert!(matches!(err, AssetServerError::AssetIoError(_)));
+
+        let err = futures_lite::future::block_on(asset_server.load_async(
+            "missing://fake.png".into(),
+            true,
+            None,
+        ))
+        .unwrap_err();
+        assert!(matches!(err, AssetServerError::MissingAssetSource(source) if source == "missing"));
+
+        // the dependency of the asset is resolved relative to its source
+        let id = futures_lite::future::block_on(asset_server.load_async(
+            "mods://fake.dependent".into(),
+            true,
+            None,
+        ))
+        .unwrap();
+        let dependency = AssetPath::from("mods://fake.png");
+        assert_ne!(dependency.get_id(), AssetPath::from("fake.png").get_id());
+        for _ in 0..1000 {
+            app.update();
+            if asset_server.get_load_state(dependency.get_id()) == LoadState::Loaded {
+                break;
+            }
+            std::thread::yield_now();
+        }
+        assert_eq!(asset_server.get_load_state(id), LoadState::Loaded);
+        assert_eq!(
+            asset_server.get_load_state(dependency.get_id()),
+            LoadState::Loaded
+        );
+    }
+
+    #[test]
+    fn recursive_dependency_load_state() {
+        use crate::{AssetEvent, MemoryAssetIo};
+        use bevy_ecs::event::{Events, ManualEventReader};
+
+        IoTaskPool::init(Default::default);
+        let asset_io = MemoryAssetIo::default();
+        asset_io.insert("fake.dependent", Vec::new());
+        let asset_server = AssetServer::new(asset_io.clone());
+        asset_server.add_loader(FakePngLoader);
+        asset_server.add_loader(FakeDependentLoader);
+        let assets = asset_server.register_asset_type::<PngAsset>();
+
+        let mut app = App::new();
+        app.insert_resource(assets);
+        app.insert_resource(asset_server.clone());
+        app.add_event::<AssetEvent<PngAsset>>();
+        app.add_system(update_asset_storage_system::<PngAsset>);
+        app.add_system(
+            Assets::<PngAsset>::asset_event_system.after(update_asset_storage_system::<PngAsset>),
+        );
+        let mut reader = ManualEventReader::<AssetEvent<PngAsset>>::default();
+        let mut update = |app: &mut App| {
+            app.update();
+            let events = app.world.resource::<Events<AssetEvent<PngAsset>>>();
+            reader
+                .iter(events)
+                .filter_map(|event| match event {
+                    AssetEvent::LoadedWithDependencies { handle } => Some(handle.id),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // the dependency is missing
+        let id = futures_lite::future::block_on(asset_server.load_async(
+            "fake.dependent".into(),
+            true,
+            None,
+        ))
+        .unwrap();
+        let dependency = AssetPath::from("fake.png").get_id();
+        for _ in 0..1000 {
+            update(&mut app);
+            if asset_server.get_load_state(dependency) == LoadState::Failed {
+                break;
+            }
+            std::thread::yield_now();
+        }
+        assert_eq!(asset_server.get_load_state(id), LoadState::Loaded);
+        assert_eq!(
+            asset_server.get_recursive_dependency_load_state(id),
+            LoadState::Failed
+        );
+
+        // reloading the dependency reloads its dependents
+        asset_io.insert("fake.png", Vec::new());
+        asset_server.reload_asset("fake.png");
+        let mut loaded_with_dependencies = Vec::new();
+        for _ in 0..1000 {
+            loaded_with_dependencies.extend(update(&mut app));
+            if loaded_with_dependencies.contains(&id.into()) {
+                break;
+            }
+            std::thread::yield_now();
+        }
+        assert!(loaded_with_dependencies.contains(&id.into()));
+        assert_eq!(
+            asset_server.get_recursive_dependency_load_state(id),
+            LoadState::Loaded
+        );
+        let asset_sources = asset_server.server.asset_sources.read();
+        assert_eq!(asset_sources[&id.source_path_id()].version, 2);
+        assert_eq!(
+            asset_sources[&id.source_path_id()].dependencies,
+            [dependency.source_path_id()].into_iter().collect()
+        );
+        // the reverse map is not duplicated by the reload
+        assert_eq!(
+            asset_server.server.dependents.read()[&dependency.source_path_id()],
+            [id.source_path_id()].into_iter().collect()
+        );
+
+        // assets added directly are never loading
+        let added = HandleId::random::<PngAsset>();
+        assert_eq!(
+            asset_server.get_recursive_dependency_load_state(added),
+            LoadState::Loaded
+        );
+        assert!(asset_server.get_loading_progress(added).is_loaded());
+    }
+
+    #[test]
+    fn loading_progress() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("fake.dependent"), &[0; 10]).unwrap();
+        std::fs::write(dir.path().join("fake.png"), &[0; 100_000]).unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakePngLoader);
+        asset_server.add_loader(FakeDependentLoader);
+        let assets = asset_server.register_asset_type::<PngAsset>();
+
+        let mut app = App::new();
+        app.insert_resource(assets);
+        app.insert_resource(asset_server.clone());
+        app.add_system(update_asset_storage_system::<PngAsset>);
+
+        let missing = asset_server.get_loading_progress(AssetPath::from("missing.png").get_id());
+        assert_eq!(missing.total, 1);
+        assert_eq!(missing.bytes_total, None);
+        assert!(!missing.is_finished());
+
+        let id = futures_lite::future::block_on(asset_server.load_async(
+            "fake.dependent".into(),
+            true,
+            None,
+        ))
+        .unwrap();
+        let dependency = AssetPath::from("fake.png").get_id();
+        for _ in 0..1000 {
+            app.update();
+            if asset_server.get_load_state(dependency) == LoadState::Loaded {
+                break;
+            }
+            std::thread::yield_now();
+        }
+        let progress = asset_server.get_loading_progress(id);
+        assert_eq!(
+            progress,
+            LoadingProgress {
+                loaded: 2,
+                failed: 0,
+                total: 2,
+                bytes_read: 100_010,
+                bytes_total: Some(100_010),
+            }
+        );
+        assert_eq!(progress.fraction(), 1.0);
+
+        // sources shared by the group are counted once
+        let group = asset_server.get_group_loading_progress([id.into(), dependency.into()]);
+        assert_eq!(group, progress);
+    }
+
+    #[test]
+    fn live_assets() {
+        let asset_server = setup(".");
+        let mut assets = asset_server.register_asset_type::<ScaleAsset>();
+        assets.set_size_hint(|asset| asset.0 as usize);
+        let small = assets.add(ScaleAsset(1));
+        let large = assets.add(ScaleAsset(100));
+        let _leaked = large.clone();
+        asset_server.mark_unused_assets();
+
+        let live_assets: Vec<_> = asset_server
+            .get_live_assets(&assets)
+            .into_iter()
+            .map(|live_asset| {
+                (
+                    live_asset.id,
+                    live_asset.strong_handles,
+                    live_asset.size_hint,
+                )
+            })
+            .collect();
+        assert_eq!(live_assets, vec![(large.id, 2, 100), (small.id, 1, 1)]);
+        assert_eq!(assets.total_size_hint(), 101);
+        assert_eq!(asset_server.get_strong_handle_count(&small), 1);
+    }
+
+    #[test]
+    fn save_asset() {
+        use crate::{save_assets_system, MemoryAssetIo};
+
+        IoTaskPool::init(Default::default);
+        let asset_io = MemoryAssetIo::default();
+        let asset_server = AssetServer::new(MemoryAssetIo::default());
+        asset_server.add_source("editor", asset_io.clone());
+        asset_server.add_saver(ScaleSaver);
+        let mut assets = asset_server.register_asset_type::<ScaleAsset>();
+        let handle = assets.add(ScaleAsset(3));
+
+        let mut app = App::new();
+        app.insert_resource(assets);
+        app.insert_resource(asset_server.clone());
+        app.add_system(save_assets_system::<ScaleAsset>);
+
+        asset_server.save(&handle, "editor://levels/saved.scale");
+        let path = Path::new("levels/saved.scale");
+        for _ in 0..1000 {
+            app.update();
+            if asset_io.is_file(path) {
+                break;
+            }
+            std::thread::yield_now();
+        }
+        assert_eq!(
+            futures_lite::future::block_on(asset_io.load_path(path)).unwrap(),
+            b"3"
+        );
+
+        assert!(matches!(
+            asset_server.serialize_asset(&ScaleAsset(3), &"saved.png".into()),
+            Err(AssetServerError::MissingAssetSaver { .. })
+        ));
+        assert!(matches!(
+            asset_server.serialize_asset(&ScaleAsset(3), &"saved.scale#Label".into()),
+            Err(AssetServerError::SaveToLabeledPath(_))
+        ));
+    }
+
+    #[cfg(feature = "filesystem_watcher")]
+    #[test]
+    fn saved_files_are_not_reloaded() {
+        let dir = tempfile::tempdir().unwrap();
+        let asset_server = setup(dir.path());
+        let path: AssetPath = "saved.scale".into();
+        futures_lite::future::block_on(asset_server.write_asset(&path, b"3".to_vec())).unwrap();
+        assert_eq!(std::fs::read(dir.path().join("saved.scale")).unwrap(), b"3");
+        assert!(asset_server.is_saved_content(None, path.path(), || Some(b"3".to_vec())));
+        assert!(!asset_server.is_saved_content(None, path.path(), || Some(b"4".to_vec())));
+        // once changed by another program, the file is reloaded again
+        assert!(!asset_server.is_saved_content(None, path.path(), || Some(b"3".to_vec())));
+
+        // a failed save is not recorded
+        let path: AssetPath = "saved.scale/nested.scale".into();
+        assert!(
+            futures_lite::future::block_on(asset_server.write_asset(&path, b"3".to_vec())).is_err()
+        );
+        assert!(!asset_server.is_saved_content(None, path.path(), || Some(b"3".to_vec())));
+    }
+
+    #[test]
+    fn load_folder_from_memory() {
+        use crate::MemoryAssetIo;
+
+        IoTaskPool::init(Default::default);
+        let asset_io = MemoryAssetIo::default();
+        asset_io.insert("textures/rock.png", Vec::new());
+        asset_io.insert("textures/terrain/grass.png", Vec::new());
+        asset_io.insert("textures/readme.txt", Vec::new());
+        let asset_server = AssetServer::new(asset_io.clone());
+        asset_server.add_source("embedded", asset_io);
+        asset_server.add_loader(FakePngLoader);
+
+        let mut paths: Vec<_> = asset_server
+            .load_folder("textures")
+            .unwrap()
+            .iter()
+            .map(|handle| asset_server.get_handle_path(handle).unwrap().to_owned())
+            .collect();
+        paths.sort_by(|a, b| a.path().cmp(b.path()));
+        assert_eq!(paths.len(), 2);
+        assert_eq!(paths[0].path(), Path::new("textures/rock.png"));
+        assert_eq!(paths[1].path(), Path::new("textures/terrain/grass.png"));
+
+        let handles = asset_server
+            .load_folder_from_source("embedded", "textures")
+            .unwrap();
+        assert_eq!(handles.len(), 2);
+        for handle in &handles {
+            let path = asset_server.get_handle_path(handle).unwrap();
+            assert_eq!(path.source(), Some("embedded"));
+        }
+
+        assert!(matches!(
+            asset_server.load_folder("textures/rock.png"),
+            Err(AssetServerError::AssetFolderNotADirectory(_))
+        ));
+        assert!(matches!(
+            asset_server.load_folder_from_source("missing", "textures"),
+            Err(AssetServerError::MissingAssetSource(_))
+        ));
+    }
+
     #[test]
     fn test_get_handle_path() {
         const PATH: &str = "path/file.png";
@@ -1,6 +1,6 @@
 use crate::{
-    update_asset_storage_system, Asset, AssetLoader, AssetServer, AssetStage, Handle, HandleId,
-    RefChange,
+    save_assets_system, update_asset_storage_system, Asset, AssetLoader, AssetSaver, AssetServer,
+    AssetStage, Handle, HandleId, RefChange,
 };
 use bevy_app::App;
 use bevy_ecs::{
@@ -23,6 +23,12 @@ pub enum AssetEvent<T: Asset> {
     Modified { handle: Handle<T> },
     #[allow(missing_docs)]
     Removed { handle: Handle<T> },
+    /// Sent by the [`AssetServer`] once an asset loaded from a path and all of its recursive
+    /// dependencies are loaded, including after the asset or one of its dependencies is reloaded.
+    ///
+    /// See [`AssetServer::get_recursive_dependency_load_state`].
+    #[allow(missing_docs)]
+    LoadedWithDependencies { handle: Handle<T> },
 }
 
 impl<T: Asset> Debug for AssetEvent<T> {
@@ -49,6 +55,13 @@ impl<T: Asset> Debug for AssetEvent<T> {
                 ))
                 .field("handle", &handle.id)
                 .finish(),
+            AssetEvent::LoadedWithDependencies { handle } => f
+                .debug_struct(&format!(
+                    "AssetEvent<{}>::LoadedWithDependencies",
+                    std::any::type_name::<T>()
+                ))
+                .field("handle", &handle.id)
+                .finish(),
         }
     }
 }
@@ -70,6 +83,7 @@ impl<T: Asset> Debug for AssetEvent<T> {
 pub struct Assets<T: Asset> {
     assets: HashMap<HandleId, T>,
     events: Events<AssetEvent<T>>,
+    size_hint: fn(&T) -> usize,
     pub(crate) ref_change_sender: Sender<RefChange>,
 }
 
@@ -78,10 +92,38 @@ impl<T: Asset> Assets<T> {
         Assets {
             assets: HashMap::default(),
             events: Events::default(),
+            size_hint: std::mem::size_of_val::<T>,
             ref_change_sender,
         }
     }
 
+    /// Sets the function estimating the memory used by an asset, in bytes.
+    ///
+    /// By default, only the size of the asset type itself is counted, and not the memory it
+    /// allocates. See [`AddAsset::register_asset_size_hint`].
+    ///
+    /// This is set per collection rather than with a `size_hint` method on [`Asset`], as `Asset`
+    /// has a blanket implementation for every type meeting its bounds, so asset types could not
+    /// override such a method.
+    pub fn set_size_hint(&mut self, size_hint: fn(&T) -> usize) {
+        self.size_hint = size_hint;
+    }
+
+    /// Estimates the memory used by the asset for the given handle, in bytes.
+    pub fn size_hint<H: Into<HandleId>>(&self, handle: H) -> Option<usize> {
+        self.assets.get(&handle.into()).map(self.size_hint)
+    }
+
+    /// Estimates the memory used by an asset, in bytes.
+    pub fn size_hint_of(&self, asset: &T) -> usize {
+        (self.size_hint)(asset)
+    }
+
+    /// Estimates the memory used by all assets in the collection, in bytes.
+    pub fn total_size_hint(&self) -> usize {
+        self.assets.values().map(self.size_hint).sum()
+    }
+
     /// Adds an asset to the collection, returning a Strong handle to that asset.
     ///
     /// # Events
@@ -224,6 +266,13 @@ impl<T: Asset> Assets<T> {
         asset
     }
 
+    /// Sends an [`AssetEvent::LoadedWithDependencies`] for the asset with the given handle.
+    pub(crate) fn send_loaded_with_dependencies(&mut self, id: HandleId) {
+        self.events.send(AssetEvent::LoadedWithDependencies {
+            handle: Handle::weak(id),
+        });
+    }
+
     /// Clears the inner asset map, removing all key-value pairs.
     ///
     /// Keeps the allocated memory for reuse.
@@ -289,6 +338,14 @@ pub trait AddAsset {
     where
         T: Asset;
 
+    /// Sets the function estimating the memory used by assets of type `T`, for diagnostics.
+    ///
+    /// This takes the place of an `Asset::size_hint` method, which the blanket implementation of
+    /// [`Asset`] would not let asset types override. See [`Assets::set_size_hint`].
+    fn register_asset_size_hint<T>(&mut self, size_hint: fn(&T) -> usize) -> &mut Self
+    where
+        T: Asset;
+
     /// Adds an asset loader `T` using default values.
     ///
     /// The default values may come from the `World` or from `T::default()`.
@@ -310,6 +367,35 @@ pub trait AddAsset {
     fn add_asset_loader<T>(&mut self, loader: T) -> &mut Self
     where
         T: AssetLoader;
+
+    /// Adds an asset saver `T` using default values.
+    ///
+    /// The default values may come from the `World` or from `T::default()`.
+    fn init_asset_saver<T>(&mut self) -> &mut Self
+    where
+        T: AssetSaver + FromWorld;
+
+    /// Adds the provided asset saver to the application.
+    ///
+    /// See [`AssetServer::save`].
+    fn add_asset_saver<T>(&mut self, saver: T) -> &mut Self
+    where
+        T: AssetSaver;
+
+    /// Adds a named asset source to the [`AssetServer`].
+    ///
+    /// See [`AssetServer::add_source`].
+    fn add_asset_source<T>(&mut self, name: impl Into<String>, asset_io: T) -> &mut Self
+    where
+        T: crate::AssetIo;
+
+    /// Adds the provided asset processor to the [`AssetPipeline`](crate::AssetPipeline).
+    ///
+    /// Does nothing unless assets are [processed](crate::AssetServerMode::Processed).
+    #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+    fn add_asset_processor<T>(&mut self, processor: T) -> &mut Self
+    where
+        T: crate::AssetProcessor;
 }
 
 impl AddAsset for App {
@@ -327,6 +413,7 @@ impl AddAsset for App {
 
         self.insert_resource(assets)
             .add_system_to_stage(AssetStage::AssetEvents, Assets::<T>::asset_event_system)
+            .add_system_to_stage(AssetStage::AssetEvents, save_assets_system::<T>)
             .add_system_to_stage(AssetStage::LoadAssets, update_asset_storage_system::<T>)
             .register_type::<Handle<T>>()
             .add_event::<AssetEvent<T>>()
@@ -348,6 +435,16 @@ impl AddAsset for App {
         self
     }
 
+    fn register_asset_size_hint<T>(&mut self, size_hint: fn(&T) -> usize) -> &mut Self
+    where
+        T: Asset,
+    {
+        self.world
+            .resource_mut::<Assets<T>>()
+            .set_size_hint(size_hint);
+        self
+    }
+
     fn init_asset_loader<T>(&mut self) -> &mut Self
     where
         T: AssetLoader + FromWorld,
@@ -377,6 +474,43 @@ impl AddAsset for App {
         self.world.resource_mut::<AssetServer>().add_loader(loader);
         self
     }
+
+    fn init_asset_saver<T>(&mut self) -> &mut Self
+    where
+        T: AssetSaver + FromWorld,
+    {
+        let result = T::from_world(&mut self.world);
+        self.add_asset_saver(result)
+    }
+
+    fn add_asset_saver<T>(&mut self, saver: T) -> &mut Self
+    where
+        T: AssetSaver,
+    {
+        self.world.resource_mut::<AssetServer>().add_saver(saver);
+        self
+    }
+
+    fn add_asset_source<T>(&mut self, name: impl Into<String>, asset_io: T) -> &mut Self
+    where
+        T: crate::AssetIo,
+    {
+        self.world
+            .resource::<AssetServer>()
+            .add_source(name, asset_io);
+        self
+    }
+
+    #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+    fn add_asset_processor<T>(&mut self, processor: T) -> &mut Self
+    where
+        T: crate::AssetProcessor,
+    {
+        if let Some(mut pipeline) = self.world.get_resource_mut::<crate::AssetPipeline>() {
+            pipeline.add_processor(processor);
+        }
+        self
+    }
 }
 
 /// Loads an internal asset.
@@ -78,6 +78,7 @@ impl Plugin for DebugAssetServerPlugin {
             .insert_resource(AssetServerSettings {
                 asset_folder: "crates".to_string(),
                 watch_for_changes: true,
+                ..Default::default()
             })
             .add_plugin(AssetPlugin);
         app.insert_non_send_resource(DebugAssetApp(debug_asset_app));
@@ -103,7 +104,7 @@ pub(crate) fn sync_debug_assets<T: Asset + Clone>(
     for changed in changed_shaders.iter_current_update_events() {
         let debug_handle = match changed {
             AssetEvent::Created { handle } | AssetEvent::Modified { handle } => handle,
-            AssetEvent::Removed { .. } => continue,
+            AssetEvent::Removed { .. } | AssetEvent::LoadedWithDependencies { .. } => continue,
         };
         if let Some(handle) = handle_map.handles.get(debug_handle) {
             if let Some(debug_asset) = debug_assets.get(debug_handle) {
@@ -1,16 +1,25 @@
-use crate::{Asset, Assets};
+use crate::{Asset, AssetServer, Assets};
 use bevy_app::prelude::*;
 use bevy_diagnostic::{Diagnostic, DiagnosticId, Diagnostics, MAX_DIAGNOSTIC_NAME_WIDTH};
 use bevy_ecs::system::{Res, ResMut};
+use bevy_log::warn;
+use bevy_utils::Uuid;
 
-/// Adds an asset count diagnostic to an [`App`] for assets of type `T`.
+/// Adds an asset count diagnostic to an [`App`] for assets of type `T`, along with a diagnostic
+/// of their [estimated size](Assets::size_hint).
+///
+/// With [`with_growth_warning`](AssetCountDiagnosticsPlugin::with_growth_warning), a warning
+/// listing the live assets is also logged when their count keeps growing, which usually means
+/// that handles are leaked.
 pub struct AssetCountDiagnosticsPlugin<T: Asset> {
+    growth_warning_frames: Option<usize>,
     marker: std::marker::PhantomData<T>,
 }
 
 impl<T: Asset> Default for AssetCountDiagnosticsPlugin<T> {
     fn default() -> Self {
         Self {
+            growth_warning_frames: None,
             marker: std::marker::PhantomData,
         }
     }
@@ -20,10 +29,33 @@ impl<T: Asset> Plugin for AssetCountDiagnosticsPlugin<T> {
     fn build(&self, app: &mut App) {
         app.add_startup_system(Self::setup_system)
             .add_system(Self::diagnostic_system);
+        if let Some(frames) = self.growth_warning_frames {
+            let mut growth = CountGrowth::new(frames);
+            app.add_system(
+                move |asset_server: Res<AssetServer>, assets: Res<Assets<T>>| {
+                    if growth.update(assets.len()) {
+                        Self::warn_growth(&asset_server, &assets, frames);
+                    }
+                },
+            );
+        }
     }
 }
 
 impl<T: Asset> AssetCountDiagnosticsPlugin<T> {
+    /// The number of live assets listed by the growth warning.
+    const LISTED_ASSETS: usize = 10;
+
+    /// Warns when the number of assets grew in `frames` frames without decreasing in between.
+    ///
+    /// Frames in which the number stays the same are not counted, so a single batch of loaded
+    /// assets does not trigger the warning.
+    #[must_use]
+    pub fn with_growth_warning(mut self, frames: usize) -> Self {
+        self.growth_warning_frames = Some(frames);
+        self
+    }
+
     /// Gets unique id of this diagnostic.
     ///
     /// The diagnostic id is the type uuid of `T`.
@@ -31,28 +63,150 @@ impl<T: Asset> AssetCountDiagnosticsPlugin<T> {
         DiagnosticId(T::TYPE_UUID)
     }
 
-    /// Registers the asset count diagnostic for the current application.
+    /// Gets unique id of the asset size diagnostic.
+    ///
+    /// The diagnostic id is derived from the type uuid of `T`.
+    pub fn size_diagnostic_id() -> DiagnosticId {
+        DiagnosticId(Uuid::from_u128(
+            T::TYPE_UUID.as_u128() ^ 0x5a1e_d1a6_0000_0000_0000_0000_0000_0000,
+        ))
+    }
+
+    /// Registers the asset count and size diagnostics for the current application.
     pub fn setup_system(mut diagnostics: ResMut<Diagnostics>) {
-        let asset_type_name = std::any::type_name::<T>();
-        let max_length = MAX_DIAGNOSTIC_NAME_WIDTH - "asset_count ".len();
         diagnostics.add(Diagnostic::new(
             Self::diagnostic_id(),
-            format!(
-                "asset_count {}",
-                if asset_type_name.len() > max_length {
-                    asset_type_name
-                        .split_at(asset_type_name.len() - max_length + 1)
-                        .1
-                } else {
-                    asset_type_name
-                }
-            ),
+            Self::diagnostic_name("asset_count"),
             20,
         ));
+        diagnostics.add(
+            Diagnostic::new(
+                Self::size_diagnostic_id(),
+                Self::diagnostic_name("asset_size"),
+                20,
+            )
+            .with_suffix("B"),
+        );
+    }
+
+    fn diagnostic_name(prefix: &str) -> String {
+        let asset_type_name = std::any::type_name::<T>();
+        let max_length = MAX_DIAGNOSTIC_NAME_WIDTH - prefix.len() - 1;
+        format!(
+            "{} {}",
+            prefix,
+            if asset_type_name.len() > max_length {
+                asset_type_name
+                    .split_at(asset_type_name.len() - max_length + 1)
+                    .1
+            } else {
+                asset_type_name
+            }
+        )
     }
 
-    /// Updates the asset count of `T` assets.
+    /// Updates the asset count and size of `T` assets.
     pub fn diagnostic_system(mut diagnostics: ResMut<Diagnostics>, assets: Res<Assets<T>>) {
         diagnostics.add_measurement(Self::diagnostic_id(), || assets.len() as f64);
+        diagnostics.add_measurement(Self::size_diagnostic_id(), || {
+            assets.total_size_hint() as f64
+        });
+    }
+
+    fn warn_growth(asset_server: &AssetServer, assets: &Assets<T>, frames: usize) {
+        let live_assets = asset_server.get_live_assets(assets);
+        let listed: Vec<String> = live_assets
+            .iter()
+            .take(Self::LISTED_ASSETS)
+            .map(|live_asset| {
+                format!(
+                    "\n  {:?} ({} strong handles, {} bytes) from {}",
+                    live_asset.id,
+                    live_asset.strong_handles,
+                    live_asset.size_hint,
+                    live_asset.path.as_ref().map_or_else(
+                        || "no path".to_string(),
+                        |path| format!("{:?}", path.path())
+                    ),
+                )
+            })
+            .collect();
+        warn!(
+            "the number of {} assets grew to {} over the last {} frames, they may be leaked. Largest assets:{}",
+            std::any::type_name::<T>(),
+            live_assets.len(),
+            frames,
+            listed.concat(),
+        );
+    }
+}
+
+/// Detects counts that grow in a number of frames without ever decreasing.
+struct CountGrowth {
+    frames: usize,
+    last: usize,
+    growing_frames: usize,
+}
+
+impl CountGrowth {
+    fn new(frames: usize) -> Self {
+        CountGrowth {
+            frames: frames.max(1),
+            last: 0,
+            growing_frames: 0,
+        }
+    }
+
+    /// Records the count of a frame, returning `true` once it grew in `frames` frames.
+    fn update(&mut self, count: usize) -> bool {
+        if count < self.last {
+            self.growing_frames = 0;
+        } else if count > self.last {
+            self.growing_frames += 1;
+        }
+        self.last = count;
+        if self.growing_frames >= self.frames {
+            self.growing_frames = 0;
+            return true;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_growth() {
+        let mut growth = CountGrowth::new(3);
+        // stable counts are not growing
+        assert!(!growth.update(0));
+        assert!(!growth.update(0));
+        assert!(!growth.update(0));
+        assert!(!growth.update(0));
+
+        // a single step is not a leak
+        let mut growth = CountGrowth::new(3);
+        assert!(!growth.update(5));
+        for _ in 0..10 {
+            assert!(!growth.update(5));
+        }
+
+        // a decrease resets the growth, while stable frames are not counted
+        let mut growth = CountGrowth::new(3);
+        assert!(!growth.update(1));
+        assert!(!growth.update(2));
+        assert!(!growth.update(1));
+        assert!(!growth.update(2));
+        assert!(!growth.update(2));
+        assert!(!growth.update(3));
+        assert!(growth.update(4));
+
+        // warnings are repeated only after further growth
+        assert!(!growth.update(4));
+        assert!(!growth.update(5));
+        assert!(!growth.update(6));
+        assert!(growth.update(7));
     }
 }
@@ -1,7 +1,9 @@
-use crate::{path::AssetPath, LabelId};
+use crate::{
+    loader::ErasedLoaderSettings, path::AssetPath, progress::BytesProgress, LabelId, SourcePathId,
+};
 use bevy_utils::{HashMap, HashSet, Uuid};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::{path::PathBuf, sync::Arc};
 
 /// Metadata for an asset source.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -26,6 +28,8 @@ pub struct AssetMeta {
 pub struct SourceInfo {
     /// Metadata for the source.
     pub meta: Option<SourceMeta>,
+    /// The name of the asset source the source is loaded from, or `None` for the default source.
+    pub source: Option<String>,
     /// The path of the source.
     pub path: PathBuf,
     /// A map of assets and their type identifiers.
@@ -34,8 +38,15 @@ pub struct SourceInfo {
     pub load_state: LoadState,
     /// A collection to track which assets were sent to their asset storages.
     pub committed_assets: HashSet<LabelId>,
+    /// The sources the assets of this source depend on, through
+    /// [`LoadedAsset::with_dependency`](crate::LoadedAsset::with_dependency).
+    pub dependencies: HashSet<SourcePathId>,
     /// Current version of the source.
     pub version: usize,
+    /// The loader settings the source was loaded with, if any.
+    pub(crate) settings: Option<ErasedLoaderSettings>,
+    /// The bytes read by the current load of the source.
+    pub(crate) bytes: Arc<BytesProgress>,
 }
 
 impl SourceInfo {
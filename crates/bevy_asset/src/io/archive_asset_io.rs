@@ -0,0 +1,793 @@
+use crate::{AssetIo, AssetIoError, FileType, Metadata, PROCESSOR_CACHE_FILE};
+use anyhow::Result;
+use bevy_utils::{BoxedFuture, HashMap, HashSet};
+use memmap2::Mmap;
+use std::{
+    collections::BTreeSet,
+    fs::File,
+    io::{self, Read, Write},
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
+use thiserror::Error;
+
+/// The extension of asset archives.
+pub const ARCHIVE_EXTENSION: &str = "bpak";
+
+const MAGIC: &[u8; 8] = b"BEVYPAK\0";
+const VERSION: u32 = 1;
+const HEADER_LEN: usize = MAGIC.len() + 4;
+const FOOTER_LEN: usize = 8 + 8 + MAGIC.len();
+
+/// Errors that occur while building or reading an [`Archive`].
+#[derive(Error, Debug)]
+pub enum ArchiveError {
+    /// Encountered an I/O error.
+    #[error("encountered an io error: {0}")]
+    Io(#[from] io::Error),
+    /// The data is not an asset archive.
+    #[error("not an asset archive")]
+    InvalidMagic,
+    /// The archive was built with an incompatible version of the format.
+    #[error("unsupported archive version {0}, expected {VERSION}")]
+    UnsupportedVersion(u32),
+    /// The index of the archive is truncated or invalid.
+    #[error("the archive index is corrupted")]
+    CorruptedIndex,
+    /// An entry does not match its content hash.
+    #[error("the content of {0:?} does not match its hash")]
+    HashMismatch(PathBuf),
+    /// An entry could not be decompressed.
+    #[error("failed to decompress {path:?}: {error}")]
+    Decompression {
+        /// The path of the entry.
+        path: PathBuf,
+        /// The decompression error.
+        error: String,
+    },
+}
+
+/// The compression of an entry in an [`Archive`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ArchiveCompression {
+    /// The entry is stored as is.
+    #[default]
+    None,
+    /// The entry is compressed with LZ4, which favors decompression speed.
+    Lz4,
+    /// The entry is compressed with Zstandard, which favors size.
+    Zstd,
+}
+
+impl ArchiveCompression {
+    /// The Zstandard compression level used for archives.
+    pub const ZSTD_LEVEL: i32 = 19;
+
+    /// The largest decompressed size of a byte of LZ4 data.
+    const LZ4_MAX_RATIO: u64 = 255;
+
+    fn to_u8(self) -> u8 {
+        match self {
+            ArchiveCompression::None => 0,
+            ArchiveCompression::Lz4 => 1,
+            ArchiveCompression::Zstd => 2,
+        }
+    }
+
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(ArchiveCompression::None),
+            1 => Some(ArchiveCompression::Lz4),
+            2 => Some(ArchiveCompression::Zstd),
+            _ => None,
+        }
+    }
+
+    fn compress(self, bytes: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            ArchiveCompression::None => Ok(bytes.to_vec()),
+            ArchiveCompression::Lz4 => Ok(lz4_flex::compress(bytes)),
+            ArchiveCompression::Zstd => zstd::bulk::compress(bytes, Self::ZSTD_LEVEL),
+        }
+    }
+
+    /// Returns `true` if `stored_len` bytes of data can decompress to `size` bytes, so that the
+    /// sizes of an untrusted index are not used to allocate more than the data can hold.
+    ///
+    /// Zstandard data has no such bound, and is decompressed without allocating its size ahead.
+    fn is_valid_size(self, stored_len: u64, size: u64) -> bool {
+        match self {
+            ArchiveCompression::None => size == stored_len,
+            ArchiveCompression::Lz4 => size <= stored_len.saturating_mul(Self::LZ4_MAX_RATIO),
+            ArchiveCompression::Zstd => usize::try_from(size).is_ok(),
+        }
+    }
+
+    fn decompress(self, bytes: &[u8], size: usize) -> Result<Vec<u8>, String> {
+        match self {
+            ArchiveCompression::None => Ok(bytes.to_vec()),
+            ArchiveCompression::Lz4 => {
+                lz4_flex::decompress(bytes, size).map_err(|error| error.to_string())
+            }
+            ArchiveCompression::Zstd => {
+                let mut decompressed = Vec::new();
+                zstd::stream::read::Decoder::with_buffer(bytes)
+                    .and_then(|decoder| {
+                        decoder.take(size as u64 + 1).read_to_end(&mut decompressed)
+                    })
+                    .map_err(|error| error.to_string())?;
+                if decompressed.len() != size {
+                    return Err(format!(
+                        "expected {} decompressed bytes, found more or less",
+                        size
+                    ));
+                }
+                Ok(decompressed)
+            }
+        }
+    }
+}
+
+/// An entry of the index of an [`Archive`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveEntry {
+    /// The offset of the stored data in the archive.
+    pub offset: u64,
+    /// The length of the stored data.
+    pub stored_len: u64,
+    /// The length of the data once decompressed.
+    pub size: u64,
+    /// The compression of the stored data.
+    pub compression: ArchiveCompression,
+    /// The hash of the decompressed data.
+    pub hash: u64,
+}
+
+/// Returns the content hash of `bytes`, as stored in the index of an [`Archive`].
+pub fn content_hash(bytes: &[u8]) -> u64 {
+    xxhash_rust::xxh3::xxh3_64(bytes)
+}
+
+/// Returns the path of an entry within an archive, with `/` separators and no `.` components.
+fn entry_path(path: &Path) -> String {
+    path.components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(name.to_string_lossy()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Builds an [`Archive`] from files.
+///
+/// Files with identical content are stored once. Entries whose compressed data is not smaller
+/// than their content are stored uncompressed.
+///
+/// ```
+/// # use bevy_asset::{Archive, ArchiveBuilder, ArchiveCompression};
+/// let mut builder = ArchiveBuilder::new(ArchiveCompression::Lz4);
+/// builder.add_file("levels/1.level", b"(size: 32)".to_vec());
+/// let mut bytes = Vec::new();
+/// builder.write(&mut bytes).unwrap();
+///
+/// let archive = Archive::from_bytes(bytes).unwrap();
+/// assert_eq!(archive.read("levels/1.level").unwrap().unwrap(), b"(size: 32)");
+/// ```
+#[derive(Debug, Default)]
+pub struct ArchiveBuilder {
+    compression: ArchiveCompression,
+    files: Vec<(String, Vec<u8>)>,
+}
+
+impl ArchiveBuilder {
+    /// Creates a builder that compresses entries with `compression`.
+    pub fn new(compression: ArchiveCompression) -> Self {
+        Self {
+            compression,
+            files: Vec::new(),
+        }
+    }
+
+    /// Adds a file at `path` in the archive.
+    pub fn add_file(&mut self, path: impl AsRef<Path>, bytes: Vec<u8>) -> &mut Self {
+        self.files.push((entry_path(path.as_ref()), bytes));
+        self
+    }
+
+    /// Adds all the files of the folder at `root` recursively, at their path relative to `root`.
+    ///
+    /// `.meta` files are kept, so that loader settings still apply, but the cache of the
+    /// [`AssetPipeline`](crate::AssetPipeline) is skipped.
+    pub fn add_folder(&mut self, root: impl AsRef<Path>) -> io::Result<&mut Self> {
+        let root = root.as_ref();
+        let mut folders = vec![root.to_path_buf()];
+        while let Some(folder) = folders.pop() {
+            for entry in std::fs::read_dir(&folder)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    folders.push(path);
+                } else if path
+                    .file_name()
+                    .map_or(false, |name| name == PROCESSOR_CACHE_FILE)
+                {
+                    continue;
+                } else {
+                    let bytes = std::fs::read(&path)?;
+                    let relative_path = path.strip_prefix(root).expect("path is within root");
+                    self.add_file(relative_path, bytes);
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    /// Returns the number of files added to the builder.
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Returns `true` if no files were added to the builder.
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Writes the archive.
+    ///
+    /// The archive starts with a header, followed by the data of the entries, their index and a
+    /// footer locating the index. All integers are little-endian.
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), ArchiveError> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        let mut offset = HEADER_LEN as u64;
+
+        let mut stored = HashMap::<u64, ArchiveEntry>::default();
+        let mut index = Vec::new();
+        let mut files: Vec<_> = self.files.iter().collect();
+        files.sort_by(|a, b| a.0.cmp(&b.0));
+        for (path, bytes) in files {
+            let hash = content_hash(bytes);
+            let entry = match stored.get(&hash) {
+                Some(entry) if entry.size == bytes.len() as u64 => entry.clone(),
+                _ => {
+                    let mut compression = self.compression;
+                    let mut data = compression.compress(bytes)?;
+                    if data.len() >= bytes.len() {
+                        compression = ArchiveCompression::None;
+                        data = bytes.clone();
+                    }
+                    writer.write_all(&data)?;
+                    let entry = ArchiveEntry {
+                        offset,
+                        stored_len: data.len() as u64,
+                        size: bytes.len() as u64,
+                        compression,
+                        hash,
+                    };
+                    offset += entry.stored_len;
+                    stored.insert(hash, entry.clone());
+                    entry
+                }
+            };
+            write_index_entry(&mut index, path, &entry);
+        }
+
+        writer.write_all(&index)?;
+        writer.write_all(&offset.to_le_bytes())?;
+        writer.write_all(&(index.len() as u64).to_le_bytes())?;
+        writer.write_all(MAGIC)?;
+        Ok(())
+    }
+}
+
+fn write_index_entry(index: &mut Vec<u8>, path: &str, entry: &ArchiveEntry) {
+    index.extend_from_slice(&(path.len() as u32).to_le_bytes());
+    index.extend_from_slice(path.as_bytes());
+    index.extend_from_slice(&entry.offset.to_le_bytes());
+    index.extend_from_slice(&entry.stored_len.to_le_bytes());
+    index.extend_from_slice(&entry.size.to_le_bytes());
+    index.push(entry.compression.to_u8());
+    index.extend_from_slice(&entry.hash.to_le_bytes());
+}
+
+/// Reads the index of an archive, one value at a time.
+struct IndexReader<'a>(&'a [u8]);
+
+impl<'a> IndexReader<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], ArchiveError> {
+        if self.0.len() < len {
+            return Err(ArchiveError::CorruptedIndex);
+        }
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, ArchiveError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, ArchiveError> {
+        Ok(u32::from_le_bytes(self.bytes(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, ArchiveError> {
+        Ok(u64::from_le_bytes(self.bytes(8)?.try_into().unwrap()))
+    }
+
+    fn entry(&mut self) -> Result<(String, ArchiveEntry), ArchiveError> {
+        let path_len = self.u32()? as usize;
+        let path = std::str::from_utf8(self.bytes(path_len)?)
+            .map_err(|_| ArchiveError::CorruptedIndex)?
+            .to_string();
+        let entry = ArchiveEntry {
+            offset: self.u64()?,
+            stored_len: self.u64()?,
+            size: self.u64()?,
+            compression: ArchiveCompression::from_u8(self.u8()?)
+                .ok_or(ArchiveError::CorruptedIndex)?,
+            hash: self.u64()?,
+        };
+        Ok((path, entry))
+    }
+}
+
+enum ArchiveData {
+    Mapped(Mmap),
+    Owned(Vec<u8>),
+}
+
+impl ArchiveData {
+    fn as_slice(&self) -> &[u8] {
+        match self {
+            ArchiveData::Mapped(mmap) => mmap,
+            ArchiveData::Owned(bytes) => bytes,
+        }
+    }
+}
+
+/// A packed asset archive, built with an [`ArchiveBuilder`].
+///
+/// Entries are read from the archive on demand, and their content is checked against their hash.
+pub struct Archive {
+    data: ArchiveData,
+    entries: HashMap<String, ArchiveEntry>,
+    directories: HashSet<String>,
+}
+
+impl Archive {
+    /// Opens the archive at `path`, memory-mapping it.
+    ///
+    /// The archive file must not be modified while it is open.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, ArchiveError> {
+        let file = File::open(path)?;
+        // SAFETY: archives are read only, and documented to not be modified while open.
+        let mmap = unsafe { Mmap::map(&file)? };
+        Self::new(ArchiveData::Mapped(mmap))
+    }
+
+    /// Reads an archive from memory.
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, ArchiveError> {
+        Self::new(ArchiveData::Owned(bytes))
+    }
+
+    fn new(data: ArchiveData) -> Result<Self, ArchiveError> {
+        let bytes = data.as_slice();
+        if bytes.len() < HEADER_LEN + FOOTER_LEN
+            || &bytes[..MAGIC.len()] != MAGIC
+            || &bytes[bytes.len() - MAGIC.len()..] != MAGIC
+        {
+            return Err(ArchiveError::InvalidMagic);
+        }
+        let version = u32::from_le_bytes(bytes[MAGIC.len()..HEADER_LEN].try_into().unwrap());
+        if version != VERSION {
+            return Err(ArchiveError::UnsupportedVersion(version));
+        }
+
+        let mut footer = IndexReader(&bytes[bytes.len() - FOOTER_LEN..]);
+        let index_offset = footer.u64()? as usize;
+        let index_len = footer.u64()? as usize;
+        let index = index_offset
+            .checked_add(index_len)
+            .filter(|end| *end <= bytes.len() - FOOTER_LEN)
+            .map(|end| &bytes[index_offset..end])
+            .ok_or(ArchiveError::CorruptedIndex)?;
+
+        let mut reader = IndexReader(index);
+        let mut entries = HashMap::default();
+        let mut directories = HashSet::default();
+        directories.insert(String::new());
+        while !reader.0.is_empty() {
+            let (path, entry) = reader.entry()?;
+            let end = entry.offset.checked_add(entry.stored_len);
+            if entry.offset < HEADER_LEN as u64
+                || !matches!(end, Some(end) if end <= index_offset as u64)
+                || !entry
+                    .compression
+                    .is_valid_size(entry.stored_len, entry.size)
+            {
+                return Err(ArchiveError::CorruptedIndex);
+            }
+            let mut parent = path.as_str();
+            while let Some((directory, _)) = parent.rsplit_once('/') {
+                directories.insert(directory.to_string());
+                parent = directory;
+            }
+            entries.insert(path, entry);
+        }
+
+        Ok(Self {
+            data,
+            entries,
+            directories,
+        })
+    }
+
+    /// Returns the index entry of the file at `path`.
+    pub fn entry(&self, path: impl AsRef<Path>) -> Option<&ArchiveEntry> {
+        self.entries.get(&entry_path(path.as_ref()))
+    }
+
+    /// Returns the paths of the files in the archive.
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(|path| path.as_str())
+    }
+
+    /// Returns `true` if there is a file at `path`.
+    pub fn contains_file(&self, path: impl AsRef<Path>) -> bool {
+        self.entry(path).is_some()
+    }
+
+    /// Returns `true` if there is a directory at `path`.
+    pub fn contains_directory(&self, path: impl AsRef<Path>) -> bool {
+        self.directories.contains(&entry_path(path.as_ref()))
+    }
+
+    /// Reads and decompresses the file at `path`, or returns `None` if there is no such file.
+    pub fn read(&self, path: impl AsRef<Path>) -> Option<Result<Vec<u8>, ArchiveError>> {
+        let path = path.as_ref();
+        let entry = self.entry(path)?;
+        let start = entry.offset as usize;
+        let stored = &self.data.as_slice()[start..start + entry.stored_len as usize];
+        Some(
+            entry
+                .compression
+                .decompress(stored, entry.size as usize)
+                .map_err(|error| ArchiveError::Decompression {
+                    path: path.to_owned(),
+                    error,
+                })
+                .and_then(|bytes| {
+                    if content_hash(&bytes) == entry.hash {
+                        Ok(bytes)
+                    } else {
+                        Err(ArchiveError::HashMismatch(path.to_owned()))
+                    }
+                }),
+        )
+    }
+
+    /// Returns the paths of the direct children of the directory at `path`.
+    fn children(&self, path: &str) -> impl Iterator<Item = &str> + '_ {
+        let prefix = if path.is_empty() {
+            String::new()
+        } else {
+            format!("{}/", path)
+        };
+        self.entries.keys().filter_map(move |file| {
+            let rest = file.strip_prefix(&prefix)?;
+            let len = prefix.len() + rest.find('/').unwrap_or(rest.len());
+            Some(&file[..len])
+        })
+    }
+}
+
+/// I/O implementation for packed asset [`Archive`]s.
+///
+/// Archives are overlaid in the order they are added: files of later archives, such as patches,
+/// shadow the files at the same path in earlier ones.
+///
+/// ```no_run
+/// # use bevy_asset::{Archive, ArchiveAssetIo, AssetServer};
+/// let asset_io = ArchiveAssetIo::default()
+///     .with_archive(Archive::open("assets.bpak").unwrap())
+///     .with_archive(Archive::open("patch-1.bpak").unwrap());
+/// let asset_server = AssetServer::new(asset_io);
+/// ```
+#[derive(Default, Clone)]
+pub struct ArchiveAssetIo {
+    archives: Vec<Arc<Archive>>,
+}
+
+impl ArchiveAssetIo {
+    /// Creates an asset I/O reading from the archives at `paths`, in overlay order.
+    pub fn open<P: AsRef<Path>>(paths: impl IntoIterator<Item = P>) -> Result<Self, ArchiveError> {
+        let mut asset_io = Self::default();
+        for path in paths {
+            asset_io.add_archive(Archive::open(path)?);
+        }
+        Ok(asset_io)
+    }
+
+    /// Adds an archive on top of the previous ones.
+    pub fn add_archive(&mut self, archive: Archive) -> &mut Self {
+        self.archives.push(Arc::new(archive));
+        self
+    }
+
+    /// Returns this asset I/O with an archive added on top of the previous ones.
+    #[must_use]
+    pub fn with_archive(mut self, archive: Archive) -> Self {
+        self.add_archive(archive);
+        self
+    }
+
+    /// Returns the archive that provides the file at `path`, if any.
+    fn archive_of(&self, path: &Path) -> Option<&Archive> {
+        self.archives
+            .iter()
+            .rev()
+            .map(|archive| &**archive)
+            .find(|archive| archive.contains_file(path))
+    }
+}
+
+impl AssetIo for ArchiveAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            match self.archive_of(path).and_then(|archive| archive.read(path)) {
+                Some(Ok(bytes)) => Ok(bytes),
+                Some(Err(error)) => Err(AssetIoError::Io(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    error,
+                ))),
+                None => Err(AssetIoError::NotFound(path.to_owned())),
+            }
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let directory = entry_path(path);
+        let mut children = BTreeSet::new();
+        for archive in &self.archives {
+            if archive.directories.contains(&directory) {
+                children.extend(archive.children(&directory).map(PathBuf::from));
+            }
+        }
+        if children.is_empty() {
+            return Err(AssetIoError::NotFound(path.to_owned()));
+        }
+        Ok(Box::new(children.into_iter()))
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        if self.archive_of(path).is_some() {
+            Ok(Metadata::new(FileType::File))
+        } else if self
+            .archives
+            .iter()
+            .any(|archive| archive.contains_directory(path))
+        {
+            Ok(Metadata::new(FileType::Directory))
+        } else {
+            Err(AssetIoError::NotFound(path.to_owned()))
+        }
+    }
+
+    fn watch_path_for_changes(&self, _path: &Path) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(compression: ArchiveCompression, files: &[(&str, &[u8])]) -> Archive {
+        let mut builder = ArchiveBuilder::new(compression);
+        for (path, bytes) in files {
+            builder.add_file(path, bytes.to_vec());
+        }
+        let mut bytes = Vec::new();
+        builder.write(&mut bytes).unwrap();
+        Archive::from_bytes(bytes).unwrap()
+    }
+
+    fn load(asset_io: &ArchiveAssetIo, path: &str) -> Vec<u8> {
+        futures_lite::future::block_on(asset_io.load_path(Path::new(path))).unwrap()
+    }
+
+    #[test]
+    fn round_trip() {
+        let text = "grass ".repeat(100);
+        for compression in [
+            ArchiveCompression::None,
+            ArchiveCompression::Lz4,
+            ArchiveCompression::Zstd,
+        ] {
+            let archive = build(
+                compression,
+                &[
+                    ("textures/grass.txt", text.as_bytes()),
+                    ("./textures/copy.txt", text.as_bytes()),
+                    ("tiny", b"a"),
+                ],
+            );
+            assert_eq!(
+                archive.read("textures/grass.txt").unwrap().unwrap(),
+                text.as_bytes()
+            );
+            assert_eq!(archive.read("tiny").unwrap().unwrap(), b"a");
+            assert!(archive.read("missing").is_none());
+
+            // identical files are stored once
+            let grass = archive.entry("textures/grass.txt").unwrap();
+            assert_eq!(archive.entry("textures/copy.txt"), Some(grass));
+            assert_eq!(grass.size, text.len() as u64);
+            if compression != ArchiveCompression::None {
+                assert_eq!(grass.compression, compression);
+                assert!(grass.stored_len < grass.size);
+                // incompressible entries are stored as is
+                let tiny = archive.entry("tiny").unwrap();
+                assert_eq!(tiny.compression, ArchiveCompression::None);
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_archives() {
+        assert!(matches!(
+            Archive::from_bytes(b"not an archive".to_vec()),
+            Err(ArchiveError::InvalidMagic)
+        ));
+
+        let mut builder = ArchiveBuilder::new(ArchiveCompression::None);
+        builder.add_file("file", b"content".to_vec());
+        let mut bytes = Vec::new();
+        builder.write(&mut bytes).unwrap();
+        bytes[HEADER_LEN] = b'C';
+        let archive = Archive::from_bytes(bytes).unwrap();
+        assert!(matches!(
+            archive.read("file"),
+            Some(Err(ArchiveError::HashMismatch(_)))
+        ));
+    }
+
+    /// Returns an archive holding `content`, indexed by a single `entry`.
+    fn archive_with_entry(content: &[u8], entry: &ArchiveEntry) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&VERSION.to_le_bytes());
+        bytes.extend_from_slice(content);
+        let mut index = Vec::new();
+        write_index_entry(&mut index, "file", entry);
+        let index_offset = bytes.len() as u64;
+        bytes.extend_from_slice(&index);
+        bytes.extend_from_slice(&index_offset.to_le_bytes());
+        bytes.extend_from_slice(&(index.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(MAGIC);
+        bytes
+    }
+
+    #[test]
+    fn corrupted_index() {
+        let content = b"content";
+        let valid = ArchiveEntry {
+            offset: HEADER_LEN as u64,
+            stored_len: content.len() as u64,
+            size: content.len() as u64,
+            compression: ArchiveCompression::None,
+            hash: content_hash(content),
+        };
+        let archive = Archive::from_bytes(archive_with_entry(content, &valid)).unwrap();
+        assert_eq!(archive.read("file").unwrap().unwrap(), content);
+
+        let invalid_entries = [
+            // the end of the data overflows
+            ArchiveEntry {
+                offset: u64::MAX,
+                ..valid.clone()
+            },
+            // the data overlaps the index
+            ArchiveEntry {
+                stored_len: content.len() as u64 + 1,
+                size: content.len() as u64 + 1,
+                ..valid.clone()
+            },
+            // uncompressed data of another size
+            ArchiveEntry {
+                size: u64::MAX,
+                ..valid.clone()
+            },
+            // more than LZ4 data can hold
+            ArchiveEntry {
+                size: 1 << 40,
+                compression: ArchiveCompression::Lz4,
+                ..valid.clone()
+            },
+        ];
+        for entry in &invalid_entries {
+            assert!(matches!(
+                Archive::from_bytes(archive_with_entry(content, entry)),
+                Err(ArchiveError::CorruptedIndex)
+            ));
+        }
+
+        // Zstandard data is not trusted to decompress to its indexed size
+        let compressed = zstd::bulk::compress(content, 1).unwrap();
+        let entry = ArchiveEntry {
+            stored_len: compressed.len() as u64,
+            size: 1 << 40,
+            compression: ArchiveCompression::Zstd,
+            ..valid
+        };
+        let archive = Archive::from_bytes(archive_with_entry(&compressed, &entry)).unwrap();
+        assert!(matches!(
+            archive.read("file"),
+            Some(Err(ArchiveError::Decompression { .. }))
+        ));
+    }
+
+    #[test]
+    fn overlaid_archives() {
+        let base = build(
+            ArchiveCompression::Lz4,
+            &[
+                ("levels/1.level", b"base 1"),
+                ("levels/2.level", b"base 2"),
+                ("music.ogg", b"music"),
+            ],
+        );
+        let patch = build(
+            ArchiveCompression::Zstd,
+            &[
+                ("levels/2.level", b"patch 2"),
+                ("levels/extra/3.level", b"patch 3"),
+            ],
+        );
+        let asset_io = ArchiveAssetIo::default()
+            .with_archive(base)
+            .with_archive(patch);
+
+        assert_eq!(load(&asset_io, "levels/1.level"), b"base 1");
+        assert_eq!(load(&asset_io, "levels/2.level"), b"patch 2");
+        assert_eq!(load(&asset_io, "levels/extra/3.level"), b"patch 3");
+
+        let levels: Vec<_> = asset_io
+            .read_directory(Path::new("levels"))
+            .unwrap()
+            .collect();
+        assert_eq!(
+            levels,
+            vec![
+                PathBuf::from("levels/1.level"),
+                PathBuf::from("levels/2.level"),
+                PathBuf::from("levels/extra"),
+            ]
+        );
+        assert_eq!(asset_io.read_directory(Path::new("")).unwrap().count(), 2);
+        assert!(asset_io.is_dir(Path::new("levels/extra")));
+        assert!(asset_io.is_file(Path::new("music.ogg")));
+        assert!(asset_io.get_metadata(Path::new("levels/4.level")).is_err());
+    }
+
+    #[test]
+    fn memory_mapped_archive() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("assets")).unwrap();
+        std::fs::write(dir.path().join("assets/a.txt"), b"a").unwrap();
+        let mut builder = ArchiveBuilder::new(ArchiveCompression::Zstd);
+        builder.add_folder(dir.path().join("assets")).unwrap();
+        let path = dir.path().join("assets.bpak");
+        builder.write(File::create(&path).unwrap()).unwrap();
+
+        let asset_io = ArchiveAssetIo::open([&path]).unwrap();
+        assert_eq!(load(&asset_io, "a.txt"), b"a");
+    }
+}
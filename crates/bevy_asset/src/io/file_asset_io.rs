@@ -1,6 +1,8 @@
 #[cfg(feature = "filesystem_watcher")]
+use crate::AssetMetaFile;
+#[cfg(feature = "filesystem_watcher")]
 use crate::{filesystem_watcher::FilesystemWatcher, AssetServer};
-use crate::{AssetIo, AssetIoError, Metadata};
+use crate::{AssetIo, AssetIoError, AssetReader, Metadata};
 use anyhow::Result;
 #[cfg(feature = "filesystem_watcher")]
 use bevy_ecs::system::Res;
@@ -105,6 +107,24 @@ impl AssetIo for FileAssetIo {
         })
     }
 
+    fn stream_path(&self, path: &Path) -> Option<Result<AssetReader, AssetIoError>> {
+        let full_path = self.root_path.join(path);
+        let result = File::open(&full_path).and_then(|file| {
+            let len = file.metadata()?.len();
+            Ok(AssetReader {
+                reader: Box::new(file),
+                len: Some(len),
+            })
+        });
+        Some(result.map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                AssetIoError::NotFound(full_path)
+            } else {
+                e.into()
+            }
+        }))
+    }
+
     fn read_directory(
         &self,
         path: &Path,
@@ -144,6 +164,55 @@ impl AssetIo for FileAssetIo {
         Ok(())
     }
 
+    fn write_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: Vec<u8>,
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            let full_path = self.root_path.join(path);
+            if let Some(parent) = full_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(full_path, bytes)?;
+            Ok(())
+        })
+    }
+
+    fn remove_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            let full_path = self.root_path.join(path);
+            fs::remove_file(&full_path).map_err(|e| {
+                if e.kind() == std::io::ErrorKind::NotFound {
+                    AssetIoError::NotFound(full_path)
+                } else {
+                    e.into()
+                }
+            })
+        })
+    }
+
+    fn rename<'a>(
+        &'a self,
+        from: &'a Path,
+        to: &'a Path,
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            let full_from = self.root_path.join(from);
+            let full_to = self.root_path.join(to);
+            if let Some(parent) = full_to.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::rename(&full_from, full_to).map_err(|e| {
+                if e.kind() == std::io::ErrorKind::NotFound {
+                    AssetIoError::NotFound(full_from)
+                } else {
+                    e.into()
+                }
+            })
+        })
+    }
+
     fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
         let full_path = self.root_path.join(path);
         full_path
@@ -160,18 +229,31 @@ impl AssetIo for FileAssetIo {
 }
 
 /// Watches for file changes in the local file system.
+///
+/// Changes are watched in each asset source whose I/O is a [`FileAssetIo`].
 #[cfg(all(
     feature = "filesystem_watcher",
     all(not(target_arch = "wasm32"), not(target_os = "android"))
 ))]
 pub fn filesystem_watcher_system(asset_server: Res<AssetServer>) {
+    reload_changed_assets(&asset_server, None, &*asset_server.server.asset_io);
+    let sources = asset_server.server.sources.read().clone();
+    for (source, asset_io) in &sources {
+        reload_changed_assets(&asset_server, Some(source), &**asset_io);
+    }
+}
+
+#[cfg(all(
+    feature = "filesystem_watcher",
+    all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+fn reload_changed_assets(asset_server: &AssetServer, source: Option<&str>, asset_io: &dyn AssetIo) {
     let mut changed = HashSet::default();
-    let asset_io =
-        if let Some(asset_io) = asset_server.server.asset_io.downcast_ref::<FileAssetIo>() {
-            asset_io
-        } else {
-            return;
-        };
+    let asset_io = if let Some(asset_io) = asset_io.downcast_ref::<FileAssetIo>() {
+        asset_io
+    } else {
+        return;
+    };
     let watcher = asset_io.filesystem_watcher.read();
     if let Some(ref watcher) = *watcher {
         loop {
@@ -189,7 +271,19 @@ pub fn filesystem_watcher_system(asset_server: Res<AssetServer>) {
                 for path in &paths {
                     if !changed.contains(path) {
                         let relative_path = path.strip_prefix(&asset_io.root_path).unwrap();
-                        let _ = asset_server.load_untracked(relative_path.into(), true);
+                        // a changed `.meta` file reloads its asset
+                        let relative_path = if AssetMetaFile::is_meta_path(relative_path) {
+                            relative_path.with_extension("")
+                        } else {
+                            relative_path.to_path_buf()
+                        };
+                        // skip the files written by `AssetServer::save`
+                        if asset_server
+                            .is_saved_content(source, &relative_path, || fs::read(path).ok())
+                        {
+                            continue;
+                        }
+                        asset_server.reload_path(source, &relative_path);
                     }
                 }
                 changed.extend(paths);
@@ -0,0 +1,512 @@
+use crate::{AssetIo, AssetIoError, FileType, Metadata};
+use anyhow::Result;
+use bevy_log::warn;
+use bevy_utils::BoxedFuture;
+use crossbeam_channel::{Receiver, Sender};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::Write,
+    fs,
+    io::{self, Read},
+    panic::{self, AssertUnwindSafe},
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+    thread,
+    time::Duration,
+};
+
+/// I/O implementation for assets served over HTTP.
+///
+/// Implementation details:
+///
+/// - `load_path` makes `GET` requests to the base URL joined with the asset path, whose
+///   components are percent-encoded. Paths with `..` components are rejected.
+/// - Requests are blocking, so they are sent from a pool of dedicated threads, which the future
+///   returned by `load_path` waits for. The threads of the [`IoTaskPool`](bevy_tasks::IoTaskPool)
+///   are not blocked, even while waiting to retry. At most
+///   [`max_concurrent_requests`](HttpAssetIo::with_max_concurrent_requests) requests are sent at
+///   once, the others are queued. Clones of an `HttpAssetIo` share its threads.
+/// - Failed requests are retried with an exponential backoff, see [`HttpAssetIo::with_retries`].
+/// - With a [cache directory](HttpAssetIo::with_cache_dir), downloaded files are kept on disk
+///   along with their `ETag` and `Last-Modified` headers. Later requests are conditional, so
+///   unchanged files are not downloaded again, and cached files are used when the server can
+///   not be reached.
+/// - `read_directory` always returns an empty iterator.
+/// - `get_metadata` only knows about cached files.
+/// - Watching for changes is not supported. The watcher methods will do nothing.
+///
+/// ```no_run
+/// # use bevy_asset::{AssetServer, HttpAssetIo};
+/// let asset_io = HttpAssetIo::new("https://example.com/dlc").with_cache_dir("cache/dlc");
+/// # let asset_server = AssetServer::new(bevy_asset::MemoryAssetIo::default());
+/// asset_server.add_source("dlc", asset_io);
+/// ```
+#[derive(Clone)]
+pub struct HttpAssetIo {
+    base_url: String,
+    cache_dir: Option<PathBuf>,
+    retries: u32,
+    backoff: Duration,
+    agent: ureq::Agent,
+    requests: Arc<RequestPool>,
+}
+
+/// A blocking request, sent from one of the threads of a [`RequestPool`].
+type Request = Box<dyn FnOnce() + Send>;
+
+/// The threads sending the requests of an [`HttpAssetIo`].
+///
+/// Threads are started as requests are queued, up to `max_threads`, and stop once the pool is
+/// dropped and the queue is empty.
+struct RequestPool {
+    max_threads: usize,
+    threads: Mutex<usize>,
+    sender: Sender<Request>,
+    receiver: Receiver<Request>,
+}
+
+impl RequestPool {
+    fn new(max_threads: usize) -> Self {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        RequestPool {
+            max_threads: max_threads.max(1),
+            threads: Mutex::new(0),
+            sender,
+            receiver,
+        }
+    }
+
+    /// Queues the request, starting a new thread if the pool is not full yet.
+    fn send(&self, request: Request) -> io::Result<()> {
+        let mut threads = self.threads.lock();
+        if *threads < self.max_threads {
+            let receiver = self.receiver.clone();
+            let spawned = thread::Builder::new()
+                .name("http asset io".to_string())
+                .spawn(move || {
+                    for request in receiver {
+                        // a panicking request drops its sender, which fails its `load_path`
+                        let _ = panic::catch_unwind(AssertUnwindSafe(request));
+                    }
+                });
+            match spawned {
+                Ok(_) => *threads += 1,
+                // the request waits for the running threads
+                Err(_) if *threads > 0 => {}
+                Err(err) => return Err(err),
+            }
+        }
+        self.sender
+            .send(request)
+            .expect("the pool keeps its queue open");
+        Ok(())
+    }
+}
+
+/// The response headers stored next to a cached file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheHeaders {
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+impl HttpAssetIo {
+    /// The number of retries of a failed request by default.
+    pub const DEFAULT_RETRIES: u32 = 3;
+    /// The delay before the first retry of a failed request by default.
+    pub const DEFAULT_BACKOFF: Duration = Duration::from_millis(250);
+    /// The number of requests sent at once by default.
+    pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 4;
+
+    /// Creates a new `HttpAssetIo` requesting assets relative to `base_url`, without a cache.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        HttpAssetIo {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            cache_dir: None,
+            retries: Self::DEFAULT_RETRIES,
+            backoff: Self::DEFAULT_BACKOFF,
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .build(),
+            requests: Arc::new(RequestPool::new(Self::DEFAULT_MAX_CONCURRENT_REQUESTS)),
+        }
+    }
+
+    /// Caches the downloaded files in the `cache_dir` directory.
+    #[must_use]
+    pub fn with_cache_dir(mut self, cache_dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = Some(cache_dir.into());
+        self
+    }
+
+    /// Retries failed requests `retries` times, waiting `backoff` before the first retry and
+    /// twice as long before each following one.
+    ///
+    /// Requests are retried when the server can not be reached, and on `5xx` and
+    /// `429 Too Many Requests` responses.
+    #[must_use]
+    pub fn with_retries(mut self, retries: u32, backoff: Duration) -> Self {
+        self.retries = retries;
+        self.backoff = backoff;
+        self
+    }
+
+    /// Sends at most `max_concurrent_requests` requests at once, each from its own thread.
+    /// Further requests wait for one of them to finish.
+    ///
+    /// Values below 1 are treated as 1.
+    #[must_use]
+    pub fn with_max_concurrent_requests(mut self, max_concurrent_requests: usize) -> Self {
+        self.requests = Arc::new(RequestPool::new(max_concurrent_requests));
+        self
+    }
+
+    /// Sets the timeout of each request, which is 30 seconds by default.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.agent = ureq::AgentBuilder::new().timeout(timeout).build();
+        self
+    }
+
+    /// Returns the base URL that asset paths are relative to.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn url(&self, segments: &[String]) -> String {
+        let mut url = self.base_url.clone();
+        for segment in segments {
+            url.push('/');
+            for byte in segment.bytes() {
+                match byte {
+                    b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                        url.push(byte as char);
+                    }
+                    _ => write!(url, "%{:02X}", byte).unwrap(),
+                }
+            }
+        }
+        url
+    }
+
+    fn cache_paths(&self, segments: &[String]) -> Option<(PathBuf, PathBuf)> {
+        let cache_dir = self.cache_dir.as_ref()?;
+        let (file_name, parents) = segments.split_last()?;
+        let mut file_path = cache_dir.join("files");
+        let mut headers_path = cache_dir.join("headers");
+        for parent in parents {
+            file_path.push(parent);
+            headers_path.push(parent);
+        }
+        file_path.push(file_name);
+        headers_path.push(format!("{}.ron", file_name));
+        Some((file_path, headers_path))
+    }
+
+    fn read_cache(&self, segments: &[String]) -> Option<(Vec<u8>, CacheHeaders)> {
+        let (file_path, headers_path) = self.cache_paths(segments)?;
+        let bytes = fs::read(file_path).ok()?;
+        let headers = fs::read(headers_path)
+            .ok()
+            .and_then(|headers| ron::de::from_bytes(&headers).ok())
+            .unwrap_or_default();
+        Some((bytes, headers))
+    }
+
+    fn write_cache(
+        &self,
+        segments: &[String],
+        bytes: &[u8],
+        headers: &CacheHeaders,
+    ) -> io::Result<()> {
+        let (file_path, headers_path) = match self.cache_paths(segments) {
+            Some(paths) => paths,
+            None => return Ok(()),
+        };
+        for path in [&file_path, &headers_path] {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+        let headers =
+            ron::to_string(headers).map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        fs::write(file_path, bytes)?;
+        fs::write(headers_path, headers)
+    }
+
+    /// Sends a conditional `GET` request for the asset, retrying on failures.
+    ///
+    /// This blocks the thread, including while waiting to retry.
+    fn request(
+        &self,
+        segments: &[String],
+        cached: Option<&CacheHeaders>,
+    ) -> Result<ureq::Response, ureq::Error> {
+        let url = self.url(segments);
+        let mut backoff = self.backoff;
+        let mut attempt = 0;
+        loop {
+            let mut request = self.agent.get(&url);
+            if let Some(cached) = cached {
+                if let Some(etag) = &cached.etag {
+                    request = request.set("If-None-Match", etag);
+                }
+                if let Some(last_modified) = &cached.last_modified {
+                    request = request.set("If-Modified-Since", last_modified);
+                }
+            }
+            let result = request.call();
+            let retry = match &result {
+                Err(ureq::Error::Transport(_)) => true,
+                Err(ureq::Error::Status(status, _)) => *status >= 500 || *status == 429,
+                Ok(_) => false,
+            };
+            if !retry || attempt >= self.retries {
+                return result;
+            }
+            thread::sleep(backoff);
+            backoff *= 2;
+            attempt += 1;
+        }
+    }
+
+    /// Loads the asset from the server, or from the cache, blocking the thread.
+    fn load_blocking(&self, path: &Path) -> Result<Vec<u8>, AssetIoError> {
+        let segments = path_segments(path)?;
+        let cached = self.read_cache(&segments);
+        match self.request(&segments, cached.as_ref().map(|(_, headers)| headers)) {
+            Ok(response) if response.status() == 304 => match cached {
+                Some((bytes, _)) => Ok(bytes),
+                None => Err(AssetIoError::Io(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "unexpected `304 Not Modified` response",
+                ))),
+            },
+            Ok(response) => {
+                let headers = CacheHeaders {
+                    etag: response.header("ETag").map(ToString::to_string),
+                    last_modified: response.header("Last-Modified").map(ToString::to_string),
+                };
+                let mut bytes = Vec::new();
+                response.into_reader().read_to_end(&mut bytes)?;
+                if let Err(err) = self.write_cache(&segments, &bytes, &headers) {
+                    warn!("failed to cache {:?}: {}", path, err);
+                }
+                Ok(bytes)
+            }
+            Err(ureq::Error::Status(404 | 410, _)) => Err(AssetIoError::NotFound(path.to_owned())),
+            Err(err) => match cached {
+                Some((bytes, _)) => {
+                    warn!("loading {:?} from the cache: {}", path, err);
+                    Ok(bytes)
+                }
+                None => Err(AssetIoError::Io(io::Error::new(io::ErrorKind::Other, err))),
+            },
+        }
+    }
+}
+
+/// Returns the components of an asset path, which must stay below the base URL.
+fn path_segments(path: &Path) -> Result<Vec<String>, AssetIoError> {
+    let mut segments = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(name) => segments.push(name.to_string_lossy().into_owned()),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                return Err(AssetIoError::Io(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("asset path {:?} is not relative to the base URL", path),
+                )));
+            }
+        }
+    }
+    if segments.is_empty() {
+        return Err(AssetIoError::NotFound(path.to_owned()));
+    }
+    Ok(segments)
+}
+
+impl AssetIo for HttpAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        let asset_io = self.clone();
+        let owned_path = path.to_owned();
+        let (sender, receiver) = async_channel::bounded(1);
+        let sent = self.requests.send(Box::new(move || {
+            let _ = sender.try_send(asset_io.load_blocking(&owned_path));
+        }));
+        Box::pin(async move {
+            sent?;
+            receiver.recv().await.map_err(|_| {
+                AssetIoError::Io(io::Error::new(
+                    io::ErrorKind::Other,
+                    "a request of `HttpAssetIo` panicked",
+                ))
+            })?
+        })
+    }
+
+    fn read_directory(
+        &self,
+        _path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        Ok(Box::new(std::iter::empty::<PathBuf>()))
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        match self.cache_paths(&path_segments(path)?) {
+            Some((file_path, _)) if file_path.is_file() => Ok(Metadata::new(FileType::File)),
+            _ => Err(AssetIoError::NotFound(path.to_owned())),
+        }
+    }
+
+    fn watch_path_for_changes(&self, _path: &Path) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        warn!("Watching for changes is not supported by `HttpAssetIo`");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+    };
+
+    /// Serves one canned response per connection, and reports the request headers.
+    fn serve(responses: Vec<&'static str>) -> (String, mpsc::Receiver<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/assets/", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut headers = Vec::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    headers.push(line.trim().to_string());
+                }
+                sender.send(headers).unwrap();
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        (url, receiver)
+    }
+
+    fn load(asset_io: &HttpAssetIo, path: &str) -> Result<Vec<u8>, AssetIoError> {
+        futures_lite::future::block_on(asset_io.load_path(Path::new(path)))
+    }
+
+    #[test]
+    fn conditional_requests_and_offline_fallback() {
+        let (url, requests) = serve(vec![
+            "HTTP/1.1 200 OK\r\nETag: \"v1\"\r\nContent-Length: 5\r\nConnection: close\r\n\r\nhello",
+            "HTTP/1.1 304 Not Modified\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        ]);
+        let cache_dir = tempfile::tempdir().unwrap();
+        let asset_io = HttpAssetIo::new(url)
+            .with_cache_dir(cache_dir.path())
+            .with_retries(1, Duration::from_millis(1));
+
+        assert_eq!(load(&asset_io, "levels/1.level").unwrap(), b"hello");
+        let request = requests.recv().unwrap();
+        assert!(request[0].starts_with("GET /assets/levels/1.level "));
+        assert!(asset_io.is_file(Path::new("levels/1.level")));
+
+        // the cached file is not modified
+        assert_eq!(load(&asset_io, "levels/1.level").unwrap(), b"hello");
+        let request = requests.recv().unwrap();
+        assert!(request
+            .iter()
+            .any(|header| header == "If-None-Match: \"v1\""));
+
+        // the server is unavailable, even after a retry
+        assert_eq!(load(&asset_io, "levels/1.level").unwrap(), b"hello");
+        assert_eq!(requests.iter().take(2).count(), 2);
+
+        assert!(matches!(
+            load(&asset_io, "levels/2.level"),
+            Err(AssetIoError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn paths_are_encoded_and_stay_in_the_cache() {
+        let (url, requests) = serve(vec![
+            "HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok",
+        ]);
+        let cache_dir = tempfile::tempdir().unwrap();
+        let asset_io = HttpAssetIo::new(url).with_cache_dir(cache_dir.path().join("cache"));
+
+        assert_eq!(
+            load(&asset_io, "./levels/my level#1?.level").unwrap(),
+            b"ok"
+        );
+        let request = requests.recv().unwrap();
+        assert!(request[0].starts_with("GET /assets/levels/my%20level%231%3F.level "));
+        assert!(cache_dir
+            .path()
+            .join("cache/files/levels/my level#1?.level")
+            .is_file());
+
+        assert!(load(&asset_io, "levels/../../secret").is_err());
+        assert!(!asset_io.is_file(Path::new("../cache/files/levels/my level#1?.level")));
+    }
+
+    #[test]
+    fn concurrent_requests_are_bounded() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let asset_io = HttpAssetIo::new(url).with_max_concurrent_requests(1);
+        let loads = thread::spawn({
+            let asset_io = asset_io.clone();
+            move || {
+                futures_lite::future::block_on(futures_lite::future::zip(
+                    asset_io.load_path(Path::new("a")),
+                    asset_io.load_path(Path::new("b")),
+                ))
+            }
+        });
+
+        for _ in 0..2 {
+            let (mut stream, _) = listener.accept().unwrap();
+            // the other request is queued until this one is answered
+            thread::sleep(Duration::from_millis(100));
+            listener.set_nonblocking(true).unwrap();
+            assert_eq!(
+                listener.accept().unwrap_err().kind(),
+                io::ErrorKind::WouldBlock
+            );
+            listener.set_nonblocking(false).unwrap();
+            let mut line = String::new();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok")
+                .unwrap();
+        }
+
+        let (a, b) = loads.join().unwrap();
+        assert_eq!(a.unwrap(), b"ok");
+        assert_eq!(b.unwrap(), b"ok");
+        assert_eq!(*asset_io.requests.threads.lock(), 1);
+    }
+}
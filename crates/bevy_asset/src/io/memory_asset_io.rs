@@ -0,0 +1,268 @@
+use crate::{AssetIo, AssetIoError, FileType, Metadata};
+use anyhow::Result;
+use bevy_utils::{BoxedFuture, HashMap};
+use parking_lot::RwLock;
+use std::{
+    borrow::Cow,
+    collections::BTreeSet,
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
+
+/// I/O implementation for assets stored in memory.
+///
+/// Files are added with [`MemoryAssetIo::insert`], or embedded into the executable at compile
+/// time with the [`embedded_asset!`](crate::embedded_asset) macro, which makes it possible to ship
+/// an application as a single executable. Directories are implied by the paths of the files.
+///
+/// Clones of a `MemoryAssetIo` share the same files, so files can still be added after the asset
+/// I/O was given to an [`AssetServer`](crate::AssetServer). This also makes it convenient to test
+/// asset loaders and savers without touching the filesystem.
+///
+/// ```
+/// # use bevy_asset::{AssetIo, MemoryAssetIo};
+/// # use std::path::Path;
+/// let asset_io = MemoryAssetIo::default();
+/// asset_io.insert("levels/1.level", b"(size: 32)".as_slice());
+/// assert!(asset_io.is_file(Path::new("levels/1.level")));
+/// assert!(asset_io.is_dir(Path::new("levels")));
+/// ```
+#[derive(Default, Clone)]
+pub struct MemoryAssetIo {
+    files: Arc<RwLock<HashMap<PathBuf, Cow<'static, [u8]>>>>,
+}
+
+impl MemoryAssetIo {
+    /// Creates an empty `MemoryAssetIo`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the file at `path`, replacing any previous file at the same path.
+    ///
+    /// Static data, such as the output of `include_bytes!`, is not copied.
+    pub fn insert(&self, path: impl AsRef<Path>, bytes: impl Into<Cow<'static, [u8]>>) {
+        self.files
+            .write()
+            .insert(normalize(path.as_ref()), bytes.into());
+    }
+
+    /// Removes the file at `path`, returning `true` if it existed.
+    pub fn remove(&self, path: impl AsRef<Path>) -> bool {
+        self.files
+            .write()
+            .remove(&normalize(path.as_ref()))
+            .is_some()
+    }
+
+    /// Returns the number of files.
+    pub fn len(&self) -> usize {
+        self.files.read().len()
+    }
+
+    /// Returns `true` if there are no files.
+    pub fn is_empty(&self) -> bool {
+        self.files.read().is_empty()
+    }
+}
+
+/// Removes the `.` components of `path`, so that `./a.png` and `a.png` are the same file.
+fn normalize(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .collect()
+}
+
+impl AssetIo for MemoryAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            self.files
+                .read()
+                .get(&normalize(path))
+                .map(|bytes| bytes.to_vec())
+                .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let path = normalize(path);
+        let entries: BTreeSet<PathBuf> = self
+            .files
+            .read()
+            .keys()
+            .filter_map(|file| {
+                let child = file.strip_prefix(&path).ok()?.components().next()?;
+                Some(path.join(child))
+            })
+            .collect();
+        if entries.is_empty() {
+            return Err(AssetIoError::NotFound(path));
+        }
+        Ok(Box::new(entries.into_iter()))
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        let path = normalize(path);
+        let files = self.files.read();
+        if files.contains_key(&path) {
+            Ok(Metadata::new(FileType::File))
+        } else if files
+            .keys()
+            .any(|file| file != &path && file.starts_with(&path))
+        {
+            Ok(Metadata::new(FileType::Directory))
+        } else {
+            Err(AssetIoError::NotFound(path))
+        }
+    }
+
+    fn write_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: Vec<u8>,
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            self.insert(path, bytes);
+            Ok(())
+        })
+    }
+
+    fn remove_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            if self.remove(path) {
+                Ok(())
+            } else {
+                Err(AssetIoError::NotFound(path.to_owned()))
+            }
+        })
+    }
+
+    fn rename<'a>(
+        &'a self,
+        from: &'a Path,
+        to: &'a Path,
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            let mut files = self.files.write();
+            let bytes = files
+                .remove(&normalize(from))
+                .ok_or_else(|| AssetIoError::NotFound(from.to_owned()))?;
+            files.insert(normalize(to), bytes);
+            Ok(())
+        })
+    }
+
+    fn watch_path_for_changes(&self, _path: &Path) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+}
+
+/// Embeds files into the executable and adds them to a [`MemoryAssetIo`].
+///
+/// Paths are relative to the file the macro is called from, like with `include_bytes!`, and files
+/// are added at the same paths. With a `root`, paths are relative to the `root` folder instead,
+/// and files are added at their path within it.
+///
+/// ```ignore
+/// # use bevy_asset::{embedded_asset, MemoryAssetIo};
+/// let asset_io = MemoryAssetIo::default();
+/// // adds `../assets/shaders/custom.wgsl` as `shaders/custom.wgsl`
+/// embedded_asset!(asset_io, root = "../assets", "shaders/custom.wgsl", "textures/logo.png");
+/// ```
+#[macro_export]
+macro_rules! embedded_asset {
+    ($asset_io: expr, root = $root: literal, $($path: literal),+ $(,)?) => {{
+        $(
+            $asset_io.insert(
+                $path,
+                include_bytes!(concat!($root, "/", $path)).as_slice(),
+            );
+        )+
+    }};
+    ($asset_io: expr, $($path: literal),+ $(,)?) => {{
+        $(
+            $asset_io.insert($path, include_bytes!($path).as_slice());
+        )+
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block_on<T>(future: impl std::future::Future<Output = T>) -> T {
+        futures_lite::future::block_on(future)
+    }
+
+    #[test]
+    fn files_and_directories() {
+        let asset_io = MemoryAssetIo::default();
+        asset_io.insert("textures/rock.png", vec![1, 2, 3]);
+        asset_io.insert("./textures/terrain/grass.png", vec![4]);
+        asset_io.insert("scene.scn.ron", vec![5]);
+
+        assert_eq!(
+            block_on(asset_io.load_path(Path::new("textures/rock.png"))).unwrap(),
+            vec![1, 2, 3]
+        );
+        assert!(matches!(
+            block_on(asset_io.load_path(Path::new("textures/missing.png"))),
+            Err(AssetIoError::NotFound(_))
+        ));
+
+        assert!(asset_io.is_dir(Path::new("")));
+        assert!(asset_io.is_dir(Path::new("textures/terrain")));
+        assert!(asset_io.is_file(Path::new("textures/terrain/grass.png")));
+        assert!(!asset_io.is_dir(Path::new("textures/rock.png")));
+        assert!(!asset_io.is_dir(Path::new("text")));
+
+        let entries: Vec<_> = asset_io
+            .read_directory(Path::new("textures"))
+            .unwrap()
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                PathBuf::from("textures/rock.png"),
+                PathBuf::from("textures/terrain")
+            ]
+        );
+        assert_eq!(asset_io.read_directory(Path::new("")).unwrap().count(), 2);
+
+        assert!(asset_io.remove("textures/rock.png"));
+        assert_eq!(asset_io.len(), 2);
+
+        block_on(asset_io.rename(
+            Path::new("scene.scn.ron"),
+            Path::new("scenes/level.scn.ron"),
+        ))
+        .unwrap();
+        assert!(asset_io.is_file(Path::new("scenes/level.scn.ron")));
+        assert!(!asset_io.is_file(Path::new("scene.scn.ron")));
+        block_on(asset_io.write_path(Path::new("scenes/level.scn.ron"), vec![6])).unwrap();
+        assert_eq!(
+            block_on(asset_io.load_path(Path::new("scenes/level.scn.ron"))).unwrap(),
+            vec![6]
+        );
+    }
+
+    #[test]
+    fn embedded_asset() {
+        let asset_io = MemoryAssetIo::default();
+        embedded_asset!(asset_io, "mod.rs");
+        embedded_asset!(asset_io, root = "..", "lib.rs");
+
+        assert_eq!(
+            block_on(asset_io.load_path(Path::new("mod.rs"))).unwrap(),
+            include_bytes!("mod.rs")
+        );
+        assert!(asset_io.is_file(Path::new("lib.rs")));
+    }
+}
@@ -1,26 +1,44 @@
 #[cfg(target_os = "android")]
 mod android_asset_io;
+#[cfg(all(
+    feature = "asset_archive",
+    not(target_arch = "wasm32"),
+    not(target_os = "android")
+))]
+mod archive_asset_io;
 #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
 mod file_asset_io;
+#[cfg(all(feature = "http_asset_io", not(target_arch = "wasm32")))]
+mod http_asset_io;
 #[cfg(target_arch = "wasm32")]
 mod wasm_asset_io;
 
+mod memory_asset_io;
 mod metadata;
 
 #[cfg(target_os = "android")]
 pub use android_asset_io::*;
+#[cfg(all(
+    feature = "asset_archive",
+    not(target_arch = "wasm32"),
+    not(target_os = "android")
+))]
+pub use archive_asset_io::*;
 #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
 pub use file_asset_io::*;
+#[cfg(all(feature = "http_asset_io", not(target_arch = "wasm32")))]
+pub use http_asset_io::*;
 #[cfg(target_arch = "wasm32")]
 pub use wasm_asset_io::*;
 
+pub use memory_asset_io::*;
 pub use metadata::*;
 
 use anyhow::Result;
 use bevy_utils::BoxedFuture;
 use downcast_rs::{impl_downcast, Downcast};
 use std::{
-    io,
+    io::{self, Read},
     path::{Path, PathBuf},
 };
 use thiserror::Error;
@@ -39,6 +57,18 @@ pub enum AssetIoError {
     /// Failed to watch path.
     #[error("failed to watch path: {0}")]
     PathWatchError(PathBuf),
+
+    /// The asset I/O does not support writing.
+    #[error("cannot write to path in a read-only asset io: {0}")]
+    ReadOnly(PathBuf),
+}
+
+/// A reader streaming the content of a file, returned by [`AssetIo::stream_path`].
+pub struct AssetReader {
+    /// The reader of the content.
+    pub reader: Box<dyn Read + Send>,
+    /// The length of the content in bytes, if it is known.
+    pub len: Option<u64>,
 }
 
 /// A storage provider for an [`AssetServer`].
@@ -56,6 +86,15 @@ pub trait AssetIo: Downcast + Send + Sync + 'static {
     /// Returns a future to load the full file data at the provided path.
     fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>>;
 
+    /// Returns a reader streaming the file at the provided path, or `None` if this asset I/O does
+    /// not support streaming, in which case files are read with [`load_path`](AssetIo::load_path).
+    ///
+    /// Streaming lets the [`AssetServer`] report the [progress](crate::LoadingProgress) of the
+    /// loads of large files in bytes.
+    fn stream_path(&self, _path: &Path) -> Option<Result<AssetReader, AssetIoError>> {
+        None
+    }
+
     /// Returns an iterator of directory entry names at the provided path.
     fn read_directory(
         &self,
@@ -71,6 +110,40 @@ pub trait AssetIo: Downcast + Send + Sync + 'static {
     /// Enables change tracking in this asset I/O.
     fn watch_for_changes(&self) -> Result<(), AssetIoError>;
 
+    /// Returns a future to write the full file data at the provided path, replacing any previous
+    /// file and creating its parent directories.
+    ///
+    /// Asset I/Os are read-only by default, in which case this returns
+    /// [`AssetIoError::ReadOnly`].
+    fn write_path<'a>(
+        &'a self,
+        path: &'a Path,
+        _bytes: Vec<u8>,
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move { Err(AssetIoError::ReadOnly(path.to_owned())) })
+    }
+
+    /// Returns a future to remove the file at the provided path.
+    ///
+    /// Asset I/Os are read-only by default, in which case this returns
+    /// [`AssetIoError::ReadOnly`].
+    fn remove_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move { Err(AssetIoError::ReadOnly(path.to_owned())) })
+    }
+
+    /// Returns a future to move the file at the `from` path to the `to` path, replacing any
+    /// previous file and creating its parent directories.
+    ///
+    /// Asset I/Os are read-only by default, in which case this returns
+    /// [`AssetIoError::ReadOnly`].
+    fn rename<'a>(
+        &'a self,
+        from: &'a Path,
+        _to: &'a Path,
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move { Err(AssetIoError::ReadOnly(from.to_owned())) })
+    }
+
     /// Returns `true` if the path is a directory.
     fn is_dir(&self, path: &Path) -> bool {
         self.get_metadata(path)
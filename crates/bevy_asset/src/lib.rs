@@ -24,7 +24,13 @@ mod handle;
 mod info;
 mod io;
 mod loader;
+mod loading_state;
+mod meta;
 mod path;
+#[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+mod processor;
+mod progress;
+mod saver;
 
 /// The `bevy_asset` prelude.
 pub mod prelude {
@@ -40,10 +46,17 @@ pub use handle::*;
 pub use info::*;
 pub use io::*;
 pub use loader::*;
+pub use loading_state::*;
+pub use meta::*;
 pub use path::*;
+#[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+pub use processor::*;
+pub use progress::LoadingProgress;
+pub use saver::*;
 
 use bevy_app::{prelude::Plugin, App};
 use bevy_ecs::schedule::{StageLabel, SystemStage};
+use bevy_utils::HashMap;
 
 /// The names of asset stages in an [`App`] schedule.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, StageLabel)]
@@ -67,7 +80,21 @@ pub struct AssetServerSettings {
     pub asset_folder: String,
     /// Whether to watch for changes in asset files. Requires the `filesystem_watcher` feature,
     /// and cannot be supported on the wasm32 arch nor android os.
+    ///
+    /// When assets are [processed](AssetServerMode::Processed), changed sources are also
+    /// processed again.
     pub watch_for_changes: bool,
+    /// Whether assets are loaded from their sources or from their processed outputs.
+    pub mode: AssetServerMode,
+    /// The folder where processed assets are written to and loaded from, relative to the
+    /// executable.
+    pub imported_asset_folder: String,
+    /// Named asset sources to load from folders, relative to the executable, by name.
+    ///
+    /// Their assets are loaded with paths of the `source://path#label` form, and are never
+    /// processed. Sources with other kinds of [`AssetIo`] are added with
+    /// [`AddAsset::add_asset_source`].
+    pub sources: HashMap<String, String>,
 }
 
 impl Default for AssetServerSettings {
@@ -75,6 +102,34 @@ impl Default for AssetServerSettings {
         Self {
             asset_folder: "assets".to_string(),
             watch_for_changes: false,
+            mode: AssetServerMode::default(),
+            imported_asset_folder: "imported_assets".to_string(),
+            sources: HashMap::default(),
+        }
+    }
+}
+
+/// Where the [`AssetServer`] loads assets from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AssetServerMode {
+    /// Assets are loaded from the asset folder as is.
+    #[default]
+    Unprocessed,
+    /// Assets are loaded only from the imported folder.
+    ///
+    /// On platforms with a filesystem, the assets of the asset folder are first processed into
+    /// the imported folder by an [`AssetPipeline`], once all plugins are built.
+    /// Elsewhere, the imported folder must be shipped with the application.
+    Processed,
+}
+
+impl AssetServerSettings {
+    /// Returns the folder the [`AssetServer`] loads assets from, depending on the
+    /// [`AssetServerMode`].
+    pub fn load_folder(&self) -> &str {
+        match self.mode {
+            AssetServerMode::Unprocessed => &self.asset_folder,
+            AssetServerMode::Processed => &self.imported_asset_folder,
         }
     }
 }
@@ -87,13 +142,19 @@ pub fn create_platform_default_asset_io(app: &mut App) -> Box<dyn AssetIo> {
     let settings = app
         .world
         .get_resource_or_insert_with(AssetServerSettings::default);
+    create_platform_asset_io(settings.load_folder(), settings.watch_for_changes)
+}
 
+fn create_platform_asset_io(
+    folder: &str,
+    #[allow(unused_variables)] watch_for_changes: bool,
+) -> Box<dyn AssetIo> {
     #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
-    let source = FileAssetIo::new(&settings.asset_folder, settings.watch_for_changes);
+    let source = FileAssetIo::new(folder, watch_for_changes);
     #[cfg(target_arch = "wasm32")]
-    let source = WasmAssetIo::new(&settings.asset_folder);
+    let source = WasmAssetIo::new(folder);
     #[cfg(target_os = "android")]
-    let source = AndroidAssetIo::new(&settings.asset_folder);
+    let source = AndroidAssetIo::new(folder);
 
     Box::new(source)
 }
@@ -106,6 +167,53 @@ impl Plugin for AssetPlugin {
             app.insert_resource(asset_server);
         }
 
+        {
+            let settings = app
+                .world
+                .get_resource_or_insert_with(AssetServerSettings::default);
+            let sources: Vec<_> = settings
+                .sources
+                .iter()
+                .map(|(name, folder)| {
+                    let asset_io = create_platform_asset_io(folder, settings.watch_for_changes);
+                    (name.clone(), asset_io)
+                })
+                .collect();
+            let processed = settings.mode == AssetServerMode::Processed;
+            let asset_server = app.world.resource::<AssetServer>();
+            if processed {
+                asset_server.set_processed(true);
+            }
+            for (name, asset_io) in sources {
+                asset_server.add_boxed_source(name, asset_io);
+            }
+        }
+
+        #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+        {
+            let settings = app
+                .world
+                .get_resource_or_insert_with(AssetServerSettings::default);
+            if settings.mode == AssetServerMode::Processed {
+                let base_path = FileAssetIo::get_base_path();
+                let pipeline = AssetPipeline::new(
+                    base_path.join(&settings.asset_folder),
+                    base_path.join(&settings.imported_asset_folder),
+                );
+                #[cfg(feature = "filesystem_watcher")]
+                if settings.watch_for_changes {
+                    if let Err(error) = pipeline.watch_for_changes() {
+                        bevy_log::warn!("failed to watch the asset folder: {}", error);
+                    }
+                    app.add_system_to_stage(
+                        AssetStage::LoadAssets,
+                        processor::process_changed_assets_system,
+                    );
+                }
+                app.insert_resource(pipeline);
+            }
+        }
+
         app.add_stage_before(
             bevy_app::CoreStage::PreUpdate,
             AssetStage::LoadAssets,
@@ -128,4 +236,12 @@ impl Plugin for AssetPlugin {
         ))]
         app.add_system_to_stage(AssetStage::LoadAssets, io::filesystem_watcher_system);
     }
+
+    fn finish(&self, _app: &mut App) {
+        // Processors are added by other plugins, so assets are processed once all are built.
+        #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+        if let Some(pipeline) = _app.world.get_resource::<AssetPipeline>() {
+            processor::log_report(&pipeline.process_all());
+        }
+    }
 }
@@ -1,5 +1,7 @@
 use crate::{
-    path::AssetPath, AssetIo, AssetIoError, AssetMeta, AssetServer, Assets, Handle, HandleId,
+    meta::deserialize_settings,
+    path::{get_hasher, AssetPath, AssetPathId},
+    AssetIo, AssetIoError, AssetMeta, AssetMetaError, AssetServer, Assets, Handle, HandleId,
     RefChangeChannel,
 };
 use anyhow::Error;
@@ -9,7 +11,15 @@ use bevy_reflect::{TypeUuid, TypeUuidDynamic};
 use bevy_utils::{BoxedFuture, HashMap};
 use crossbeam_channel::{Receiver, Sender};
 use downcast_rs::{impl_downcast, Downcast};
-use std::path::Path;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    any::Any,
+    fmt::Debug,
+    hash::{Hash, Hasher},
+    path::Path,
+    sync::Arc,
+};
+use thiserror::Error;
 
 /// A loader for an asset source.
 ///
@@ -27,6 +37,71 @@ pub trait AssetLoader: Send + Sync + 'static {
     fn extensions(&self) -> &[&str];
 }
 
+/// Settings that configure how an [`AssetLoader`] loads a single asset.
+///
+/// Settings are either passed at the call site with [`AssetServer::load_with_settings`], or read
+/// from the `loader_settings` of the [`.meta` file](crate::AssetMetaFile) of the asset. Loaders
+/// read them with [`LoadContext::settings`].
+///
+/// The settings are hashed into the identity of the loaded assets, so that the same path loaded
+/// with different settings results in different assets.
+pub trait LoaderSettings:
+    Any + Send + Sync + Clone + Default + Debug + Hash + Serialize + DeserializeOwned
+{
+}
+
+impl<T> LoaderSettings for T where
+    T: Any + Send + Sync + Clone + Default + Debug + Hash + Serialize + DeserializeOwned
+{
+}
+
+/// Errors that occur while reading the [`LoaderSettings`] of a load.
+#[derive(Error, Debug)]
+pub enum LoaderSettingsError {
+    /// The settings passed at the call site are not of the type expected by the loader.
+    #[error("the loader expected settings of type `{expected}`, but was given `{found}`")]
+    IncorrectType {
+        /// The type of settings the loader expected.
+        expected: &'static str,
+        /// The type of settings passed at the call site.
+        found: &'static str,
+    },
+    /// The settings in the `.meta` file of the asset are invalid.
+    #[error(transparent)]
+    AssetMetaError(#[from] AssetMetaError),
+}
+
+/// Type-erased [`LoaderSettings`] passed at the call site, together with their hash.
+#[derive(Clone)]
+pub(crate) struct ErasedLoaderSettings {
+    value: Arc<dyn Any + Send + Sync>,
+    type_name: &'static str,
+    pub(crate) hash: u64,
+}
+
+impl ErasedLoaderSettings {
+    pub(crate) fn new<S: LoaderSettings>(settings: S) -> Self {
+        let type_name = std::any::type_name::<S>();
+        let mut hasher = get_hasher();
+        type_name.hash(&mut hasher);
+        settings.hash(&mut hasher);
+        Self {
+            value: Arc::new(settings),
+            type_name,
+            hash: hasher.finish(),
+        }
+    }
+}
+
+impl Debug for ErasedLoaderSettings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ErasedLoaderSettings")
+            .field("type_name", &self.type_name)
+            .field("hash", &self.hash)
+            .finish()
+    }
+}
+
 /// An essential piece of data of an application.
 ///
 /// Assets are the building blocks of games. They can be anything, from images and sounds to scenes
@@ -123,23 +198,31 @@ pub struct LoadContext<'a> {
     pub(crate) ref_change_channel: &'a RefChangeChannel,
     pub(crate) asset_io: &'a dyn AssetIo,
     pub(crate) labeled_assets: HashMap<Option<String>, BoxedLoadedAsset>,
+    pub(crate) source: Option<&'a str>,
     pub(crate) path: &'a Path,
     pub(crate) version: usize,
+    pub(crate) settings: Option<ErasedLoaderSettings>,
+    pub(crate) meta_settings: Option<String>,
 }
 
 impl<'a> LoadContext<'a> {
     pub(crate) fn new(
-        path: &'a Path,
+        asset_path: &'a AssetPath<'a>,
         ref_change_channel: &'a RefChangeChannel,
         asset_io: &'a dyn AssetIo,
         version: usize,
+        settings: Option<ErasedLoaderSettings>,
+        meta_settings: Option<String>,
     ) -> Self {
         Self {
             ref_change_channel,
             asset_io,
             labeled_assets: Default::default(),
             version,
-            path,
+            source: asset_path.source(),
+            path: asset_path.path(),
+            settings,
+            meta_settings,
         }
     }
 
@@ -148,6 +231,48 @@ impl<'a> LoadContext<'a> {
         self.path
     }
 
+    /// Gets the name of the asset source of this load context, or `None` for the default source.
+    pub fn source(&self) -> Option<&str> {
+        self.source
+    }
+
+    /// Resolves `path` relative to the asset source of this load context.
+    ///
+    /// Paths without a source of their own refer to assets of the same source as the asset being
+    /// loaded. This is how the dependencies of a [`LoadedAsset`] are loaded, so handles to them
+    /// should be obtained from their resolved path.
+    pub fn resolve_path<'b>(&self, path: impl Into<AssetPath<'b>>) -> AssetPath<'b>
+    where
+        'a: 'b,
+    {
+        path.into().or_source(self.source)
+    }
+
+    /// Gets the [`LoaderSettings`] of this load.
+    ///
+    /// Settings passed to [`AssetServer::load_with_settings`] take precedence over the
+    /// `loader_settings` of the [`.meta` file](crate::AssetMetaFile) of the asset. If there are
+    /// neither, the default settings are returned.
+    pub fn settings<S: LoaderSettings>(&self) -> Result<S, LoaderSettingsError> {
+        match &self.settings {
+            Some(settings) => settings.value.downcast_ref::<S>().cloned().ok_or(
+                LoaderSettingsError::IncorrectType {
+                    expected: std::any::type_name::<S>(),
+                    found: settings.type_name,
+                },
+            ),
+            None => Ok(deserialize_settings(self.meta_settings.as_deref())?),
+        }
+    }
+
+    /// Gets the id of the asset with the specified label in this load context.
+    pub(crate) fn asset_path_id(&self, label: Option<&str>) -> AssetPathId {
+        AssetPath::new_ref(self.path, label)
+            .or_source(self.source)
+            .get_id()
+            .with_settings_hash(self.settings.as_ref().map(|settings| settings.hash))
+    }
+
     /// Returns `true` if the load context contains an asset with the specified label.
     pub fn has_labeled_asset(&self, label: &str) -> bool {
         self.labeled_assets.contains_key(&Some(label.to_string()))
@@ -163,7 +288,15 @@ impl<'a> LoadContext<'a> {
         assert!(!label.is_empty());
         self.labeled_assets
             .insert(Some(label.to_string()), asset.into());
-        self.get_handle(AssetPath::new_ref(self.path(), Some(label)))
+        self.get_labeled_handle(label)
+    }
+
+    /// Gets a handle to the asset with the specified label in this load context.
+    ///
+    /// Unlike a handle obtained from the [`AssetPath`] of the asset, this handle accounts for the
+    /// [`LoaderSettings`] of the load.
+    pub fn get_labeled_handle<T: Asset>(&self, label: &str) -> Handle<T> {
+        self.get_handle(self.asset_path_id(Some(label)))
     }
 
     /// Gets a handle to an asset of type `T` from its id.
@@ -0,0 +1,255 @@
+use crate::{Asset, AssetServer, Handle, HandleUntyped, LoadingProgress};
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    schedule::{State, StateData},
+    system::{Res, ResMut},
+};
+use bevy_log::warn;
+
+/// Adds a [`LoadingState`] to an [`App`], which switches the app [`State`] once the assets it
+/// tracks are loaded.
+///
+/// ```
+/// # use bevy_app::App;
+/// # use bevy_asset::LoadingStatePlugin;
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// enum AppState {
+///     Loading,
+///     InGame,
+///     Error,
+/// }
+///
+/// # let mut app = App::new();
+/// app.add_state(AppState::Loading).add_plugin(
+///     LoadingStatePlugin::new(AppState::Loading, AppState::InGame)
+///         .with_failure_state(AppState::Error),
+/// );
+/// ```
+pub struct LoadingStatePlugin<S: StateData> {
+    loading: S,
+    next: S,
+    failed: Option<S>,
+}
+
+impl<S: StateData> LoadingStatePlugin<S> {
+    /// Creates a plugin switching from the `loading` state to the `next` state once the tracked
+    /// assets are loaded.
+    pub fn new(loading: S, next: S) -> Self {
+        LoadingStatePlugin {
+            loading,
+            next,
+            failed: None,
+        }
+    }
+
+    /// Switches to the `failed` state instead when one of the tracked assets failed to load.
+    ///
+    /// Without a failure state, the app stays in the loading state.
+    #[must_use]
+    pub fn with_failure_state(mut self, failed: S) -> Self {
+        self.failed = Some(failed);
+        self
+    }
+}
+
+impl<S: StateData> Plugin for LoadingStatePlugin<S> {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(LoadingState {
+            loading: self.loading.clone(),
+            next: self.next.clone(),
+            failed: self.failed.clone(),
+            handles: Vec::new(),
+        })
+        .add_system(loading_state_system::<S>);
+    }
+}
+
+/// The assets to load in the loading state of a [`LoadingStatePlugin`].
+///
+/// Tracked handles are kept alive until [cleared](LoadingState::clear). Their recursive
+/// dependencies are tracked too.
+pub struct LoadingState<S: StateData> {
+    loading: S,
+    next: S,
+    failed: Option<S>,
+    handles: Vec<HandleUntyped>,
+}
+
+impl<S: StateData> LoadingState<S> {
+    /// Tracks the loading of the asset of `handle`.
+    pub fn track<T: Asset>(&mut self, handle: &Handle<T>) {
+        self.handles.push(handle.clone_untyped());
+    }
+
+    /// Tracks the loading of the asset of an untyped `handle`.
+    pub fn track_untyped(&mut self, handle: &HandleUntyped) {
+        self.handles.push(handle.clone());
+    }
+
+    /// Returns the tracked handles.
+    pub fn handles(&self) -> &[HandleUntyped] {
+        &self.handles
+    }
+
+    /// Stops tracking all assets, releasing their handles.
+    pub fn clear(&mut self) {
+        self.handles.clear();
+    }
+
+    /// Returns the loading progress of the tracked assets.
+    pub fn progress(&self, asset_server: &AssetServer) -> LoadingProgress {
+        asset_server.get_group_loading_progress(self.handles.iter().map(|handle| handle.id))
+    }
+
+    /// Returns the state in which the tracked assets are loaded.
+    pub fn loading_state(&self) -> &S {
+        &self.loading
+    }
+}
+
+/// Switches the app [`State`] when the assets tracked by the [`LoadingState`] finished loading.
+pub fn loading_state_system<S: StateData>(
+    loading_state: Res<LoadingState<S>>,
+    asset_server: Res<AssetServer>,
+    mut state: ResMut<State<S>>,
+) {
+    if state.current() != &loading_state.loading {
+        return;
+    }
+    let progress = loading_state.progress(&asset_server);
+    let next = if progress.is_loaded() {
+        &loading_state.next
+    } else if progress.is_finished() {
+        match &loading_state.failed {
+            Some(failed) => failed,
+            None => return,
+        }
+    } else {
+        return;
+    };
+    if let Err(err) = state.set(next.clone()) {
+        warn!(
+            "failed to leave the loading state {:?}: {}",
+            state.current(),
+            err
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        update_asset_storage_system, AssetLoader, LoadContext, LoadedAsset, MemoryAssetIo,
+    };
+    use bevy_reflect::TypeUuid;
+    use bevy_tasks::IoTaskPool;
+    use bevy_utils::BoxedFuture;
+
+    #[derive(Debug, TypeUuid)]
+    #[uuid = "7f0e1a53-2d4c-4b8e-9a61-52c3d0b8e4f7"]
+    struct Level;
+
+    struct LevelLoader;
+    impl AssetLoader for LevelLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            Box::pin(async move {
+                anyhow::ensure!(!bytes.is_empty(), "empty level");
+                ctx.set_default_asset(LoadedAsset::new(Level));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["level"]
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    enum AppState {
+        Loading,
+        InGame,
+        Error,
+    }
+
+    fn run(files: &[(&str, &[u8])]) -> AppState {
+        run_tracking_after(files, 0)
+    }
+
+    /// Runs the app in the loading state, tracking the `files` after `frames` updates.
+    fn run_tracking_after(files: &[(&str, &[u8])], frames: usize) -> AppState {
+        IoTaskPool::init(Default::default);
+        let asset_io = MemoryAssetIo::default();
+        for (path, bytes) in files {
+            asset_io.insert(path, bytes.to_vec());
+        }
+        let asset_server = AssetServer::new(asset_io);
+        asset_server.add_loader(LevelLoader);
+        let assets = asset_server.register_asset_type::<Level>();
+
+        let mut app = App::new();
+        app.insert_resource(assets)
+            .insert_resource(asset_server.clone())
+            .add_system(update_asset_storage_system::<Level>)
+            .add_state(AppState::Loading)
+            .add_plugin(
+                LoadingStatePlugin::new(AppState::Loading, AppState::InGame)
+                    .with_failure_state(AppState::Error),
+            );
+        for _ in 0..frames {
+            app.update();
+        }
+        // nothing is tracked yet, so the assets are not loaded
+        assert_eq!(
+            app.world.resource::<State<AppState>>().current(),
+            &AppState::Loading
+        );
+        {
+            let mut loading_state = app.world.resource_mut::<LoadingState<AppState>>();
+            for (path, _) in files {
+                loading_state.track(&asset_server.load::<Level, _>(*path));
+            }
+        }
+
+        for _ in 0..1000 {
+            app.update();
+            if app.world.resource::<State<AppState>>().current() != &AppState::Loading {
+                break;
+            }
+            std::thread::yield_now();
+        }
+        let progress = app
+            .world
+            .resource::<LoadingState<AppState>>()
+            .progress(&asset_server);
+        assert!(progress.is_finished());
+        assert_eq!(progress.total, files.len());
+        app.world.resource::<State<AppState>>().current().clone()
+    }
+
+    #[test]
+    fn switches_state_when_loaded() {
+        assert_eq!(
+            run(&[("1.level", b"first"), ("2.level", b"second")]),
+            AppState::InGame
+        );
+        assert_eq!(
+            run(&[("1.level", b"first"), ("2.level", b"")]),
+            AppState::Error
+        );
+    }
+
+    #[test]
+    fn waits_for_tracked_assets() {
+        // assets tracked after the first frames, like in an `OnEnter` system
+        assert_eq!(
+            run_tracking_after(&[("1.level", b"first")], 3),
+            AppState::InGame
+        );
+    }
+}
@@ -0,0 +1,159 @@
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// The extension of the `.meta` files stored next to assets.
+pub const META_EXTENSION: &str = "meta";
+
+/// Errors that occur while reading or writing a `.meta` file.
+#[derive(Error, Debug)]
+pub enum AssetMetaError {
+    /// The `.meta` file is not valid RON.
+    #[error("invalid meta file: {0}")]
+    Ron(#[from] ron::Error),
+    /// The settings in the `.meta` file do not match the type expected by their consumer.
+    #[error("invalid settings in meta file: {0}")]
+    Settings(ron::Error),
+}
+
+/// The settings of an asset, stored in a `.meta` file next to it.
+///
+/// For an asset at `textures/rock.png`, the `.meta` file is `textures/rock.png.meta`:
+///
+/// ```ron
+/// (
+///     processor: Some((
+///         name: "ktx2",
+///         settings: Some("(quality: 3)"),
+///     )),
+///     loader: None,
+///     loader_settings: None,
+/// )
+/// ```
+///
+/// Settings are stored as RON text, so that each [`AssetProcessor`](crate::AssetProcessor) or
+/// [`AssetLoader`](crate::AssetLoader) can deserialize them into its own type.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssetMetaFile {
+    /// The processor to run on the asset, if any.
+    #[serde(default)]
+    pub processor: Option<ProcessorMeta>,
+    /// The extension of the loader to use for the asset, instead of the one of its path.
+    #[serde(default)]
+    pub loader: Option<String>,
+    /// The RON-serialized settings of the loader.
+    #[serde(default)]
+    pub loader_settings: Option<String>,
+}
+
+/// The processor of an asset and its settings, in an [`AssetMetaFile`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessorMeta {
+    /// The [name](crate::AssetProcessor::name) of the processor.
+    pub name: String,
+    /// The RON-serialized settings of the processor.
+    #[serde(default)]
+    pub settings: Option<String>,
+}
+
+impl AssetMetaFile {
+    /// Returns the path of the `.meta` file of the asset at `path`.
+    pub fn path_for(path: &Path) -> PathBuf {
+        let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+        file_name.push(".");
+        file_name.push(META_EXTENSION);
+        path.with_file_name(file_name)
+    }
+
+    /// Returns `true` if `path` is the path of a `.meta` file.
+    pub fn is_meta_path(path: &Path) -> bool {
+        path.extension()
+            .map_or(false, |extension| extension == META_EXTENSION)
+    }
+
+    /// Parses the content of a `.meta` file.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, AssetMetaError> {
+        Ok(ron::de::from_bytes(bytes)?)
+    }
+
+    /// Serializes this meta to RON.
+    pub fn to_ron(&self) -> Result<String, AssetMetaError> {
+        Ok(ron::ser::to_string_pretty(
+            self,
+            ron::ser::PrettyConfig::default(),
+        )?)
+    }
+
+    /// Deserializes the loader settings, or returns the default settings if there are none.
+    pub fn loader_settings<T: DeserializeOwned + Default>(&self) -> Result<T, AssetMetaError> {
+        deserialize_settings(self.loader_settings.as_deref())
+    }
+
+    /// Serializes and stores the loader settings.
+    pub fn set_loader_settings<T: Serialize>(
+        &mut self,
+        settings: &T,
+    ) -> Result<(), AssetMetaError> {
+        self.loader_settings = Some(ron::to_string(settings).map_err(AssetMetaError::Settings)?);
+        Ok(())
+    }
+}
+
+pub(crate) fn deserialize_settings<T: DeserializeOwned + Default>(
+    settings: Option<&str>,
+) -> Result<T, AssetMetaError> {
+    match settings {
+        Some(settings) => ron::from_str(settings).map_err(AssetMetaError::Settings),
+        None => Ok(T::default()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+    enum Filter {
+        #[default]
+        Linear,
+        Nearest,
+    }
+
+    #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+    struct Settings {
+        filter: Filter,
+        levels: u32,
+    }
+
+    #[test]
+    fn meta_path() {
+        let path = AssetMetaFile::path_for(Path::new("textures/rock.png"));
+        assert_eq!(path, Path::new("textures/rock.png.meta"));
+        assert!(AssetMetaFile::is_meta_path(&path));
+        assert!(!AssetMetaFile::is_meta_path(Path::new("textures/rock.png")));
+    }
+
+    #[test]
+    fn settings_round_trip() {
+        let mut meta = AssetMetaFile::default();
+        assert_eq!(
+            meta.loader_settings::<Settings>().unwrap(),
+            Settings::default()
+        );
+
+        let settings = Settings {
+            filter: Filter::Nearest,
+            levels: 4,
+        };
+        meta.set_loader_settings(&settings).unwrap();
+        let meta = AssetMetaFile::from_bytes(meta.to_ron().unwrap().as_bytes()).unwrap();
+        assert_eq!(meta.loader_settings::<Settings>().unwrap(), settings);
+    }
+
+    #[test]
+    fn missing_fields_default() {
+        let meta = AssetMetaFile::from_bytes(b"(loader: Some(\"ktx2\"))").unwrap();
+        assert_eq!(meta.loader.as_deref(), Some("ktx2"));
+        assert!(meta.processor.is_none());
+    }
+}
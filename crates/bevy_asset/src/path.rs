@@ -7,9 +7,41 @@ use std::{
     path::{Path, PathBuf},
 };
 
+/// The separator between the name of the asset source and the path in an [`AssetPath`].
+pub const ASSET_SOURCE_SEPARATOR: &str = "://";
+
+/// URL schemes that are never parsed as the name of an asset source, so that a URL such as
+/// `https://example.com/texture.png` is not read as the path `example.com/texture.png` of an
+/// `https` source.
+pub const RESERVED_SOURCE_NAMES: &[&str] = &["http", "https", "file", "ftp", "data"];
+
+/// Returns `true` if `name` can be the name of an asset source.
+///
+/// Names are made of ASCII letters, digits, `_` and `-`, and are not one of the
+/// [`RESERVED_SOURCE_NAMES`], in any case.
+pub fn is_valid_source_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        && !RESERVED_SOURCE_NAMES
+            .iter()
+            .any(|reserved| reserved.eq_ignore_ascii_case(name))
+}
+
 /// Represents a path to an asset in the file system.
+///
+/// An asset path is parsed from the `source://path#label` form, where the `source://` and
+/// `#label` parts are optional. Without a source, the asset is loaded from the default
+/// [`AssetIo`](crate::AssetIo) of the [`AssetServer`](crate::AssetServer); otherwise it is
+/// loaded from the [named source](crate::AssetServer::add_source).
+///
+/// Only a [valid source name](is_valid_source_name) followed by `://` is parsed as a source, so
+/// URLs like `https://example.com/texture.png` are kept whole as the path.
 #[derive(Debug, Hash, Clone, Serialize, Deserialize)]
 pub struct AssetPath<'a> {
+    #[serde(default)]
+    source: Option<Cow<'a, str>>,
     path: Cow<'a, Path>,
     label: Option<Cow<'a, str>>,
 }
@@ -19,6 +51,7 @@ impl<'a> AssetPath<'a> {
     #[inline]
     pub fn new_ref(path: &'a Path, label: Option<&'a str>) -> AssetPath<'a> {
         AssetPath {
+            source: None,
             path: Cow::Borrowed(path),
             label: label.map(Cow::Borrowed),
         }
@@ -28,11 +61,37 @@ impl<'a> AssetPath<'a> {
     #[inline]
     pub fn new(path: PathBuf, label: Option<String>) -> AssetPath<'a> {
         AssetPath {
+            source: None,
             path: Cow::Owned(path),
             label: label.map(Cow::Owned),
         }
     }
 
+    /// Returns this asset path in the named asset source.
+    #[inline]
+    #[must_use]
+    pub fn with_source(self, source: impl Into<Cow<'a, str>>) -> AssetPath<'a> {
+        AssetPath {
+            source: Some(source.into()),
+            ..self
+        }
+    }
+
+    /// Returns this asset path in `source` if it has no source of its own.
+    #[inline]
+    pub(crate) fn or_source(self, source: Option<&'a str>) -> AssetPath<'a> {
+        AssetPath {
+            source: self.source.or_else(|| source.map(Cow::Borrowed)),
+            ..self
+        }
+    }
+
+    /// Gets the name of the asset source, or `None` for the default source.
+    #[inline]
+    pub fn source(&self) -> Option<&str> {
+        self.source.as_deref()
+    }
+
     /// Constructs an identifier from this asset path.
     #[inline]
     pub fn get_id(&self) -> AssetPathId {
@@ -55,6 +114,10 @@ impl<'a> AssetPath<'a> {
     #[inline]
     pub fn to_owned(&self) -> AssetPath<'static> {
         AssetPath {
+            source: self
+                .source
+                .as_ref()
+                .map(|value| Cow::Owned(value.to_string())),
             path: Cow::Owned(self.path.to_path_buf()),
             label: self
                 .label
@@ -93,6 +156,18 @@ impl<'a> From<&'a Path> for SourcePathId {
     }
 }
 
+impl SourcePathId {
+    fn new(source: Option<&str>, path: &Path) -> Self {
+        let mut hasher = get_hasher();
+        path.hash(&mut hasher);
+        // the default source hashes as the bare path, so that its ids do not depend on sources
+        if let Some(source) = source {
+            source.hash(&mut hasher);
+        }
+        SourcePathId(hasher.finish())
+    }
+}
+
 impl From<AssetPathId> for SourcePathId {
     fn from(id: AssetPathId) -> Self {
         id.source_path_id()
@@ -123,6 +198,20 @@ impl AssetPathId {
     pub fn label_id(&self) -> LabelId {
         self.1
     }
+
+    /// Mixes the hash of the [`LoaderSettings`](crate::LoaderSettings) of a load into the source
+    /// path id, so that loads of the same path with different settings do not collide.
+    pub(crate) fn with_settings_hash(self, settings_hash: Option<u64>) -> Self {
+        match settings_hash {
+            Some(settings_hash) => {
+                let mut hasher = get_hasher();
+                self.0 .0.hash(&mut hasher);
+                settings_hash.hash(&mut hasher);
+                AssetPathId(SourcePathId(hasher.finish()), self.1)
+            }
+            None => self,
+        }
+    }
 }
 
 /// this hasher provides consistent results across runs
@@ -136,17 +225,14 @@ where
 {
     fn from(value: T) -> Self {
         let asset_path: AssetPath = value.into();
-        AssetPathId(
-            SourcePathId::from(asset_path.path()),
-            LabelId::from(asset_path.label()),
-        )
+        AssetPathId::from(&asset_path)
     }
 }
 
 impl<'a, 'b> From<&'a AssetPath<'b>> for AssetPathId {
     fn from(asset_path: &'a AssetPath<'b>) -> Self {
         AssetPathId(
-            SourcePathId::from(asset_path.path()),
+            SourcePathId::new(asset_path.source(), asset_path.path()),
             LabelId::from(asset_path.label()),
         )
     }
@@ -154,10 +240,15 @@ impl<'a, 'b> From<&'a AssetPath<'b>> for AssetPathId {
 
 impl<'a> From<&'a str> for AssetPath<'a> {
     fn from(asset_path: &'a str) -> Self {
+        let (source, asset_path) = match asset_path.split_once(ASSET_SOURCE_SEPARATOR) {
+            Some((source, path)) if is_valid_source_name(source) => (Some(source), path),
+            _ => (None, asset_path),
+        };
         let mut parts = asset_path.split('#');
         let path = Path::new(parts.next().expect("Path must be set."));
         let label = parts.next();
         AssetPath {
+            source: source.map(Cow::Borrowed),
             path: Cow::Borrowed(path),
             label: label.map(Cow::Borrowed),
         }
@@ -173,6 +264,7 @@ impl<'a> From<&'a String> for AssetPath<'a> {
 impl<'a> From<&'a Path> for AssetPath<'a> {
     fn from(path: &'a Path) -> Self {
         AssetPath {
+            source: None,
             path: Cow::Borrowed(path),
             label: None,
         }
@@ -182,8 +274,55 @@ impl<'a> From<&'a Path> for AssetPath<'a> {
 impl<'a> From<PathBuf> for AssetPath<'a> {
     fn from(path: PathBuf) -> Self {
         AssetPath {
+            source: None,
             path: Cow::Owned(path),
             label: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_asset_path() {
+        let path = AssetPath::from("mods://models/ship.gltf#Mesh0");
+        assert_eq!(path.source(), Some("mods"));
+        assert_eq!(path.path(), Path::new("models/ship.gltf"));
+        assert_eq!(path.label(), Some("Mesh0"));
+
+        let path = AssetPath::from("models/ship.gltf");
+        assert_eq!(path.source(), None);
+        assert_eq!(path.path(), Path::new("models/ship.gltf"));
+        assert_eq!(path.label(), None);
+    }
+
+    #[test]
+    fn urls_are_not_sources() {
+        for url in [
+            "https://example.com/ship.gltf",
+            "HTTP://example.com/ship.gltf",
+            "my.host://ship.gltf",
+        ] {
+            let path = AssetPath::from(url);
+            assert_eq!(path.source(), None);
+            assert_eq!(path.path(), Path::new(url));
+        }
+        assert!(is_valid_source_name("user-mods_2"));
+        assert!(!is_valid_source_name("https"));
+        assert!(!is_valid_source_name(""));
+    }
+
+    #[test]
+    fn source_is_part_of_id() {
+        let default = AssetPath::from("models/ship.gltf");
+        let mods = AssetPath::from("mods://models/ship.gltf");
+        assert_ne!(default.get_id(), mods.get_id());
+        assert_eq!(
+            default.get_id().source_path_id(),
+            SourcePathId::from(Path::new("models/ship.gltf"))
+        );
+        assert_eq!(mods.get_id(), default.with_source("mods").get_id());
+    }
+}
@@ -0,0 +1,691 @@
+#[cfg(feature = "filesystem_watcher")]
+use crate::filesystem_watcher::FilesystemWatcher;
+use crate::{meta::deserialize_settings, AssetMetaError, AssetMetaFile};
+use anyhow::Error;
+#[cfg(feature = "filesystem_watcher")]
+use bevy_ecs::system::Res;
+use bevy_log::{info, warn};
+use bevy_tasks::IoTaskPool;
+#[cfg(feature = "filesystem_watcher")]
+use bevy_utils::HashSet;
+use bevy_utils::{BoxedFuture, HashMap};
+#[cfg(feature = "filesystem_watcher")]
+use crossbeam_channel::TryRecvError;
+use parking_lot::Mutex;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use thiserror::Error;
+use xxhash_rust::xxh3::Xxh3;
+
+/// The name of the file in the imported folder where the [`AssetPipeline`] caches the hashes of
+/// the processed sources.
+pub const PROCESSOR_CACHE_FILE: &str = ".processor_cache";
+
+/// The version of the cache format and of the hashes it stores.
+///
+/// Bump it whenever the hash of a source changes for the same inputs (a different hash function,
+/// or hashed fields added, removed or reordered in [`AssetPipeline::process_asset`]), or the way
+/// outputs are written changes, so that caches written by earlier versions are discarded rather
+/// than trusted.
+const CACHE_VERSION: u32 = 1;
+
+/// Errors that occur while processing assets.
+#[derive(Error, Debug)]
+pub enum ProcessError {
+    /// Encountered an I/O error while reading a source or writing an output.
+    #[error("encountered an io error while processing {path:?}: {error}")]
+    Io {
+        /// The path of the file that could not be read or written.
+        path: PathBuf,
+        /// The underlying error.
+        error: io::Error,
+    },
+
+    /// The `.meta` file of the asset is invalid.
+    #[error(transparent)]
+    Meta(#[from] AssetMetaError),
+
+    /// The `.meta` file of the asset names a processor that was not added.
+    #[error("no `AssetProcessor` named `{0}`")]
+    MissingProcessor(String),
+
+    /// The processor returned an error.
+    #[error("asset processor `{processor}` failed on {path:?}: {error}")]
+    ProcessorError {
+        /// The name of the processor.
+        processor: String,
+        /// The path of the processed asset.
+        path: PathBuf,
+        /// The error returned by the processor.
+        error: Error,
+    },
+}
+
+/// A transform run ahead of time on asset sources, producing the files loaded at runtime.
+///
+/// Processors are run by the [`AssetPipeline`] on the assets of the source folder that have a
+/// matching extension, or whose `.meta` file names them. For example, a processor could compress
+/// `.png` textures to KTX2, or optimize the meshes of `.gltf` files.
+///
+/// No processors are built in yet, including these two: they need a texture encoder and a mesh
+/// optimizer Bevy does not depend on, so for now they are left to third party plugins, added with
+/// [`AddAsset::add_asset_processor`](crate::AddAsset::add_asset_processor).
+pub trait AssetProcessor: Send + Sync + 'static {
+    /// Transforms the bytes of a source asset into the bytes of the processed asset.
+    fn process<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        context: &'a mut ProcessContext,
+    ) -> BoxedFuture<'a, Result<Vec<u8>, Error>>;
+
+    /// Returns a list of extensions, without the preceding dot, of the assets this processor is
+    /// run on when their `.meta` file does not name a processor.
+    fn extensions(&self) -> &[&str] {
+        &[]
+    }
+
+    /// The name `.meta` files refer to this processor by.
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    /// The version of this processor, part of the hash of its outputs.
+    ///
+    /// Increment it when the output of the processor changes, so that cached outputs are
+    /// processed again.
+    fn version(&self) -> u32 {
+        0
+    }
+}
+
+/// The context in which an [`AssetProcessor`] processes an asset.
+pub struct ProcessContext<'a> {
+    path: &'a Path,
+    settings: Option<&'a str>,
+    output_meta: &'a mut AssetMetaFile,
+}
+
+impl<'a> ProcessContext<'a> {
+    /// Gets the path of the processed asset, relative to the source folder.
+    pub fn path(&self) -> &Path {
+        self.path
+    }
+
+    /// Deserializes the processor settings from the `.meta` file of the asset, or returns the
+    /// default settings if there are none.
+    pub fn settings<T: DeserializeOwned + Default>(&self) -> Result<T, AssetMetaError> {
+        deserialize_settings(self.settings)
+    }
+
+    /// Sets the extension of the loader to load the processed asset with, when it differs from
+    /// the extension of its path.
+    pub fn set_loader(&mut self, extension: &str) {
+        self.output_meta.loader = Some(extension.to_string());
+    }
+
+    /// Gets the `.meta` file written next to the processed asset.
+    ///
+    /// It starts as the `.meta` file of the source, without its processor.
+    pub fn output_meta(&mut self) -> &mut AssetMetaFile {
+        self.output_meta
+    }
+}
+
+/// What the [`AssetPipeline`] did with an asset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessOutcome {
+    /// The asset was run through a processor.
+    Processed,
+    /// The asset has no processor and was copied as is.
+    Copied,
+    /// The asset did not change since it was last processed.
+    Cached,
+    /// The source of the asset was removed, so its output was removed too.
+    Removed,
+}
+
+/// The result of processing several assets with an [`AssetPipeline`].
+#[derive(Debug, Default)]
+pub struct ProcessReport {
+    /// The paths of the assets that were written or removed in the imported folder.
+    pub updated: Vec<(PathBuf, ProcessOutcome)>,
+    /// The paths of the assets that failed to process, with the reason.
+    pub failed: Vec<(PathBuf, ProcessError)>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct ProcessorCache {
+    version: u32,
+    hashes: BTreeMap<PathBuf, u64>,
+}
+
+/// Processes the assets of a source folder into an imported folder.
+///
+/// Each asset of the source folder is run through the [`AssetProcessor`] named in its `.meta`
+/// file, or through the processor registered for its extension. Assets without a processor are
+/// copied as is. The output is written to the same path in the imported folder, along with a
+/// `.meta` file holding the loader settings.
+///
+/// Outputs are cached by the hash of the source, its `.meta` file and the processor version, so
+/// that only changed assets are processed again.
+///
+/// When [`AssetServerSettings::mode`](crate::AssetServerSettings::mode) is
+/// [`AssetServerMode::Processed`](crate::AssetServerMode::Processed), the `AssetPlugin` processes
+/// the assets once all plugins are built, and the [`AssetServer`](crate::AssetServer) only reads
+/// the imported folder.
+pub struct AssetPipeline {
+    source_path: PathBuf,
+    imported_path: PathBuf,
+    processors: Vec<Arc<dyn AssetProcessor>>,
+    name_to_processor_index: HashMap<String, usize>,
+    extension_to_processor_index: HashMap<String, usize>,
+    cache: Mutex<ProcessorCache>,
+    #[cfg(feature = "filesystem_watcher")]
+    watcher: Mutex<Option<FilesystemWatcher>>,
+}
+
+impl AssetPipeline {
+    /// Creates a pipeline processing the assets of `source_path` into `imported_path`.
+    ///
+    /// The cache of a previous run is read from the imported folder, if any.
+    pub fn new(source_path: impl Into<PathBuf>, imported_path: impl Into<PathBuf>) -> Self {
+        let imported_path = imported_path.into();
+        let cache = fs::read(imported_path.join(PROCESSOR_CACHE_FILE))
+            .ok()
+            .and_then(|bytes| ron::de::from_bytes::<ProcessorCache>(&bytes).ok())
+            .filter(|cache| cache.version == CACHE_VERSION)
+            .unwrap_or_default();
+        Self {
+            source_path: source_path.into(),
+            imported_path,
+            processors: Vec::new(),
+            name_to_processor_index: HashMap::default(),
+            extension_to_processor_index: HashMap::default(),
+            cache: Mutex::new(cache),
+            #[cfg(feature = "filesystem_watcher")]
+            watcher: Default::default(),
+        }
+    }
+
+    /// Returns the folder the sources are read from.
+    pub fn source_path(&self) -> &Path {
+        &self.source_path
+    }
+
+    /// Returns the folder the processed assets are written to.
+    pub fn imported_path(&self) -> &Path {
+        &self.imported_path
+    }
+
+    /// Adds the provided asset processor to the pipeline.
+    ///
+    /// If `processor` has a name or extensions in conflict with processors that came before it,
+    /// it will replace them.
+    pub fn add_processor<T: AssetProcessor>(&mut self, processor: T) {
+        let index = self.processors.len();
+        self.name_to_processor_index
+            .insert(processor.name().to_string(), index);
+        for extension in processor.extensions() {
+            self.extension_to_processor_index
+                .insert(extension.to_string(), index);
+        }
+        self.processors.push(Arc::new(processor));
+    }
+
+    fn get_processor(&self, name: &str) -> Result<Arc<dyn AssetProcessor>, ProcessError> {
+        self.name_to_processor_index
+            .get(name)
+            .map(|index| self.processors[*index].clone())
+            .ok_or_else(|| ProcessError::MissingProcessor(name.to_string()))
+    }
+
+    fn get_path_processor(&self, path: &Path) -> Option<Arc<dyn AssetProcessor>> {
+        let file_name = path.file_name()?.to_str()?.to_lowercase();
+        let mut extension = file_name.as_str();
+        while let Some(index) = extension.find('.') {
+            extension = &extension[index + 1..];
+            if let Some(index) = self.extension_to_processor_index.get(extension) {
+                return Some(self.processors[*index].clone());
+            }
+        }
+        None
+    }
+
+    /// Processes the asset at `path`, relative to the source folder, unless its output is
+    /// cached.
+    pub async fn process_asset(&self, path: &Path) -> Result<ProcessOutcome, ProcessError> {
+        let source_path = self.source_path.join(path);
+        let bytes = async_fs::read(&source_path)
+            .await
+            .map_err(io_error(&source_path))?;
+        let meta_bytes =
+            match async_fs::read(self.source_path.join(AssetMetaFile::path_for(path))).await {
+                Ok(bytes) => Some(bytes),
+                Err(error) if error.kind() == io::ErrorKind::NotFound => None,
+                Err(error) => {
+                    return Err(ProcessError::Io {
+                        path: AssetMetaFile::path_for(path),
+                        error,
+                    })
+                }
+            };
+        let meta = match &meta_bytes {
+            Some(meta_bytes) => AssetMetaFile::from_bytes(meta_bytes)?,
+            None => AssetMetaFile::default(),
+        };
+        let processor = match &meta.processor {
+            Some(processor) => Some(self.get_processor(&processor.name)?),
+            None => self.get_path_processor(path),
+        };
+
+        let hash = {
+            // a content hash, stable across Rust releases and platforms as it is stored on disk
+            let mut hasher = Xxh3::new();
+            let mut hash_field = |bytes: &[u8]| {
+                hasher.update(&(bytes.len() as u64).to_le_bytes());
+                hasher.update(bytes);
+            };
+            hash_field(&bytes);
+            hash_field(meta_bytes.as_deref().unwrap_or_default());
+            if let Some(processor) = &processor {
+                hash_field(processor.name().as_bytes());
+                hash_field(&processor.version().to_le_bytes());
+            }
+            hasher.digest()
+        };
+        let output_path = self.imported_path.join(path);
+        if self.cache.lock().hashes.get(path) == Some(&hash) && output_path.exists() {
+            return Ok(ProcessOutcome::Cached);
+        }
+
+        let mut output_meta = AssetMetaFile {
+            processor: None,
+            ..meta.clone()
+        };
+        let (output, outcome) = match processor {
+            Some(processor) => {
+                let mut context = ProcessContext {
+                    path,
+                    settings: meta
+                        .processor
+                        .as_ref()
+                        .and_then(|processor| processor.settings.as_deref()),
+                    output_meta: &mut output_meta,
+                };
+                let output = processor
+                    .process(&bytes, &mut context)
+                    .await
+                    .map_err(|error| ProcessError::ProcessorError {
+                        processor: processor.name().to_string(),
+                        path: path.to_path_buf(),
+                        error,
+                    })?;
+                (output, ProcessOutcome::Processed)
+            }
+            None => (bytes, ProcessOutcome::Copied),
+        };
+
+        if let Some(parent) = output_path.parent() {
+            async_fs::create_dir_all(parent)
+                .await
+                .map_err(io_error(parent))?;
+        }
+        async_fs::write(&output_path, &output)
+            .await
+            .map_err(io_error(&output_path))?;
+        let output_meta_path = AssetMetaFile::path_for(&output_path);
+        if output_meta == AssetMetaFile::default() {
+            ignore_not_found(async_fs::remove_file(&output_meta_path).await)
+                .map_err(io_error(&output_meta_path))?;
+        } else {
+            async_fs::write(&output_meta_path, output_meta.to_ron()?)
+                .await
+                .map_err(io_error(&output_meta_path))?;
+        }
+
+        self.cache.lock().hashes.insert(path.to_path_buf(), hash);
+        Ok(outcome)
+    }
+
+    /// Removes the output of the asset at `path`, relative to the source folder.
+    pub fn remove_asset(&self, path: &Path) -> Result<(), ProcessError> {
+        let output_path = self.imported_path.join(path);
+        let output_meta_path = AssetMetaFile::path_for(&output_path);
+        ignore_not_found(fs::remove_file(&output_path)).map_err(io_error(&output_path))?;
+        ignore_not_found(fs::remove_file(&output_meta_path))
+            .map_err(io_error(&output_meta_path))?;
+        self.cache.lock().hashes.remove(path);
+        Ok(())
+    }
+
+    /// Processes every asset of the source folder, and removes the outputs of the assets that no
+    /// longer exist.
+    pub fn process_all(&self) -> ProcessReport {
+        let mut paths = Vec::new();
+        if let Err(error) = collect_sources(&self.source_path, Path::new(""), &mut paths) {
+            let mut report = ProcessReport::default();
+            report.failed.push((
+                self.source_path.clone(),
+                ProcessError::Io {
+                    path: self.source_path.clone(),
+                    error,
+                },
+            ));
+            return report;
+        }
+
+        let mut report = self.process_paths(&paths);
+        let removed: Vec<PathBuf> = self
+            .cache
+            .lock()
+            .hashes
+            .keys()
+            .filter(|path| !paths.contains(path))
+            .cloned()
+            .collect();
+        for path in removed {
+            match self.remove_asset(&path) {
+                Ok(()) => report.updated.push((path, ProcessOutcome::Removed)),
+                Err(error) => report.failed.push((path, error)),
+            }
+        }
+        self.save_cache();
+        report
+    }
+
+    fn process_paths(&self, paths: &[PathBuf]) -> ProcessReport {
+        let results = IoTaskPool::get().scope(|scope| {
+            for path in paths {
+                scope.spawn(async move { (path.clone(), self.process_asset(path).await) });
+            }
+        });
+
+        let mut report = ProcessReport::default();
+        for (path, result) in results {
+            match result {
+                Ok(ProcessOutcome::Cached) => {}
+                Ok(outcome) => report.updated.push((path, outcome)),
+                Err(error) => report.failed.push((path, error)),
+            }
+        }
+        report
+    }
+
+    fn save_cache(&self) {
+        let cache = self.cache.lock();
+        let result = ron::to_string(&ProcessorCache {
+            version: CACHE_VERSION,
+            hashes: cache.hashes.clone(),
+        })
+        .map_err(|error| io::Error::new(io::ErrorKind::Other, error))
+        .and_then(|cache| {
+            fs::create_dir_all(&self.imported_path)?;
+            fs::write(self.imported_path.join(PROCESSOR_CACHE_FILE), cache)
+        });
+        if let Err(error) = result {
+            warn!("failed to save the asset processor cache: {}", error);
+        }
+    }
+
+    /// Starts watching the source folder for changes, to process modified assets again in
+    /// [`AssetPipeline::process_changed`].
+    #[cfg(feature = "filesystem_watcher")]
+    pub fn watch_for_changes(&self) -> Result<(), ProcessError> {
+        let mut watcher = FilesystemWatcher::default();
+        watcher
+            .watch(&self.source_path)
+            .map_err(|error| ProcessError::Io {
+                path: self.source_path.clone(),
+                error: io::Error::new(io::ErrorKind::Other, error),
+            })?;
+        *self.watcher.lock() = Some(watcher);
+        Ok(())
+    }
+
+    /// Processes the assets that changed in the source folder since the last call.
+    #[cfg(feature = "filesystem_watcher")]
+    pub fn process_changed(&self) -> ProcessReport {
+        let mut changed = HashSet::default();
+        if let Some(watcher) = &*self.watcher.lock() {
+            loop {
+                let event = match watcher.receiver.try_recv() {
+                    Ok(Ok(event)) => event,
+                    Ok(Err(error)) => {
+                        warn!("asset processor watcher error: {}", error);
+                        continue;
+                    }
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => panic!("FilesystemWatcher disconnected."),
+                };
+                if event.kind.is_access() {
+                    continue;
+                }
+                for path in event.paths {
+                    if let Ok(path) = path.strip_prefix(&self.source_path) {
+                        changed.insert(asset_path_of(path));
+                    }
+                }
+            }
+        }
+        if changed.is_empty() {
+            return ProcessReport::default();
+        }
+
+        let (existing, removed): (Vec<PathBuf>, Vec<PathBuf>) = changed
+            .into_iter()
+            .partition(|path| self.source_path.join(path).exists());
+        let mut report = self.process_paths(
+            &existing
+                .into_iter()
+                .filter(|path| self.source_path.join(path).is_file())
+                .collect::<Vec<_>>(),
+        );
+        for path in removed {
+            match self.remove_asset(&path) {
+                Ok(()) => report.updated.push((path, ProcessOutcome::Removed)),
+                Err(error) => report.failed.push((path, error)),
+            }
+        }
+        self.save_cache();
+        report
+    }
+}
+
+/// Returns the path of the asset a source file belongs to: the file itself, or the asset of a
+/// `.meta` file.
+#[cfg(feature = "filesystem_watcher")]
+fn asset_path_of(path: &Path) -> PathBuf {
+    if AssetMetaFile::is_meta_path(path) {
+        path.with_extension("")
+    } else {
+        path.to_path_buf()
+    }
+}
+
+fn collect_sources(root: &Path, directory: &Path, paths: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(root.join(directory))? {
+        let entry = entry?;
+        let path = directory.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            collect_sources(root, &path, paths)?;
+        } else if !AssetMetaFile::is_meta_path(&path) {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn io_error(path: &Path) -> impl FnOnce(io::Error) -> ProcessError + '_ {
+    move |error| ProcessError::Io {
+        path: path.to_path_buf(),
+        error,
+    }
+}
+
+/// Treats removing a file that does not exist as a success.
+fn ignore_not_found(result: io::Result<()>) -> io::Result<()> {
+    match result {
+        Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+        _ => Ok(()),
+    }
+}
+
+/// Logs the outcome of processing assets.
+pub(crate) fn log_report(report: &ProcessReport) {
+    for (path, error) in &report.failed {
+        warn!("failed to process asset {:?}: {}", path, error);
+    }
+    if !report.updated.is_empty() {
+        info!("processed {} asset(s)", report.updated.len());
+    }
+}
+
+/// Processes the assets that changed in the source folder of the [`AssetPipeline`].
+#[cfg(feature = "filesystem_watcher")]
+pub fn process_changed_assets_system(pipeline: Res<AssetPipeline>) {
+    log_report(&pipeline.process_changed());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProcessorMeta;
+    use bevy_tasks::TaskPool;
+
+    #[derive(Default, Deserialize)]
+    struct UppercaseSettings {
+        suffix: String,
+    }
+
+    struct UppercaseProcessor;
+
+    impl AssetProcessor for UppercaseProcessor {
+        fn process<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            context: &'a mut ProcessContext,
+        ) -> BoxedFuture<'a, Result<Vec<u8>, Error>> {
+            Box::pin(async move {
+                let settings: UppercaseSettings = context.settings()?;
+                context.set_loader("upper");
+                let mut output = std::str::from_utf8(bytes)?.to_uppercase();
+                output.push_str(&settings.suffix);
+                Ok(output.into_bytes())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+
+        fn name(&self) -> &str {
+            "uppercase"
+        }
+    }
+
+    fn setup() -> (tempfile::TempDir, AssetPipeline) {
+        IoTaskPool::init(TaskPool::default);
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("source/nested")).unwrap();
+        let mut pipeline =
+            AssetPipeline::new(dir.path().join("source"), dir.path().join("imported"));
+        pipeline.add_processor(UppercaseProcessor);
+        (dir, pipeline)
+    }
+
+    #[test]
+    fn process_and_copy() {
+        let (dir, pipeline) = setup();
+        let source = dir.path().join("source");
+        let imported = dir.path().join("imported");
+        fs::write(source.join("nested/a.txt"), "hello").unwrap();
+        fs::write(source.join("b.bin"), [1, 2, 3]).unwrap();
+
+        let report = pipeline.process_all();
+        assert!(report.failed.is_empty());
+        assert_eq!(report.updated.len(), 2);
+        assert_eq!(fs::read(imported.join("nested/a.txt")).unwrap(), b"HELLO");
+        assert_eq!(fs::read(imported.join("b.bin")).unwrap(), [1, 2, 3]);
+
+        let meta =
+            AssetMetaFile::from_bytes(&fs::read(imported.join("nested/a.txt.meta")).unwrap())
+                .unwrap();
+        assert_eq!(meta.loader.as_deref(), Some("upper"));
+        assert!(meta.processor.is_none());
+        assert!(!imported.join("b.bin.meta").exists());
+    }
+
+    #[test]
+    fn meta_settings_and_cache() {
+        let (dir, pipeline) = setup();
+        let source = dir.path().join("source");
+        let imported = dir.path().join("imported");
+        fs::write(source.join("a.data"), "hello").unwrap();
+        let meta = AssetMetaFile {
+            processor: Some(ProcessorMeta {
+                name: "uppercase".to_string(),
+                settings: Some("(suffix: \"!\")".to_string()),
+            }),
+            ..Default::default()
+        };
+        fs::write(source.join("a.data.meta"), meta.to_ron().unwrap()).unwrap();
+
+        let report = pipeline.process_all();
+        assert!(report.failed.is_empty());
+        assert_eq!(fs::read(imported.join("a.data")).unwrap(), b"HELLO!");
+
+        // Unchanged sources are not processed again, even by a new pipeline.
+        assert!(pipeline.process_all().updated.is_empty());
+        let mut reloaded = AssetPipeline::new(&source, &imported);
+        reloaded.add_processor(UppercaseProcessor);
+        assert!(reloaded.process_all().updated.is_empty());
+
+        // Changing the meta file invalidates the cache.
+        let meta = AssetMetaFile {
+            processor: Some(ProcessorMeta {
+                name: "uppercase".to_string(),
+                settings: Some("(suffix: \"?\")".to_string()),
+            }),
+            ..Default::default()
+        };
+        fs::write(source.join("a.data.meta"), meta.to_ron().unwrap()).unwrap();
+        assert_eq!(pipeline.process_all().updated.len(), 1);
+        assert_eq!(fs::read(imported.join("a.data")).unwrap(), b"HELLO?");
+    }
+
+    #[test]
+    fn removed_sources_and_errors() {
+        let (dir, pipeline) = setup();
+        let source = dir.path().join("source");
+        let imported = dir.path().join("imported");
+        fs::write(source.join("a.txt"), "hello").unwrap();
+        pipeline.process_all();
+        assert!(imported.join("a.txt").exists());
+
+        fs::remove_file(source.join("a.txt")).unwrap();
+        let report = pipeline.process_all();
+        assert_eq!(
+            report.updated,
+            vec![(PathBuf::from("a.txt"), ProcessOutcome::Removed)]
+        );
+        assert!(!imported.join("a.txt").exists());
+        assert!(!imported.join("a.txt.meta").exists());
+
+        fs::write(source.join("b.txt"), "hello").unwrap();
+        fs::write(
+            source.join("b.txt.meta"),
+            "(processor: Some((name: \"missing\")))",
+        )
+        .unwrap();
+        let report = pipeline.process_all();
+        assert!(matches!(
+            report.failed.as_slice(),
+            [(_, ProcessError::MissingProcessor(name))] if name == "missing"
+        ));
+    }
+}
@@ -0,0 +1,163 @@
+use std::{
+    ops::{Add, AddAssign},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// The progress of the loading of a group of assets, along with their recursive dependencies.
+///
+/// Returned by [`AssetServer::get_loading_progress`](crate::AssetServer::get_loading_progress)
+/// and [`AssetServer::get_group_loading_progress`](crate::AssetServer::get_group_loading_progress).
+/// Each asset source is counted once, even when several assets of the group depend on it.
+///
+/// The totals grow while the assets load, as the dependencies of an asset are only known once it
+/// is loaded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LoadingProgress {
+    /// The number of assets that are loaded.
+    pub loaded: usize,
+    /// The number of assets that failed to load.
+    pub failed: usize,
+    /// The number of assets.
+    pub total: usize,
+    /// The number of bytes read so far.
+    pub bytes_read: u64,
+    /// The total number of bytes to read, or `None` until the size of every asset is known.
+    pub bytes_total: Option<u64>,
+}
+
+impl LoadingProgress {
+    /// Returns `true` once every asset is either loaded or failed to load.
+    ///
+    /// An empty group is never finished, as its assets may not be tracked yet.
+    pub fn is_finished(&self) -> bool {
+        self.total > 0 && self.loaded + self.failed == self.total
+    }
+
+    /// Returns `true` once every asset is loaded.
+    ///
+    /// An empty group is never loaded, as its assets may not be tracked yet.
+    pub fn is_loaded(&self) -> bool {
+        self.total > 0 && self.loaded == self.total
+    }
+
+    /// Returns the progress as a fraction between `0.0` and `1.0`.
+    ///
+    /// The fraction counts bytes once the size of every asset is known, and assets until then.
+    /// It is `0.0` for an empty group.
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        if self.is_finished() {
+            return 1.0;
+        }
+        match self.bytes_total {
+            Some(bytes_total) if bytes_total > 0 => {
+                (self.bytes_read as f64 / bytes_total as f64).min(1.0) as f32
+            }
+            _ => (self.loaded + self.failed) as f32 / self.total as f32,
+        }
+    }
+}
+
+impl Add for LoadingProgress {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        LoadingProgress {
+            loaded: self.loaded + rhs.loaded,
+            failed: self.failed + rhs.failed,
+            total: self.total + rhs.total,
+            bytes_read: self.bytes_read + rhs.bytes_read,
+            bytes_total: self
+                .bytes_total
+                .zip(rhs.bytes_total)
+                .map(|(lhs, rhs)| lhs + rhs),
+        }
+    }
+}
+
+impl AddAssign for LoadingProgress {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+/// The number of bytes read by the current load of an asset source, updated while it is read.
+#[derive(Debug)]
+pub(crate) struct BytesProgress {
+    read: AtomicU64,
+    /// `u64::MAX` while unknown.
+    total: AtomicU64,
+}
+
+impl Default for BytesProgress {
+    fn default() -> Self {
+        BytesProgress {
+            read: AtomicU64::new(0),
+            total: AtomicU64::new(u64::MAX),
+        }
+    }
+}
+
+impl BytesProgress {
+    pub(crate) fn set_total(&self, total: u64) {
+        self.total.store(total, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_read(&self, read: u64) {
+        self.read.fetch_add(read, Ordering::Relaxed);
+    }
+
+    pub(crate) fn read(&self) -> u64 {
+        self.read.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn total(&self) -> Option<u64> {
+        Some(self.total.load(Ordering::Relaxed)).filter(|total| *total != u64::MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fraction() {
+        let mut progress = LoadingProgress {
+            loaded: 1,
+            failed: 0,
+            total: 4,
+            bytes_read: 0,
+            bytes_total: None,
+        };
+        assert_eq!(progress.fraction(), 0.25);
+
+        progress += LoadingProgress {
+            loaded: 0,
+            failed: 1,
+            total: 1,
+            bytes_read: 10,
+            bytes_total: Some(40),
+        };
+        // the size of the first group is unknown
+        assert_eq!(progress.bytes_total, None);
+        assert_eq!(progress.fraction(), 0.4);
+
+        progress.bytes_total = Some(40);
+        assert_eq!(progress.fraction(), 0.25);
+
+        progress.loaded = 4;
+        assert!(progress.is_finished());
+        assert!(!progress.is_loaded());
+        assert_eq!(progress.fraction(), 1.0);
+    }
+
+    #[test]
+    fn empty_group() {
+        let progress = LoadingProgress::default();
+        assert!(!progress.is_finished());
+        assert!(!progress.is_loaded());
+        assert_eq!(progress.fraction(), 0.0);
+    }
+}
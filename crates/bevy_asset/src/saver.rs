@@ -0,0 +1,24 @@
+use crate::{Asset, AssetServer, Assets};
+use anyhow::Error;
+use bevy_ecs::system::Res;
+
+/// A saver for assets of a single type, the counterpart of an [`AssetLoader`](crate::AssetLoader).
+///
+/// Types implementing this trait are used by [`AssetServer::save`] to write assets back through
+/// the [`AssetIo`](crate::AssetIo) of their asset source. The bytes a saver produces should be
+/// readable by the loader of the same extensions.
+pub trait AssetSaver: Send + Sync + 'static {
+    /// The type of asset saved by this saver.
+    type Asset: Asset;
+
+    /// Serializes the asset into the content of its file.
+    fn save(&self, asset: &Self::Asset) -> Result<Vec<u8>, Error>;
+
+    /// Returns a list of extensions supported by this asset saver, without the preceding dot.
+    fn extensions(&self) -> &[&str];
+}
+
+/// Saves the assets of the [`Assets`] collection queued up with [`AssetServer::save`].
+pub fn save_assets_system<T: Asset>(asset_server: Res<AssetServer>, assets: Res<Assets<T>>) {
+    asset_server.save_queued_assets(&assets);
+}
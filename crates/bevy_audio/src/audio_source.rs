@@ -2,6 +2,7 @@ use anyhow::Result;
 use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
 use bevy_reflect::TypeUuid;
 use bevy_utils::BoxedFuture;
+use serde::{Deserialize, Serialize};
 use std::{io::Cursor, sync::Arc};
 
 /// A source of audio data
@@ -29,12 +30,20 @@ impl AsRef<[u8]> for AudioSource {
 #[derive(Default)]
 pub struct AudioLoader;
 
+/// Settings of the [`AudioLoader`], passed with
+/// [`AssetServer::load_with_settings`](bevy_asset::AssetServer::load_with_settings).
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AudioLoaderSettings {
+    /// Whether to check that the audio can be decoded while loading it.
+    ///
+    /// Audio is otherwise only decoded when played, which panics if it is invalid.
+    pub validate: bool,
+}
+
 impl AssetLoader for AudioLoader {
     fn load(&self, bytes: &[u8], load_context: &mut LoadContext) -> BoxedFuture<Result<()>> {
-        load_context.set_default_asset(LoadedAsset::new(AudioSource {
-            bytes: bytes.into(),
-        }));
-        Box::pin(async move { Ok(()) })
+        let result = load_audio(bytes, load_context);
+        Box::pin(async move { result })
     }
 
     fn extensions(&self) -> &[&str] {
@@ -55,6 +64,18 @@ impl AssetLoader for AudioLoader {
     }
 }
 
+fn load_audio(bytes: &[u8], load_context: &mut LoadContext) -> Result<()> {
+    let settings = load_context.settings::<AudioLoaderSettings>()?;
+    let source = AudioSource {
+        bytes: bytes.into(),
+    };
+    if settings.validate {
+        rodio::Decoder::new(Cursor::new(source.clone()))?;
+    }
+    load_context.set_default_asset(LoadedAsset::new(source));
+    Ok(())
+}
+
 /// A type implementing this trait can be decoded as a rodio source
 pub trait Decodable: Send + Sync + 'static {
     /// The decoder that can decode the implementing type
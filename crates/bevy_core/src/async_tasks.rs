@@ -0,0 +1,336 @@
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    event::{Event, Events},
+    system::{ResMut, SystemParam},
+    world::{Mut, World},
+};
+use bevy_tasks::AsyncComputeTaskPool;
+use bevy_utils::HashMap;
+use crossbeam_channel::{Receiver, Sender};
+use std::{future::Future, marker::PhantomData};
+
+/// Identifies a task spawned through [`Tasks`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TaskId(u64);
+
+type TaskCommand = Box<dyn FnOnce(&mut World) + Send>;
+
+// Dropping a `Task` cancels it. The single threaded task pool cannot cancel tasks, so their
+// completion is ignored instead.
+#[cfg(not(target_arch = "wasm32"))]
+type TaskHandle = bevy_tasks::Task<()>;
+#[cfg(target_arch = "wasm32")]
+type TaskHandle = ();
+
+struct TrackedTask {
+    entity: Option<Entity>,
+    _handle: TaskHandle,
+}
+
+/// The tasks spawned through [`Tasks`], and the channel their results are sent back through.
+///
+/// Results are applied to the [`World`] by [`apply_completed_tasks`].
+pub struct AsyncTasks {
+    tasks: HashMap<TaskId, TrackedTask>,
+    next_id: u64,
+    sender: Sender<(TaskId, TaskCommand)>,
+    receiver: Receiver<(TaskId, TaskCommand)>,
+}
+
+impl Default for AsyncTasks {
+    fn default() -> Self {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        Self {
+            tasks: HashMap::default(),
+            next_id: 0,
+            sender,
+            receiver,
+        }
+    }
+}
+
+impl AsyncTasks {
+    fn spawn<T, Fut, F>(&mut self, entity: Option<Entity>, future: Fut, on_complete: F) -> TaskId
+    where
+        T: Send + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+        F: FnOnce(T, &mut World) + Send + 'static,
+    {
+        let id = TaskId(self.next_id);
+        self.next_id += 1;
+
+        let sender = self.sender.clone();
+        let future = async move {
+            let output = future.await;
+            let command: TaskCommand = Box::new(move |world| on_complete(output, world));
+            // The receiver lives as long as the world, which is gone if this fails.
+            let _ = sender.send((id, command));
+        };
+
+        #[cfg(not(target_arch = "wasm32"))]
+        let handle = AsyncComputeTaskPool::get().spawn(future);
+        #[cfg(target_arch = "wasm32")]
+        let handle = {
+            AsyncComputeTaskPool::get().spawn(future);
+        };
+
+        self.tasks.insert(
+            id,
+            TrackedTask {
+                entity,
+                _handle: handle,
+            },
+        );
+        id
+    }
+
+    /// Returns `true` if the task has neither completed nor been cancelled.
+    pub fn is_running(&self, id: TaskId) -> bool {
+        self.tasks.contains_key(&id)
+    }
+
+    /// Returns the number of running tasks.
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Returns `true` if there are no running tasks.
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Cancels a task. Its result will not be delivered.
+    ///
+    /// Returns `false` if the task had already completed or been cancelled.
+    pub fn cancel(&mut self, id: TaskId) -> bool {
+        self.tasks.remove(&id).is_some()
+    }
+
+    /// Cancels all the tasks tied to `entity`.
+    pub fn cancel_entity(&mut self, entity: Entity) {
+        self.tasks.retain(|_, task| task.entity != Some(entity));
+    }
+}
+
+/// A [`SystemParam`] to spawn tasks on the [`AsyncComputeTaskPool`] whose results are delivered
+/// back into the [`World`] when they complete, instead of having to be polled every frame.
+///
+/// Results are applied by [`apply_completed_tasks`], which `CorePlugin` runs at the start of
+/// [`CoreStage::PreUpdate`](bevy_app::CoreStage::PreUpdate). Tasks tied to an entity are
+/// cancelled when the entity is despawned.
+///
+/// ```
+/// # use bevy_core::Tasks;
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Component)]
+/// struct Path(Vec<u32>);
+///
+/// fn find_paths(mut tasks: Tasks, query: Query<Entity, Without<Path>>) {
+///     for entity in &query {
+///         tasks.insert_on_completion(entity, async move {
+///             // Expensive work
+///             Path(vec![1, 2, 3])
+///         });
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(find_paths);
+/// ```
+#[derive(SystemParam)]
+pub struct Tasks<'w, 's> {
+    tasks: ResMut<'w, AsyncTasks>,
+    #[system_param(ignore)]
+    marker: PhantomData<&'s ()>,
+}
+
+impl<'w, 's> Tasks<'w, 's> {
+    /// Spawns a task that runs `on_complete` on the [`World`] with its output.
+    pub fn spawn<T, Fut, F>(&mut self, future: Fut, on_complete: F) -> TaskId
+    where
+        T: Send + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+        F: FnOnce(T, &mut World) + Send + 'static,
+    {
+        self.tasks.spawn(None, future, on_complete)
+    }
+
+    /// Spawns a task tied to `entity`, that runs `on_complete` on the [`World`] with its output.
+    ///
+    /// The task is cancelled if `entity` is despawned before it completes.
+    pub fn spawn_for<T, Fut, F>(&mut self, entity: Entity, future: Fut, on_complete: F) -> TaskId
+    where
+        T: Send + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+        F: FnOnce(T, Entity, &mut World) + Send + 'static,
+    {
+        self.tasks
+            .spawn(Some(entity), future, move |output, world| {
+                // The entity may have been despawned by the result of another task.
+                if world.get_entity(entity).is_some() {
+                    on_complete(output, entity, world);
+                }
+            })
+    }
+
+    /// Spawns a task tied to `entity`, whose output is inserted as a component on `entity`.
+    ///
+    /// The task is cancelled if `entity` is despawned before it completes.
+    pub fn insert_on_completion<C, Fut>(&mut self, entity: Entity, future: Fut) -> TaskId
+    where
+        C: Component,
+        Fut: Future<Output = C> + Send + 'static,
+    {
+        self.spawn_for(entity, future, |component, entity, world| {
+            world.entity_mut(entity).insert(component);
+        })
+    }
+
+    /// Spawns a task whose output is sent as an event.
+    ///
+    /// # Panics
+    ///
+    /// Applying the result panics if the event was not added to the app.
+    pub fn send_on_completion<E, Fut>(&mut self, future: Fut) -> TaskId
+    where
+        E: Event,
+        Fut: Future<Output = E> + Send + 'static,
+    {
+        self.spawn(future, |event, world| {
+            world.resource_mut::<Events<E>>().send(event);
+        })
+    }
+
+    /// Returns `true` if the task has neither completed nor been cancelled.
+    pub fn is_running(&self, id: TaskId) -> bool {
+        self.tasks.is_running(id)
+    }
+
+    /// Cancels a task. Its result will not be delivered.
+    ///
+    /// Returns `false` if the task had already completed or been cancelled.
+    pub fn cancel(&mut self, id: TaskId) -> bool {
+        self.tasks.cancel(id)
+    }
+
+    /// Cancels all the tasks tied to `entity`.
+    pub fn cancel_entity(&mut self, entity: Entity) {
+        self.tasks.cancel_entity(entity);
+    }
+}
+
+/// Cancels the tasks whose entity was despawned, and applies the results of the completed
+/// tasks to the [`World`].
+pub fn apply_completed_tasks(world: &mut World) {
+    let completed = world.resource_scope(|world, mut tasks: Mut<AsyncTasks>| {
+        tasks.tasks.retain(|_, task| match task.entity {
+            Some(entity) => world.get_entity(entity).is_some(),
+            None => true,
+        });
+        let AsyncTasks {
+            tasks, receiver, ..
+        } = &mut *tasks;
+        receiver
+            .try_iter()
+            .filter(|(id, _)| tasks.remove(id).is_some())
+            .map(|(_, command)| command)
+            .collect::<Vec<_>>()
+    });
+    for command in completed {
+        command(world);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::{IntoSystem, System};
+    use bevy_tasks::TaskPool;
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Output(u32);
+
+    struct Done(u32);
+
+    fn setup() -> World {
+        AsyncComputeTaskPool::init(TaskPool::default);
+        let mut world = World::new();
+        world.init_resource::<AsyncTasks>();
+        world
+    }
+
+    fn run_system<Param>(world: &mut World, system: impl IntoSystem<(), (), Param>) {
+        let mut system = IntoSystem::into_system(system);
+        system.initialize(world);
+        system.run((), world);
+        system.apply_buffers(world);
+    }
+
+    /// Applies completed tasks until none are running.
+    fn wait_for_tasks(world: &mut World) {
+        while !world.resource::<AsyncTasks>().is_empty() {
+            apply_completed_tasks(world);
+            std::thread::yield_now();
+        }
+    }
+
+    #[test]
+    fn insert_on_completion() {
+        let mut world = setup();
+        let entity = world.spawn().id();
+        run_system(&mut world, move |mut tasks: Tasks| {
+            tasks.insert_on_completion(entity, async { Output(3) });
+        });
+        wait_for_tasks(&mut world);
+        assert_eq!(world.get::<Output>(entity), Some(&Output(3)));
+    }
+
+    #[test]
+    fn send_on_completion() {
+        let mut world = setup();
+        world.init_resource::<Events<Done>>();
+        run_system(&mut world, |mut tasks: Tasks| {
+            tasks.send_on_completion(async { Done(5) });
+        });
+        wait_for_tasks(&mut world);
+        let events = world.resource::<Events<Done>>();
+        let mut reader = events.get_reader();
+        let values: Vec<u32> = reader.iter(events).map(|event| event.0).collect();
+        assert_eq!(values, vec![5]);
+    }
+
+    #[test]
+    fn despawn_cancels_task() {
+        let mut world = setup();
+        let entity = world.spawn().id();
+        let (sender, receiver) = crossbeam_channel::bounded::<()>(0);
+        run_system(&mut world, move |mut tasks: Tasks| {
+            let receiver = receiver.clone();
+            tasks.insert_on_completion(entity, async move {
+                let _ = receiver.recv();
+                Output(1)
+            });
+        });
+        world.despawn(entity);
+        apply_completed_tasks(&mut world);
+        assert!(world.resource::<AsyncTasks>().is_empty());
+        drop(sender);
+    }
+
+    #[test]
+    fn cancelled_result_is_dropped() {
+        let mut world = setup();
+        let (sender, receiver) = crossbeam_channel::bounded::<()>(1);
+        let id = world.resource_mut::<AsyncTasks>().spawn(
+            None,
+            async move {
+                let _ = receiver.recv();
+            },
+            |_, world| world.insert_resource(Done(0)),
+        );
+        assert!(world.resource::<AsyncTasks>().is_running(id));
+        assert!(world.resource_mut::<AsyncTasks>().cancel(id));
+        sender.send(()).unwrap();
+        apply_completed_tasks(&mut world);
+        assert!(!world.contains_resource::<Done>());
+    }
+}
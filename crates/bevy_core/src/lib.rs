@@ -1,9 +1,11 @@
 #![warn(missing_docs)]
 //! This crate provides core functionality for Bevy Engine.
 
+mod async_tasks;
 mod name;
 mod task_pool_options;
 
+pub use async_tasks::*;
 pub use bytemuck::{bytes_of, cast_slice, Pod, Zeroable};
 pub use name::*;
 pub use task_pool_options::*;
@@ -11,11 +13,13 @@ pub use task_pool_options::*;
 pub mod prelude {
     //! The Bevy Core Prelude.
     #[doc(hidden)]
-    pub use crate::{DefaultTaskPoolOptions, Name};
+    pub use crate::{DefaultTaskPoolOptions, Name, Tasks};
 }
 
 use bevy_app::prelude::*;
-use bevy_ecs::entity::Entity;
+use bevy_ecs::{
+    entity::Entity, schedule::ExclusiveSystemDescriptorCoercion, system::IntoExclusiveSystem,
+};
 use bevy_utils::HashSet;
 use std::ops::Range;
 
@@ -34,6 +38,11 @@ impl Plugin for CorePlugin {
 
         app.register_type::<Entity>().register_type::<Name>();
 
+        app.init_resource::<AsyncTasks>().add_system_to_stage(
+            CoreStage::PreUpdate,
+            apply_completed_tasks.exclusive_system().at_start(),
+        );
+
         register_rust_types(app);
         register_math_types(app);
     }
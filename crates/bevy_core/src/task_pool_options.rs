@@ -33,6 +33,10 @@ impl TaskPoolThreadAssignmentPolicy {
 /// Helper for configuring and creating the default task pools. For end-users who want full control,
 /// insert the default task pools into the resource map manually. If the pools are already inserted,
 /// this helper will do nothing.
+///
+/// The number of threads of each pool is fixed when the pools are created: an idle pool does not
+/// lend its threads to a busy one. A thread waiting on a pool can still work for it, see
+/// [`TaskPool::block_on`](bevy_tasks::TaskPool::block_on).
 #[derive(Clone)]
 pub struct DefaultTaskPoolOptions {
     /// If the number of physical cores is less than min_total_threads, force using
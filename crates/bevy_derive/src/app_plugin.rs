@@ -14,5 +14,11 @@ pub fn derive_dynamic_plugin(input: TokenStream) -> TokenStream {
             let boxed = Box::new(object);
             Box::into_raw(boxed)
         }
+
+        #[no_mangle]
+        #[allow(improper_ctypes_definitions)]
+        pub extern "C" fn _bevy_plugin_abi() -> bevy::app::PluginAbi {
+            bevy::app::PluginAbi::current()
+        }
     })
 }
@@ -0,0 +1,150 @@
+use crate::{Diagnostic, DiagnosticId, Diagnostics};
+use bevy_app::prelude::*;
+use bevy_ecs::system::{Res, ResMut};
+use bevy_tasks::{AsyncComputeTaskPool, ComputeTaskPool, IoTaskPool, TaskPool, TaskPoolMetrics};
+use bevy_time::Time;
+use std::time::Duration;
+
+/// Adds "queued tasks", "active tasks" and "busy" diagnostics for each of the global task pools
+/// to an App.
+///
+/// "busy" is the percentage of the pool's thread time spent running tasks since the previous
+/// frame. The task pools must have been initialized, which `CorePlugin` does.
+#[derive(Default)]
+pub struct TaskPoolDiagnosticsPlugin;
+
+/// The busy time of each pool at the previous frame.
+#[derive(Default)]
+pub struct TaskPoolDiagnosticsState {
+    compute_busy_time: Duration,
+    async_compute_busy_time: Duration,
+    io_busy_time: Duration,
+}
+
+impl Plugin for TaskPoolDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system(Self::setup_system)
+            .init_resource::<TaskPoolDiagnosticsState>()
+            .add_system(Self::diagnostic_system);
+    }
+}
+
+impl TaskPoolDiagnosticsPlugin {
+    pub const COMPUTE_QUEUED_TASKS: DiagnosticId =
+        DiagnosticId::from_u128(264209171958619738865397083456685137639);
+    pub const COMPUTE_ACTIVE_TASKS: DiagnosticId =
+        DiagnosticId::from_u128(8530705277198350507056644798478290695);
+    pub const COMPUTE_BUSY: DiagnosticId =
+        DiagnosticId::from_u128(2264927316601233977492966195455244044);
+    pub const ASYNC_COMPUTE_QUEUED_TASKS: DiagnosticId =
+        DiagnosticId::from_u128(64386861071959647548161541662329537217);
+    pub const ASYNC_COMPUTE_ACTIVE_TASKS: DiagnosticId =
+        DiagnosticId::from_u128(30931378868904584874451026358335420257);
+    pub const ASYNC_COMPUTE_BUSY: DiagnosticId =
+        DiagnosticId::from_u128(10114914566001031477881334306378232690);
+    pub const IO_QUEUED_TASKS: DiagnosticId =
+        DiagnosticId::from_u128(154868074809532597582634051111538845642);
+    pub const IO_ACTIVE_TASKS: DiagnosticId =
+        DiagnosticId::from_u128(288076998790859871111000995048412688154);
+    pub const IO_BUSY: DiagnosticId =
+        DiagnosticId::from_u128(138357267993683239370097002681338164787);
+
+    pub fn setup_system(mut diagnostics: ResMut<Diagnostics>) {
+        for (prefix, queued, active, busy) in [
+            (
+                "compute_pool",
+                Self::COMPUTE_QUEUED_TASKS,
+                Self::COMPUTE_ACTIVE_TASKS,
+                Self::COMPUTE_BUSY,
+            ),
+            (
+                "async_compute_pool",
+                Self::ASYNC_COMPUTE_QUEUED_TASKS,
+                Self::ASYNC_COMPUTE_ACTIVE_TASKS,
+                Self::ASYNC_COMPUTE_BUSY,
+            ),
+            (
+                "io_pool",
+                Self::IO_QUEUED_TASKS,
+                Self::IO_ACTIVE_TASKS,
+                Self::IO_BUSY,
+            ),
+        ] {
+            diagnostics.add(Diagnostic::new(
+                queued,
+                format!("{}_queued_tasks", prefix),
+                20,
+            ));
+            diagnostics.add(Diagnostic::new(
+                active,
+                format!("{}_active_tasks", prefix),
+                20,
+            ));
+            diagnostics.add(Diagnostic::new(busy, format!("{}_busy", prefix), 20).with_suffix("%"));
+        }
+    }
+
+    pub fn diagnostic_system(
+        mut diagnostics: ResMut<Diagnostics>,
+        time: Res<Time>,
+        mut state: ResMut<TaskPoolDiagnosticsState>,
+    ) {
+        let state = &mut *state;
+        let delta = time.delta();
+        Self::add_measurements(
+            &mut diagnostics,
+            ComputeTaskPool::get(),
+            delta,
+            &mut state.compute_busy_time,
+            [
+                Self::COMPUTE_QUEUED_TASKS,
+                Self::COMPUTE_ACTIVE_TASKS,
+                Self::COMPUTE_BUSY,
+            ],
+        );
+        Self::add_measurements(
+            &mut diagnostics,
+            AsyncComputeTaskPool::get(),
+            delta,
+            &mut state.async_compute_busy_time,
+            [
+                Self::ASYNC_COMPUTE_QUEUED_TASKS,
+                Self::ASYNC_COMPUTE_ACTIVE_TASKS,
+                Self::ASYNC_COMPUTE_BUSY,
+            ],
+        );
+        Self::add_measurements(
+            &mut diagnostics,
+            IoTaskPool::get(),
+            delta,
+            &mut state.io_busy_time,
+            [Self::IO_QUEUED_TASKS, Self::IO_ACTIVE_TASKS, Self::IO_BUSY],
+        );
+    }
+
+    fn add_measurements(
+        diagnostics: &mut Diagnostics,
+        pool: &TaskPool,
+        delta: Duration,
+        previous_busy_time: &mut Duration,
+        [queued, active, busy]: [DiagnosticId; 3],
+    ) {
+        let TaskPoolMetrics {
+            thread_count,
+            queued_tasks,
+            active_tasks,
+            busy_time,
+        } = pool.metrics();
+        diagnostics.add_measurement(queued, || queued_tasks as f64);
+        diagnostics.add_measurement(active, || active_tasks as f64);
+
+        let busy_delta = busy_time.saturating_sub(*previous_busy_time);
+        *previous_busy_time = busy_time;
+        if delta.is_zero() || thread_count == 0 {
+            return;
+        }
+        diagnostics.add_measurement(busy, || {
+            100.0 * busy_delta.as_secs_f64() / (delta.as_secs_f64() * thread_count as f64)
+        });
+    }
+}
@@ -0,0 +1,405 @@
+use crate::{
+    load_dynamic_plugin, remove_versioned_library, DynamicPluginError, DynamicPluginLibrary,
+};
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    entity::Entity,
+    reflect::{ReflectComponent, ReflectResource},
+    schedule::{Schedule, Stage, StageLabel},
+    world::World,
+};
+use bevy_reflect::{
+    serde::{ReflectDeserializer, ReflectSerializer},
+    TypeRegistryArc,
+};
+use bevy_utils::tracing::{error, info, warn};
+use serde::de::DeserializeSeed;
+use std::{
+    any::TypeId,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// The label of the [`HotReloadStage`], which runs after [`CoreStage::Update`](bevy_app::CoreStage::Update).
+#[derive(Debug, Hash, PartialEq, Eq, Clone, StageLabel)]
+pub struct DynamicPluginStage;
+
+/// A [`Stage`] running the systems of hot-reloadable dynamic plugins, and reloading them when
+/// their library changes on disk.
+///
+/// A hot-reloadable plugin is built into a schedule of its own rather than the app's, so that its
+/// systems can be dropped when it is reloaded. Before a reload, the reflected components and
+/// resources whose types the plugin registered are serialized and removed from the
+/// [`World`], along with their registrations. The new version of the library is then built,
+/// and the serialized state is deserialized using the types it registered. Startup systems of the
+/// plugin run again after each reload.
+///
+/// Previous versions of a library stay loaded until this stage is dropped, as the [`World`] may
+/// still hold values and component metadata pointing into their code. Their versioned copies are
+/// removed on the next reload where the platform allows removing a loaded library, and otherwise
+/// when this stage is dropped.
+#[derive(Default)]
+pub struct HotReloadStage {
+    plugins: Vec<HotReloadablePlugin>,
+}
+
+impl HotReloadStage {
+    /// Adds a plugin to this stage.
+    pub fn add(&mut self, plugin: HotReloadablePlugin) -> &mut Self {
+        self.plugins.push(plugin);
+        self
+    }
+}
+
+impl Stage for HotReloadStage {
+    fn run(&mut self, world: &mut World) {
+        for plugin in &mut self.plugins {
+            if plugin.has_changed() {
+                // SAFETY: upheld by the caller of `DynamicPluginExt::load_hot_reloadable_plugin`
+                if let Err(error) = unsafe { plugin.reload(world) } {
+                    error!(
+                        "failed to reload dynamic plugin {:?}: {}",
+                        plugin.source, error
+                    );
+                }
+            }
+            plugin.schedule.run(world);
+        }
+    }
+}
+
+/// A dynamic plugin that can be reloaded from the library it was loaded from.
+pub struct HotReloadablePlugin {
+    source: PathBuf,
+    last_modified: Option<SystemTime>,
+    schedule: Schedule,
+    registered_types: Vec<TypeId>,
+    /// The loaded versions of the library, the current one last.
+    libraries: Vec<DynamicPluginLibrary>,
+    /// The versioned copies of previous versions that could not be removed yet.
+    stale_copies: Vec<PathBuf>,
+}
+
+impl HotReloadablePlugin {
+    /// Loads the library at `path` and builds its plugin, with its systems in a schedule of its
+    /// own.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`DynamicPluginExt::load_hot_reloadable_plugin`](crate::DynamicPluginExt::load_hot_reloadable_plugin).
+    pub unsafe fn load(path: &Path, world: &mut World) -> Result<Self, DynamicPluginError> {
+        let last_modified = modified(path);
+        let library = load_dynamic_plugin(path, 0)?;
+        let (schedule, registered_types) = build_plugin(library.plugin(), world);
+        info!("loaded dynamic plugin {:?}", library.plugin().name());
+        Ok(Self {
+            source: path.to_path_buf(),
+            last_modified,
+            schedule,
+            registered_types,
+            libraries: vec![library],
+            stale_copies: Vec::new(),
+        })
+    }
+
+    /// The version of the currently loaded library, incremented on each reload.
+    pub fn version(&self) -> u32 {
+        self.library().version()
+    }
+
+    fn library(&self) -> &DynamicPluginLibrary {
+        self.libraries.last().unwrap()
+    }
+
+    fn has_changed(&mut self) -> bool {
+        let last_modified = modified(&self.source);
+        if last_modified.is_some() && last_modified != self.last_modified {
+            self.last_modified = last_modified;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Replaces the plugin with a newly loaded version of its library, carrying over its state.
+    ///
+    /// If the new library fails to load, the current one is kept.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`DynamicPluginExt::load_hot_reloadable_plugin`](crate::DynamicPluginExt::load_hot_reloadable_plugin).
+    pub unsafe fn reload(&mut self, world: &mut World) -> Result<(), DynamicPluginError> {
+        let library = load_dynamic_plugin(&self.source, self.version() + 1)?;
+
+        let state = PluginState::take(world, &self.registered_types);
+        self.schedule = Schedule::default();
+        self.stale_copies.push(self.library().path().to_path_buf());
+        self.stale_copies
+            .retain(|path| remove_versioned_library(path).is_err());
+        self.libraries.push(library);
+
+        let (schedule, registered_types) = build_plugin(self.library().plugin(), world);
+        self.schedule = schedule;
+        self.registered_types = registered_types;
+        state.restore(world);
+
+        info!(
+            "reloaded dynamic plugin {:?} as version {}",
+            self.library().plugin().name(),
+            self.version()
+        );
+        Ok(())
+    }
+}
+
+impl Drop for HotReloadablePlugin {
+    fn drop(&mut self) {
+        // The systems hold the plugin's code, so they are dropped before the libraries.
+        self.schedule = Schedule::default();
+        let paths: Vec<PathBuf> = self
+            .libraries
+            .drain(..)
+            .map(|library| library.path().to_path_buf())
+            .collect();
+        for path in &paths {
+            if let Err(error) = remove_versioned_library(path) {
+                warn!(
+                    "failed to remove the plugin library copy {:?}: {}",
+                    path, error
+                );
+            }
+        }
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Builds `plugin` against `world` into a new [`Schedule`], returning it along with the types
+/// the plugin registered.
+fn build_plugin(plugin: &dyn Plugin, world: &mut World) -> (Schedule, Vec<TypeId>) {
+    let registered_before = registered_types(world);
+
+    let mut app = App::empty();
+    app.add_default_stages();
+    std::mem::swap(&mut app.world, world);
+    plugin.build(&mut app);
+    plugin.finish(&mut app);
+    plugin.cleanup(&mut app);
+    std::mem::swap(&mut app.world, world);
+
+    let registered_types = registered_types(world)
+        .into_iter()
+        .filter(|type_id| !registered_before.contains(type_id))
+        .collect();
+    (app.schedule, registered_types)
+}
+
+fn registered_types(world: &World) -> Vec<TypeId> {
+    world
+        .get_resource::<TypeRegistryArc>()
+        .map(|registry| {
+            registry
+                .read()
+                .iter()
+                .map(|registration| registration.type_id())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The reflected components and resources of a plugin, serialized so that they outlive the
+/// library defining their types.
+#[derive(Default)]
+struct PluginState {
+    components: Vec<(Entity, String)>,
+    resources: Vec<String>,
+}
+
+impl PluginState {
+    /// Serializes then removes the components and resources of the given types from `world`,
+    /// and removes the registrations of these types.
+    fn take(world: &mut World, types: &[TypeId]) -> Self {
+        let mut state = PluginState::default();
+        let registry_arc = match world.get_resource::<TypeRegistryArc>() {
+            Some(registry) => registry.clone(),
+            None => return state,
+        };
+        let mut registry = registry_arc.write();
+
+        for type_id in types {
+            let registration = match registry.get(*type_id) {
+                Some(registration) => registration,
+                None => continue,
+            };
+            if let Some(reflect_component) = registration.data::<ReflectComponent>() {
+                let entities: Vec<Entity> = world
+                    .components()
+                    .get_id(*type_id)
+                    .map(|component_id| {
+                        world
+                            .archetypes()
+                            .iter()
+                            .filter(|archetype| archetype.contains(component_id))
+                            .flat_map(|archetype| archetype.entities().iter().copied())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                for entity in entities {
+                    if let Some(component) = reflect_component.reflect(world, entity) {
+                        match serialize(component, &registry) {
+                            Ok(serialized) => state.components.push((entity, serialized)),
+                            Err(error) => warn!(
+                                "failed to serialize {} on {:?}: {}",
+                                registration.type_name(),
+                                entity,
+                                error
+                            ),
+                        }
+                    }
+                    reflect_component.remove(world, entity);
+                }
+            }
+            if let Some(reflect_resource) = registration.data::<ReflectResource>() {
+                if let Some(resource) = reflect_resource.reflect(world) {
+                    match serialize(resource, &registry) {
+                        Ok(serialized) => state.resources.push(serialized),
+                        Err(error) => warn!(
+                            "failed to serialize resource {}: {}",
+                            registration.type_name(),
+                            error
+                        ),
+                    }
+                    reflect_resource.remove(world);
+                }
+            }
+        }
+
+        for type_id in types {
+            registry.remove(*type_id);
+        }
+        state
+    }
+
+    /// Deserializes the components and resources back into `world`, using the types currently
+    /// registered.
+    fn restore(self, world: &mut World) {
+        let registry_arc = match world.get_resource::<TypeRegistryArc>() {
+            Some(registry) => registry.clone(),
+            None => return,
+        };
+        let registry = registry_arc.read();
+
+        for (entity, serialized) in &self.components {
+            let value = match deserialize(serialized, &registry) {
+                Ok(value) => value,
+                Err(error) => {
+                    warn!("failed to deserialize component of {:?}: {}", entity, error);
+                    continue;
+                }
+            };
+            match registry
+                .get_with_name(value.type_name())
+                .and_then(|registration| registration.data::<ReflectComponent>())
+            {
+                Some(reflect_component) if world.get_entity(*entity).is_some() => {
+                    reflect_component.insert(world, *entity, &*value);
+                }
+                Some(_) => {}
+                None => warn!(
+                    "component {} is no longer registered by the reloaded plugin",
+                    value.type_name()
+                ),
+            }
+        }
+
+        for serialized in &self.resources {
+            let value = match deserialize(serialized, &registry) {
+                Ok(value) => value,
+                Err(error) => {
+                    warn!("failed to deserialize resource: {}", error);
+                    continue;
+                }
+            };
+            match registry
+                .get_with_name(value.type_name())
+                .and_then(|registration| registration.data::<ReflectResource>())
+            {
+                Some(reflect_resource) => reflect_resource.insert(world, &*value),
+                None => warn!(
+                    "resource {} is no longer registered by the reloaded plugin",
+                    value.type_name()
+                ),
+            }
+        }
+    }
+}
+
+fn serialize(
+    value: &dyn bevy_reflect::Reflect,
+    registry: &bevy_reflect::TypeRegistry,
+) -> Result<String, ron::Error> {
+    ron::to_string(&ReflectSerializer::new(value, registry))
+}
+
+fn deserialize(
+    serialized: &str,
+    registry: &bevy_reflect::TypeRegistry,
+) -> Result<Box<dyn bevy_reflect::Reflect>, ron::Error> {
+    let mut deserializer = ron::de::Deserializer::from_str(serialized)?;
+    ReflectDeserializer::new(registry).deserialize(&mut deserializer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PluginState;
+    use bevy_ecs::{
+        prelude::*,
+        reflect::{ReflectComponent, ReflectResource},
+    };
+    use bevy_reflect::{Reflect, TypeRegistryArc};
+    use std::any::TypeId;
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Health(u32);
+
+    #[derive(Reflect, Default, Debug, PartialEq)]
+    #[reflect(Resource)]
+    struct Score {
+        value: u32,
+    }
+
+    #[test]
+    fn state_round_trip() {
+        let mut world = World::new();
+        let registry = TypeRegistryArc::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<Health>();
+            registry.register::<Score>();
+        }
+        world.insert_resource(registry.clone());
+        world.insert_resource(Score { value: 12 });
+        let entity = world.spawn().insert(Health(3)).id();
+
+        let types = [TypeId::of::<Health>(), TypeId::of::<Score>()];
+        let state = PluginState::take(&mut world, &types);
+        assert!(world.get::<Health>(entity).is_none());
+        assert!(!world.contains_resource::<Score>());
+        assert!(registry.read().get(TypeId::of::<Health>()).is_none());
+
+        // The reloaded plugin registers its types again.
+        {
+            let mut registry = registry.write();
+            registry.register::<Health>();
+            registry.register::<Score>();
+        }
+        state.restore(&mut world);
+        assert_eq!(world.get::<Health>(entity), Some(&Health(3)));
+        assert_eq!(world.resource::<Score>(), &Score { value: 12 });
+    }
+}
@@ -1,6 +1,69 @@
 use libloading::{Library, Symbol};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
 
-use bevy_app::{App, CreatePlugin, Plugin};
+use bevy_app::{App, CreatePlugin, GetPluginAbi, Plugin, PluginAbi};
+use bevy_utils::tracing::warn;
+
+/// Errors that occur while loading a dynamic plugin.
+#[derive(Error, Debug)]
+pub enum DynamicPluginError {
+    /// The library could not be copied to its [versioned path](versioned_library_path).
+    #[error("failed to copy the plugin library to {path:?}: {error}")]
+    Copy {
+        /// The versioned path the library was copied to.
+        path: PathBuf,
+        /// The error returned by the copy.
+        error: std::io::Error,
+    },
+    /// The platform loader failed to load the library, or to find `_bevy_create_plugin` in it.
+    #[error(transparent)]
+    Library(#[from] libloading::Error),
+    /// The library does not export `_bevy_plugin_abi`, so it can't be checked to be compatible.
+    #[error("plugin library {0:?} does not export `_bevy_plugin_abi`; it should be created by deriving `DynamicPlugin`")]
+    MissingAbi(PathBuf),
+    /// The library was built against a different version or build of Bevy than this program.
+    #[error("plugin library was built against bevy {found}, but this program uses bevy {expected} (or a different build of it)")]
+    AbiMismatch {
+        /// The version of Bevy this program uses.
+        expected: String,
+        /// The version of Bevy the library was built against.
+        found: String,
+    },
+}
+
+/// A dynamic plugin library, along with the [`Plugin`] it created.
+///
+/// Dropping it drops the plugin and unloads the library.
+pub struct DynamicPluginLibrary {
+    // Declared before `library` so that the plugin is dropped while its code is still loaded.
+    plugin: Box<dyn Plugin>,
+    library: Library,
+    path: PathBuf,
+    version: u32,
+}
+
+impl DynamicPluginLibrary {
+    /// The [`Plugin`] created by the library.
+    pub fn plugin(&self) -> &dyn Plugin {
+        &*self.plugin
+    }
+
+    /// The path of the versioned copy the library was loaded from.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The version this library was loaded as.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Gives up ownership of the [`Plugin`] and [`Library`], in that order.
+    pub fn into_parts(self) -> (Box<dyn Plugin>, Library) {
+        (self.plugin, self.library)
+    }
+}
 
 /// Dynamically links a plugin at the given path. The plugin must export a function with the
 /// [`CreatePlugin`] signature named `_bevy_create_plugin`.
@@ -17,18 +80,193 @@ pub unsafe fn dynamically_load_plugin(path: &str) -> (Library, Box<dyn Plugin>)
     (lib, plugin)
 }
 
+/// Returns the path a library is copied to when loaded as the given `version`.
+///
+/// `libgame.so` loaded as version 3 becomes `libgame.3.so`, in the same directory.
+pub fn versioned_library_path(path: &Path, version: u32) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let file_name = match path.extension() {
+        Some(extension) => format!("{}.{}.{}", stem, version, extension.to_string_lossy()),
+        None => format!("{}.{}", stem, version),
+    };
+    path.with_file_name(file_name)
+}
+
+/// Checks that a library reporting the [`PluginAbi`] `found` can be loaded by this program.
+pub fn check_plugin_abi(found: PluginAbi) -> Result<(), DynamicPluginError> {
+    let expected = PluginAbi::current();
+    if found == expected {
+        Ok(())
+    } else {
+        Err(DynamicPluginError::AbiMismatch {
+            expected: expected.bevy_version.to_string(),
+            found: found.bevy_version.to_string(),
+        })
+    }
+}
+
+/// Removes the versioned copy of a library at `path`, if it still exists.
+///
+/// Platforms that lock loaded libraries, like Windows, refuse to remove a library that is
+/// still loaded.
+pub(crate) fn remove_versioned_library(path: &Path) -> std::io::Result<()> {
+    match std::fs::remove_file(path) {
+        Err(error) if error.kind() != std::io::ErrorKind::NotFound => Err(error),
+        _ => Ok(()),
+    }
+}
+
+/// Copies the library at `path` to its [versioned path](versioned_library_path), checks its
+/// [`PluginAbi`] and creates its plugin.
+///
+/// Loading from a versioned copy leaves the original file free to be overwritten by the
+/// compiler, and prevents the platform loader from handing back an earlier version of the
+/// library that is still loaded. The copy is removed if the library fails to load; otherwise
+/// it is up to the caller to remove it once the library is unloaded.
+///
+/// # Safety
+///
+/// The `_bevy_create_plugin` and `_bevy_plugin_abi` symbols must not be manually created, but
+/// instead created by deriving `DynamicPlugin` on a unit struct implementing [`Plugin`].
+pub unsafe fn load_dynamic_plugin(
+    path: impl AsRef<Path>,
+    version: u32,
+) -> Result<DynamicPluginLibrary, DynamicPluginError> {
+    let versioned_path = versioned_library_path(path.as_ref(), version);
+    std::fs::copy(path.as_ref(), &versioned_path).map_err(|error| DynamicPluginError::Copy {
+        path: versioned_path.clone(),
+        error,
+    })?;
+
+    let loaded = load_versioned_library(&versioned_path);
+    if loaded.is_err() {
+        if let Err(error) = remove_versioned_library(&versioned_path) {
+            warn!(
+                "failed to remove the plugin library copy {:?}: {}",
+                versioned_path, error
+            );
+        }
+    }
+    let (library, plugin) = loaded?;
+    Ok(DynamicPluginLibrary {
+        plugin,
+        library,
+        path: versioned_path,
+        version,
+    })
+}
+
+unsafe fn load_versioned_library(
+    path: &Path,
+) -> Result<(Library, Box<dyn Plugin>), DynamicPluginError> {
+    let library = Library::new(path)?;
+    let abi: Symbol<GetPluginAbi> = library
+        .get(b"_bevy_plugin_abi")
+        .map_err(|_| DynamicPluginError::MissingAbi(path.to_path_buf()))?;
+    check_plugin_abi(abi())?;
+
+    let create_plugin: Symbol<CreatePlugin> = library.get(b"_bevy_create_plugin")?;
+    let plugin = Box::from_raw(create_plugin());
+    Ok((library, plugin))
+}
+
 pub trait DynamicPluginExt {
     /// # Safety
     ///
     /// Same as [`dynamically_load_plugin`].
     unsafe fn load_plugin(&mut self, path: &str) -> &mut Self;
+
+    /// Loads a plugin that is reloaded whenever the library at `path` changes.
+    ///
+    /// See [`HotReloadStage`](crate::HotReloadStage) for how its systems and state are handled.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`load_dynamic_plugin`]. In addition, the types of the components and resources
+    /// the plugin stores in the [`World`](bevy_ecs::world::World) must keep the same memory
+    /// layout across reloads.
+    unsafe fn load_hot_reloadable_plugin(&mut self, path: impl AsRef<Path>) -> &mut Self;
 }
 
 impl DynamicPluginExt for App {
     unsafe fn load_plugin(&mut self, path: &str) -> &mut Self {
         let (lib, plugin) = dynamically_load_plugin(path);
         std::mem::forget(lib); // Ensure that the library is not automatically unloaded
-        plugin.build(self);
+        self.add_boxed_plugin(plugin)
+    }
+
+    unsafe fn load_hot_reloadable_plugin(&mut self, path: impl AsRef<Path>) -> &mut Self {
+        let plugin = crate::HotReloadablePlugin::load(path.as_ref(), &mut self.world)
+            .unwrap_or_else(|error| panic!("failed to load dynamic plugin: {}", error));
+        if self
+            .schedule
+            .get_stage::<crate::HotReloadStage>(&crate::DynamicPluginStage)
+            .is_none()
+        {
+            self.add_stage_after(
+                bevy_app::CoreStage::Update,
+                crate::DynamicPluginStage,
+                crate::HotReloadStage::default(),
+            );
+        }
+        self.schedule.stage(
+            crate::DynamicPluginStage,
+            |stage: &mut crate::HotReloadStage| {
+                stage.add(plugin);
+                stage
+            },
+        );
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::any::TypeId;
+
+    #[test]
+    fn abi_mismatch() {
+        assert!(check_plugin_abi(PluginAbi::current()).is_ok());
+
+        let other_version = PluginAbi {
+            bevy_version: "0.1.0",
+            ..PluginAbi::current()
+        };
+        assert!(matches!(
+            check_plugin_abi(other_version),
+            Err(DynamicPluginError::AbiMismatch { expected, found })
+                if expected == PluginAbi::BEVY_VERSION && found == "0.1.0"
+        ));
+
+        // another build of the same version
+        let other_build = PluginAbi {
+            app_type_id: TypeId::of::<()>(),
+            ..PluginAbi::current()
+        };
+        assert!(matches!(
+            check_plugin_abi(other_build),
+            Err(DynamicPluginError::AbiMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn invalid_library_copy_is_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("libinvalid.so");
+        std::fs::write(&path, b"not a library").unwrap();
+
+        // SAFETY: the file is not a library, so no code is run from it
+        let result = unsafe { load_dynamic_plugin(&path, 1) };
+        assert!(matches!(result, Err(DynamicPluginError::Library(_))));
+        assert!(!versioned_library_path(&path, 1).exists());
+
+        assert!(matches!(
+            unsafe { load_dynamic_plugin(dir.path().join("missing.so"), 0) },
+            Err(DynamicPluginError::Copy { .. })
+        ));
+    }
+}
@@ -0,0 +1,406 @@
+use crate::{
+    query::{Fetch, QueryFetch, QueryItem, QueryState, WorldQuery},
+    world::World,
+};
+use bevy_tasks::{ComputeTaskPool, ParallelIterator};
+use std::ops::{Range, RangeInclusive};
+
+/// Dictates how a [`QueryParIter`] splits the matched entities into batches.
+///
+/// By default, the entities are split so that each thread of the [`ComputeTaskPool`] processes
+/// one batch.
+#[derive(Debug, Clone)]
+pub struct BatchingStrategy {
+    /// The bounds of the batch size, both included. The computed batch size is clamped to this
+    /// range.
+    pub batch_size_limits: RangeInclusive<usize>,
+    /// The number of batches to create per thread of the [`ComputeTaskPool`].
+    ///
+    /// More batches per thread even out the load when items take varying amounts of time to
+    /// process, at the cost of more overhead.
+    pub batches_per_thread: usize,
+}
+
+impl Default for BatchingStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BatchingStrategy {
+    /// Creates a batching strategy with one batch per thread and no bounds on the batch size.
+    pub const fn new() -> Self {
+        Self {
+            batch_size_limits: 1..=usize::MAX,
+            batches_per_thread: 1,
+        }
+    }
+
+    /// Creates a batching strategy that always uses batches of `batch_size` items.
+    pub const fn fixed(batch_size: usize) -> Self {
+        Self {
+            batch_size_limits: batch_size..=batch_size,
+            batches_per_thread: 1,
+        }
+    }
+
+    /// Sets the minimum batch size.
+    pub const fn min_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size_limits = batch_size..=*self.batch_size_limits.end();
+        self
+    }
+
+    /// Sets the maximum batch size.
+    pub const fn max_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size_limits = *self.batch_size_limits.start()..=batch_size;
+        self
+    }
+
+    /// Sets the number of batches to create per thread.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batches_per_thread` is zero.
+    pub fn batches_per_thread(mut self, batches_per_thread: usize) -> Self {
+        assert!(
+            batches_per_thread > 0,
+            "The number of batches per thread must be non-zero."
+        );
+        self.batches_per_thread = batches_per_thread;
+        self
+    }
+
+    /// Computes the batch size to split `item_count` items between `thread_count` threads.
+    ///
+    /// The result is never zero.
+    pub fn calc_batch_size(&self, item_count: usize, thread_count: usize) -> usize {
+        let batch_count = thread_count.max(1) * self.batches_per_thread;
+        let batch_size = (item_count + batch_count - 1) / batch_count;
+        batch_size
+            .max(*self.batch_size_limits.start())
+            .min(*self.batch_size_limits.end())
+            .max(1)
+    }
+}
+
+/// A parallel iterator over query results, created by [`Query::par_iter`](crate::system::Query::par_iter)
+/// and [`Query::par_iter_mut`](crate::system::Query::par_iter_mut).
+///
+/// Results are split into batches sized by a [`BatchingStrategy`], which are processed on the
+/// [`ComputeTaskPool`]. Besides [`QueryParIter::for_each`], it implements [`ParallelIterator`],
+/// so that results can be mapped, collected, folded or reduced in parallel:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_tasks::{ComputeTaskPool, ParallelIterator};
+/// #[derive(Component)]
+/// struct Health(f32);
+///
+/// fn total_health(query: Query<&Health>) {
+///     let total: f32 = query
+///         .par_iter()
+///         .map(|health| health.0)
+///         .sum::<f32, f32>(ComputeTaskPool::get());
+/// }
+/// # bevy_ecs::system::assert_is_system(total_health);
+/// ```
+pub struct QueryParIter<'w, 's, Q: WorldQuery, F: WorldQuery> {
+    world: &'w World,
+    state: &'s QueryState<Q, F>,
+    batching_strategy: BatchingStrategy,
+    last_change_tick: u32,
+    change_tick: u32,
+    cursor: BatchCursor,
+}
+
+/// The position of a [`QueryParIter`] in the matched tables or archetypes.
+struct BatchCursor {
+    batch_size: Option<usize>,
+    index: usize,
+    offset: usize,
+}
+
+impl<'w, 's, Q: WorldQuery, F: WorldQuery> QueryParIter<'w, 's, Q, F> {
+    /// # Safety
+    /// This does not check for mutable query correctness. To be safe, make sure mutable queries
+    /// have unique access to the components they query.
+    /// This does not validate that `world.id()` matches `state.world_id`. Calling this on a `world`
+    /// with a mismatched [`WorldId`](crate::world::WorldId) is unsound.
+    pub(crate) unsafe fn new(
+        world: &'w World,
+        state: &'s QueryState<Q, F>,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> Self {
+        Self {
+            world,
+            state,
+            batching_strategy: BatchingStrategy::default(),
+            last_change_tick,
+            change_tick,
+            cursor: BatchCursor {
+                batch_size: None,
+                index: 0,
+                offset: 0,
+            },
+        }
+    }
+
+    /// Changes the [`BatchingStrategy`] used to split the results into batches.
+    pub fn batching_strategy(mut self, strategy: BatchingStrategy) -> Self {
+        self.batching_strategy = strategy;
+        self
+    }
+
+    /// Runs `func` on each query result in parallel.
+    ///
+    /// # Panics
+    /// The [`ComputeTaskPool`] is not initialized. If using this from a query that is being
+    /// initialized and run from the ECS scheduler, this should never panic.
+    #[inline]
+    pub fn for_each<FN: Fn(QueryItem<'w, Q>) + Send + Sync + Clone>(self, func: FN) {
+        let batch_size = self.batch_size();
+        // SAFETY: upheld by the caller of `QueryParIter::new`
+        unsafe {
+            self.state.par_for_each_unchecked_manual(
+                self.world,
+                batch_size,
+                func,
+                self.last_change_tick,
+                self.change_tick,
+            );
+        }
+    }
+
+    fn is_dense() -> bool {
+        <QueryFetch<'static, Q>>::IS_DENSE && <QueryFetch<'static, F>>::IS_DENSE
+    }
+
+    /// The sizes of the matched tables or archetypes, in iteration order.
+    fn len_of(&self, index: usize) -> Option<usize> {
+        if Self::is_dense() {
+            let table_id = self.state.matched_table_ids.get(index)?;
+            Some(self.world.storages().tables[*table_id].len())
+        } else {
+            let archetype_id = self.state.matched_archetype_ids.get(index)?;
+            Some(self.world.archetypes[*archetype_id].len())
+        }
+    }
+
+    fn batch_size(&self) -> usize {
+        if let Some(batch_size) = self.cursor.batch_size {
+            return batch_size;
+        }
+        let item_count = (0..).map_while(|index| self.len_of(index)).sum::<usize>();
+        let thread_count = ComputeTaskPool::get().thread_num();
+        self.batching_strategy
+            .calc_batch_size(item_count, thread_count)
+    }
+}
+
+impl<'w, 's, Q: WorldQuery, F: WorldQuery> ParallelIterator<QueryBatch<'w, Q, F>>
+    for QueryParIter<'w, 's, Q, F>
+where
+    QueryItem<'w, Q>: Send,
+{
+    fn next_batch(&mut self) -> Option<QueryBatch<'w, Q, F>> {
+        let batch_size = self.batch_size();
+        self.cursor.batch_size = Some(batch_size);
+
+        loop {
+            let len = self.len_of(self.cursor.index)?;
+            if self.cursor.offset >= len {
+                self.cursor.index += 1;
+                self.cursor.offset = 0;
+                continue;
+            }
+            let start = self.cursor.offset;
+            let end = len.min(start + batch_size);
+            self.cursor.offset = end;
+
+            let world = self.world;
+            let state = self.state;
+            // SAFETY: upheld by the caller of `QueryParIter::new`. The batches cover disjoint
+            // ranges of the matched tables and archetypes.
+            unsafe {
+                let mut fetch = <QueryFetch<Q> as Fetch>::init(
+                    world,
+                    &state.fetch_state,
+                    self.last_change_tick,
+                    self.change_tick,
+                );
+                let mut filter = <QueryFetch<F> as Fetch>::init(
+                    world,
+                    &state.filter_state,
+                    self.last_change_tick,
+                    self.change_tick,
+                );
+                let tables = &world.storages().tables;
+                if Self::is_dense() {
+                    let table = &tables[state.matched_table_ids[self.cursor.index]];
+                    fetch.set_table(&state.fetch_state, table);
+                    filter.set_table(&state.filter_state, table);
+                } else {
+                    let archetype =
+                        &world.archetypes[state.matched_archetype_ids[self.cursor.index]];
+                    fetch.set_archetype(&state.fetch_state, archetype, tables);
+                    filter.set_archetype(&state.filter_state, archetype, tables);
+                }
+                return Some(QueryBatch {
+                    fetch,
+                    filter,
+                    dense: Self::is_dense(),
+                    indices: start..end,
+                });
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.cursor.index..)
+            .map_while(|index| self.len_of(index))
+            .sum::<usize>()
+            .saturating_sub(self.cursor.offset);
+        // Filters may reject any number of the remaining items.
+        (0, Some(remaining))
+    }
+}
+
+/// A batch of query results within a single table or archetype, yielded by a [`QueryParIter`].
+pub struct QueryBatch<'w, Q: WorldQuery, F: WorldQuery> {
+    fetch: QueryFetch<'w, Q>,
+    filter: QueryFetch<'w, F>,
+    dense: bool,
+    indices: Range<usize>,
+}
+
+// SAFETY: A batch only yields its items, so it can be sent to another thread if they can. The
+// fetches are raw pointers into the storage of the `World` they were created from, which outlives
+// `'w`, and the filter fetch only reads from it. Each batch covers a range of entities that no
+// other batch covers, so the items of mutable fetches cannot alias across threads.
+unsafe impl<'w, Q: WorldQuery, F: WorldQuery> Send for QueryBatch<'w, Q, F> where
+    QueryItem<'w, Q>: Send
+{
+}
+
+impl<'w, Q: WorldQuery, F: WorldQuery> Iterator for QueryBatch<'w, Q, F> {
+    type Item = QueryItem<'w, Q>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        // SAFETY: the fetches were set to the table or archetype these indices are in.
+        unsafe {
+            for index in self.indices.by_ref() {
+                if self.dense {
+                    if self.filter.table_filter_fetch(index) {
+                        return Some(self.fetch.table_fetch(index));
+                    }
+                } else if self.filter.archetype_filter_fetch(index) {
+                    return Some(self.fetch.archetype_fetch(index));
+                }
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.indices.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BatchingStrategy;
+    use crate::system::{IntoSystem, System};
+    use crate::{self as bevy_ecs, component::Component, system::Query, world::World};
+    use bevy_tasks::{ComputeTaskPool, ParallelIterator, TaskPool};
+
+    #[derive(Component)]
+    struct A(usize);
+
+    #[derive(Component)]
+    #[component(storage = "SparseSet")]
+    struct B;
+
+    fn run_system<Param>(world: &mut World, system: impl IntoSystem<(), (), Param>) {
+        let mut system = IntoSystem::into_system(system);
+        system.initialize(world);
+        system.run((), world);
+    }
+
+    #[test]
+    fn calc_batch_size() {
+        let strategy = BatchingStrategy::new();
+        assert_eq!(strategy.calc_batch_size(100, 4), 25);
+        assert_eq!(strategy.calc_batch_size(101, 4), 26);
+        assert_eq!(strategy.calc_batch_size(0, 4), 1);
+        assert_eq!(strategy.calc_batch_size(10, 0), 10);
+
+        let strategy = BatchingStrategy::new().batches_per_thread(2);
+        assert_eq!(strategy.calc_batch_size(100, 4), 13);
+
+        let strategy = BatchingStrategy::new()
+            .min_batch_size(40)
+            .max_batch_size(50);
+        assert_eq!(strategy.calc_batch_size(100, 4), 40);
+        assert_eq!(strategy.calc_batch_size(1000, 4), 50);
+
+        assert_eq!(BatchingStrategy::fixed(7).calc_batch_size(1000, 4), 7);
+        assert_eq!(BatchingStrategy::fixed(7).calc_batch_size(3, 4), 7);
+    }
+
+    fn world_with_entities() -> World {
+        ComputeTaskPool::init(TaskPool::default);
+        let mut world = World::new();
+        for i in 0..100 {
+            let mut entity = world.spawn();
+            entity.insert(A(i));
+            if i % 3 == 0 {
+                entity.insert(B);
+            }
+        }
+        world
+    }
+
+    #[test]
+    fn par_iter_collect() {
+        let mut world = world_with_entities();
+        run_system(&mut world, |query: Query<&A>| {
+            let mut values: Vec<usize> = query
+                .par_iter()
+                .batching_strategy(BatchingStrategy::fixed(7))
+                .map(|a| a.0)
+                .collect(ComputeTaskPool::get());
+            values.sort_unstable();
+            assert_eq!(values, (0..100).collect::<Vec<_>>());
+        });
+    }
+
+    #[test]
+    fn par_iter_fold_reduce_with_filter() {
+        let mut world = world_with_entities();
+        run_system(&mut world, |query: Query<&A, bevy_ecs::query::With<B>>| {
+            let expected: usize = (0..100).filter(|i| i % 3 == 0).sum();
+            let sums = query
+                .par_iter()
+                .fold(ComputeTaskPool::get(), 0, |sum, a| sum + a.0);
+            assert_eq!(sums.iter().sum::<usize>(), expected);
+            let sum = query
+                .par_iter()
+                .map(|a| a.0)
+                .reduce(ComputeTaskPool::get(), |a, b| a + b);
+            assert_eq!(sum, Some(expected));
+        });
+    }
+
+    #[test]
+    fn par_iter_mut_for_each() {
+        let mut world = world_with_entities();
+        run_system(&mut world, |mut query: Query<&mut A>| {
+            query.par_iter_mut().for_each(|mut a| a.0 *= 2);
+        });
+        let mut query = world.query::<&A>();
+        let sum: usize = query.iter(&world).map(|a| a.0).sum();
+        assert_eq!(sum, (0..100).map(|i| i * 2).sum());
+    }
+}
@@ -4,7 +4,8 @@ use crate::{
     entity::Entity,
     prelude::FromWorld,
     query::{
-        Access, Fetch, FetchState, FilteredAccess, QueryCombinationIter, QueryIter, WorldQuery,
+        Access, Fetch, FetchState, FilteredAccess, QueryCombinationIter, QueryIter, QueryParIter,
+        WorldQuery,
     },
     storage::TableId,
     world::{World, WorldId},
@@ -839,6 +840,41 @@ impl<Q: WorldQuery, F: WorldQuery> QueryState<Q, F> {
         }
     }
 
+    /// Returns a [`QueryParIter`] over the query results for the given [`World`].
+    ///
+    /// This can only be called for read-only queries, see [`Self::par_iter_mut`] for write-queries.
+    #[inline]
+    pub fn par_iter<'w, 's>(
+        &'s mut self,
+        world: &'w World,
+    ) -> QueryParIter<'w, 's, Q::ReadOnly, F::ReadOnly> {
+        // SAFETY: query is read only
+        unsafe {
+            self.update_archetypes(world);
+            QueryParIter::new(
+                world,
+                self.as_readonly(),
+                world.last_change_tick(),
+                world.read_change_tick(),
+            )
+        }
+    }
+
+    /// Returns a [`QueryParIter`] over the query results for the given [`World`].
+    #[inline]
+    pub fn par_iter_mut<'w, 's>(&'s mut self, world: &'w mut World) -> QueryParIter<'w, 's, Q, F> {
+        // SAFETY: query has unique world access
+        unsafe {
+            self.update_archetypes(world);
+            QueryParIter::new(
+                world,
+                self,
+                world.last_change_tick(),
+                world.read_change_tick(),
+            )
+        }
+    }
+
     /// Runs `func` on each query result in parallel.
     ///
     /// This can only be called for read-only queries.
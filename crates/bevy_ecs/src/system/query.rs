@@ -2,7 +2,7 @@ use crate::{
     component::Component,
     entity::Entity,
     query::{
-        QueryCombinationIter, QueryEntityError, QueryItem, QueryIter, QueryManyIter,
+        QueryCombinationIter, QueryEntityError, QueryItem, QueryIter, QueryManyIter, QueryParIter,
         QuerySingleError, QueryState, ROQueryItem, ReadOnlyWorldQuery, WorldQuery,
     },
     world::{Mut, World},
@@ -659,6 +659,66 @@ impl<'w, 's, Q: WorldQuery, F: WorldQuery> Query<'w, 's, Q, F> {
         };
     }
 
+    /// Returns a [`QueryParIter`] over the query results, to process them in parallel on the
+    /// [`ComputeTaskPool`].
+    ///
+    /// Unlike [`Self::par_for_each`], the batch size is derived from the number of matched
+    /// entities and threads, see [`BatchingStrategy`](crate::query::BatchingStrategy). The results can also be mapped,
+    /// collected, folded or reduced through [`ParallelIterator`](bevy_tasks::ParallelIterator).
+    ///
+    /// This can only return immutable data, see [`Self::par_iter_mut`] for mutable access.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_tasks::{ComputeTaskPool, ParallelIterator};
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Player { score: u32 }
+    /// fn best_score_system(query: Query<&Player>) {
+    ///     let best_score = query
+    ///         .par_iter()
+    ///         .map(|player| player.score)
+    ///         .max(ComputeTaskPool::get());
+    /// }
+    /// # bevy_ecs::system::assert_is_system(best_score_system);
+    /// ```
+    ///
+    /// [`ComputeTaskPool`]: bevy_tasks::prelude::ComputeTaskPool
+    #[inline]
+    pub fn par_iter(&self) -> QueryParIter<'_, 's, Q::ReadOnly, F::ReadOnly> {
+        // SAFETY: system runs without conflicts with other systems. same-system queries have runtime
+        // borrow checks when they conflict
+        unsafe {
+            QueryParIter::new(
+                self.world,
+                self.state.as_readonly(),
+                self.last_change_tick,
+                self.change_tick,
+            )
+        }
+    }
+
+    /// Returns a [`QueryParIter`] over the query results, to process them in parallel on the
+    /// [`ComputeTaskPool`].
+    /// See [`Self::par_iter`] for more details.
+    ///
+    /// [`ComputeTaskPool`]: bevy_tasks::prelude::ComputeTaskPool
+    #[inline]
+    pub fn par_iter_mut(&mut self) -> QueryParIter<'_, 's, Q, F> {
+        // SAFETY: system runs without conflicts with other systems. same-system queries have runtime
+        // borrow checks when they conflict
+        unsafe {
+            QueryParIter::new(
+                self.world,
+                self.state,
+                self.last_change_tick,
+                self.change_tick,
+            )
+        }
+    }
+
     /// Calls a closure on each result of [`Query`] where the entities match.
     /// # Examples
     ///
@@ -1,6 +1,6 @@
 use anyhow::Result;
 use bevy_asset::{
-    AssetIoError, AssetLoader, AssetPath, BoxedFuture, Handle, LoadContext, LoadedAsset,
+    AssetIoError, AssetLoader, BoxedFuture, Handle, LoadContext, LoadedAsset, LoaderSettingsError,
 };
 use bevy_core::Name;
 use bevy_core_pipeline::prelude::Camera3d;
@@ -40,6 +40,7 @@ use gltf::{
     texture::{MagFilter, MinFilter, WrappingMode},
     Material, Node, Primitive,
 };
+use serde::{Deserialize, Serialize};
 use std::{collections::VecDeque, path::Path};
 use thiserror::Error;
 
@@ -68,6 +69,27 @@ pub enum GltfError {
     MissingAnimationSampler(usize),
     #[error("failed to generate tangents: {0}")]
     GenerateTangentsError(#[from] bevy_render::mesh::GenerateTangentsError),
+    #[error("invalid loader settings: {0}")]
+    LoaderSettingsError(#[from] LoaderSettingsError),
+}
+
+/// Settings of the [`GltfLoader`], passed with
+/// [`AssetServer::load_with_settings`](bevy_asset::AssetServer::load_with_settings).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GltfLoaderSettings {
+    /// Whether to spawn the cameras of the glTF file in its scenes.
+    pub load_cameras: bool,
+    /// Whether to spawn the lights of the glTF file in its scenes.
+    pub load_lights: bool,
+}
+
+impl Default for GltfLoaderSettings {
+    fn default() -> Self {
+        Self {
+            load_cameras: true,
+            load_lights: true,
+        }
+    }
 }
 
 /// Loads glTF files with all of their data as their corresponding bevy representations.
@@ -110,6 +132,7 @@ async fn load_gltf<'a, 'b>(
     load_context: &'a mut LoadContext<'b>,
     supported_compressed_formats: CompressedImageFormats,
 ) -> Result<(), GltfError> {
+    let settings = load_context.settings::<GltfLoaderSettings>()?;
     let gltf = gltf::Gltf::from_slice(bytes)?;
     let buffer_data = load_buffers(&gltf, load_context, load_context.path()).await?;
 
@@ -477,6 +500,7 @@ async fn load_gltf<'a, 'b>(
                         &mut node_index_to_entity_map,
                         &mut entity_to_skin_index_map,
                         &mut active_camera_found,
+                        &settings,
                     );
                     if result.is_err() {
                         err = Some(result);
@@ -631,8 +655,7 @@ fn load_material(material: &Material, load_context: &mut LoadContext) -> Handle<
     let base_color_texture = pbr.base_color_texture().map(|info| {
         // TODO: handle info.tex_coord() (the *set* index for the right texcoords)
         let label = texture_label(&info.texture());
-        let path = AssetPath::new_ref(load_context.path(), Some(&label));
-        load_context.get_handle(path)
+        load_context.get_labeled_handle(&label)
     });
 
     let normal_map_texture: Option<Handle<Image>> =
@@ -640,23 +663,20 @@ fn load_material(material: &Material, load_context: &mut LoadContext) -> Handle<
             // TODO: handle normal_texture.scale
             // TODO: handle normal_texture.tex_coord() (the *set* index for the right texcoords)
             let label = texture_label(&normal_texture.texture());
-            let path = AssetPath::new_ref(load_context.path(), Some(&label));
-            load_context.get_handle(path)
+            load_context.get_labeled_handle(&label)
         });
 
     let metallic_roughness_texture = pbr.metallic_roughness_texture().map(|info| {
         // TODO: handle info.tex_coord() (the *set* index for the right texcoords)
         let label = texture_label(&info.texture());
-        let path = AssetPath::new_ref(load_context.path(), Some(&label));
-        load_context.get_handle(path)
+        load_context.get_labeled_handle(&label)
     });
 
     let occlusion_texture = material.occlusion_texture().map(|occlusion_texture| {
         // TODO: handle occlusion_texture.tex_coord() (the *set* index for the right texcoords)
         // TODO: handle occlusion_texture.strength() (a scalar multiplier for occlusion strength)
         let label = texture_label(&occlusion_texture.texture());
-        let path = AssetPath::new_ref(load_context.path(), Some(&label));
-        load_context.get_handle(path)
+        load_context.get_labeled_handle(&label)
     });
 
     let emissive = material.emissive_factor();
@@ -664,8 +684,7 @@ fn load_material(material: &Material, load_context: &mut LoadContext) -> Handle<
         // TODO: handle occlusion_texture.tex_coord() (the *set* index for the right texcoords)
         // TODO: handle occlusion_texture.strength() (a scalar multiplier for occlusion strength)
         let label = texture_label(&info.texture());
-        let path = AssetPath::new_ref(load_context.path(), Some(&label));
-        load_context.get_handle(path)
+        load_context.get_labeled_handle(&label)
     });
 
     load_context.set_labeled_asset(
@@ -694,6 +713,7 @@ fn load_material(material: &Material, load_context: &mut LoadContext) -> Handle<
 }
 
 /// Loads a glTF node.
+#[allow(clippy::too_many_arguments)]
 fn load_node(
     gltf_node: &gltf::Node,
     world_builder: &mut WorldChildBuilder,
@@ -702,6 +722,7 @@ fn load_node(
     node_index_to_entity_map: &mut HashMap<usize, Entity>,
     entity_to_skin_index_map: &mut HashMap<Entity, usize>,
     active_camera_found: &mut bool,
+    settings: &GltfLoaderSettings,
 ) -> Result<(), GltfError> {
     let transform = gltf_node.transform();
     let mut gltf_error = None;
@@ -718,7 +739,7 @@ fn load_node(
     }
 
     // create camera node
-    if let Some(camera) = gltf_node.camera() {
+    if let Some(camera) = gltf_node.camera().filter(|_| settings.load_cameras) {
         let projection = match camera.projection() {
             gltf::camera::Projection::Orthographic(orthographic) => {
                 let xmag = orthographic.xmag();
@@ -782,14 +803,10 @@ fn load_node(
 
                 let primitive_label = primitive_label(&mesh, &primitive);
                 let bounds = primitive.bounding_box();
-                let mesh_asset_path =
-                    AssetPath::new_ref(load_context.path(), Some(&primitive_label));
-                let material_asset_path =
-                    AssetPath::new_ref(load_context.path(), Some(&material_label));
 
                 let mut mesh_entity = parent.spawn_bundle(PbrBundle {
-                    mesh: load_context.get_handle(mesh_asset_path),
-                    material: load_context.get_handle(material_asset_path),
+                    mesh: load_context.get_labeled_handle(&primitive_label),
+                    material: load_context.get_labeled_handle(&material_label),
                     ..Default::default()
                 });
                 mesh_entity.insert(Aabb::from_min_max(
@@ -812,7 +829,7 @@ fn load_node(
             }
         }
 
-        if let Some(light) = gltf_node.light() {
+        if let Some(light) = gltf_node.light().filter(|_| settings.load_lights) {
             match light.kind() {
                 gltf::khr_lights_punctual::Kind::Directional => {
                     let mut entity = parent.spawn_bundle(DirectionalLightBundle {
@@ -898,6 +915,7 @@ fn load_node(
                 node_index_to_entity_map,
                 entity_to_skin_index_map,
                 active_camera_found,
+                settings,
             ) {
                 gltf_error = Some(err);
                 return;
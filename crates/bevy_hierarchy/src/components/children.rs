@@ -17,8 +17,12 @@ pub struct Children(pub(crate) SmallVec<[Entity; 8]>);
 
 impl MapEntities for Children {
     fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+        // Children of an entity in the new world can be in outside world, in which case they
+        // should not be mapped.
         for entity in &mut self.0 {
-            *entity = entity_map.get(*entity)?;
+            if let Ok(mapped_entity) = entity_map.get(*entity) {
+                *entity = mapped_entity;
+            }
         }
 
         Ok(())
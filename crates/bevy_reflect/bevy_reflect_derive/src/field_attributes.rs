@@ -3,11 +3,16 @@
 //! A field attribute is an attribute which applies to particular field or variant
 //! as opposed to an entire struct or enum. An example of such an attribute is
 //! the derive helper attribute for `Reflect`, which looks like: `#[reflect(ignore)]`.
+//!
+//! Custom attributes, stored in the type info of the field, are expressions prefixed with `@`,
+//! like: `#[reflect(@Range(0.0..1.0))]`.
 
 use crate::REFLECT_ATTRIBUTE_NAME;
 use quote::ToTokens;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
-use syn::{Attribute, Lit, Meta, NestedMeta};
+use syn::{Attribute, Expr, Lit, Meta, NestedMeta, Token};
 
 pub(crate) static IGNORE_ATTR: &str = "ignore";
 pub(crate) static DEFAULT_ATTR: &str = "default";
@@ -19,6 +24,8 @@ pub(crate) struct ReflectFieldAttr {
     pub ignore: bool,
     /// Sets the default behavior of this field.
     pub default: DefaultBehavior,
+    /// The expressions of the custom attributes of this field.
+    pub custom_attributes: Vec<Expr>,
 }
 
 /// Controls how the default value is determined for a field.
@@ -36,6 +43,24 @@ pub(crate) enum DefaultBehavior {
     Func(syn::ExprPath),
 }
 
+/// An argument of a `reflect` field attribute.
+enum ReflectFieldArg {
+    /// A custom attribute, like `@Range(0.0..1.0)`.
+    Custom(Expr),
+    Meta(NestedMeta),
+}
+
+impl Parse for ReflectFieldArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![@]) {
+            input.parse::<Token![@]>()?;
+            Ok(ReflectFieldArg::Custom(input.parse()?))
+        } else {
+            Ok(ReflectFieldArg::Meta(input.parse()?))
+        }
+    }
+}
+
 /// Parse all field attributes marked "reflect" (such as `#[reflect(ignore)]`).
 pub(crate) fn parse_field_attrs(attrs: &[Attribute]) -> Result<ReflectFieldAttr, syn::Error> {
     let mut args = ReflectFieldAttr::default();
@@ -45,12 +70,23 @@ pub(crate) fn parse_field_attrs(attrs: &[Attribute]) -> Result<ReflectFieldAttr,
         .iter()
         .filter(|a| a.path.is_ident(REFLECT_ATTRIBUTE_NAME));
     for attr in attrs {
-        let meta = attr.parse_meta()?;
-        if let Err(err) = parse_meta(&mut args, &meta) {
-            if let Some(ref mut error) = errors {
-                error.combine(err);
-            } else {
-                errors = Some(err);
+        let reflect_args =
+            attr.parse_args_with(Punctuated::<ReflectFieldArg, Token![,]>::parse_terminated)?;
+        for reflect_arg in reflect_args {
+            let result = match reflect_arg {
+                ReflectFieldArg::Custom(expr) => {
+                    args.custom_attributes.push(expr);
+                    Ok(())
+                }
+                ReflectFieldArg::Meta(NestedMeta::Meta(meta)) => parse_meta(&mut args, &meta),
+                ReflectFieldArg::Meta(NestedMeta::Lit(_)) => Ok(()),
+            };
+            if let Err(err) = result {
+                if let Some(ref mut error) = errors {
+                    error.combine(err);
+                } else {
+                    errors = Some(err);
+                }
             }
         }
     }
@@ -1,4 +1,5 @@
 use crate::container_attributes::ReflectTraits;
+use crate::derive_data::StructField;
 use crate::ReflectDeriveData;
 use proc_macro::TokenStream;
 use proc_macro2::Ident;
@@ -36,6 +37,10 @@ pub(crate) fn impl_struct(derive_data: &ReflectDeriveData) -> TokenStream {
         .active_fields()
         .map(|field| field.data.ty.clone())
         .collect::<Vec<_>>();
+    let field_attributes = derive_data
+        .active_fields()
+        .map(|field| impl_custom_attributes(field, bevy_reflect_path))
+        .collect::<Vec<_>>();
     let field_count = field_idents.len();
     let field_indices = (0..field_count).collect::<Vec<usize>>();
 
@@ -57,7 +62,7 @@ pub(crate) fn impl_struct(derive_data: &ReflectDeriveData) -> TokenStream {
         derive_data.generics(),
         quote! {
            let fields: [#bevy_reflect_path::NamedField; #field_count] = [
-                #(#bevy_reflect_path::NamedField::new::<#field_types, _>(#field_names),)*
+                #(#bevy_reflect_path::NamedField::new::<#field_types, _>(#field_names)#field_attributes,)*
             ];
             let info = #bevy_reflect_path::StructInfo::new::<Self>(&fields);
             #bevy_reflect_path::TypeInfo::Struct(info)
@@ -214,6 +219,10 @@ pub(crate) fn impl_tuple_struct(derive_data: &ReflectDeriveData) -> TokenStream
         .active_fields()
         .map(|field| field.data.ty.clone())
         .collect::<Vec<_>>();
+    let field_attributes = derive_data
+        .active_fields()
+        .map(|field| impl_custom_attributes(field, bevy_reflect_path))
+        .collect::<Vec<_>>();
     let field_count = field_idents.len();
     let field_indices = (0..field_count).collect::<Vec<usize>>();
 
@@ -235,7 +244,7 @@ pub(crate) fn impl_tuple_struct(derive_data: &ReflectDeriveData) -> TokenStream
         derive_data.generics(),
         quote! {
             let fields: [#bevy_reflect_path::UnnamedField; #field_count] = [
-                #(#bevy_reflect_path::UnnamedField::new::<#field_types>(#field_indices),)*
+                #(#bevy_reflect_path::UnnamedField::new::<#field_types>(#field_indices)#field_attributes,)*
             ];
             let info = #bevy_reflect_path::TupleStructInfo::new::<Self>(&fields);
             #bevy_reflect_path::TypeInfo::TupleStruct(info)
@@ -490,3 +499,20 @@ fn impl_typed(
         }
     }
 }
+
+/// Sets the custom attributes of a field in its `NamedField` or `UnnamedField`, if it has any.
+fn impl_custom_attributes(
+    field: &StructField,
+    bevy_reflect_path: &Path,
+) -> proc_macro2::TokenStream {
+    let custom_attributes = &field.attrs.custom_attributes;
+    if custom_attributes.is_empty() {
+        return proc_macro2::TokenStream::new();
+    }
+    quote! {
+        .with_custom_attributes(
+            #bevy_reflect_path::CustomAttributes::default()
+                #(.with_attribute(#custom_attributes))*
+        )
+    }
+}
@@ -0,0 +1,79 @@
+use bevy_utils::HashMap;
+use std::{
+    any::{Any, TypeId},
+    fmt::{Debug, Formatter},
+    sync::Arc,
+};
+
+/// Custom attributes of a reflected field, such as editor metadata.
+///
+/// Attributes are arbitrary values, stored by type. They are set with `@` in the `reflect`
+/// attribute of a field, followed by an expression building the attribute:
+///
+/// ```
+/// # use bevy_reflect::{Reflect, TypeInfo, Typed};
+/// struct Tooltip(&'static str);
+///
+/// #[derive(Reflect)]
+/// struct Light {
+///     #[reflect(@Tooltip("The intensity of the light"), @0.0..1.0)]
+///     intensity: f32,
+/// }
+///
+/// let info = match Light::type_info() {
+///     TypeInfo::Struct(info) => info,
+///     _ => unreachable!(),
+/// };
+/// let intensity = info.field("intensity").unwrap();
+/// assert_eq!(intensity.get_attribute::<Tooltip>().unwrap().0, "The intensity of the light");
+/// assert_eq!(intensity.get_attribute::<std::ops::Range<f64>>(), Some(&(0.0..1.0)));
+/// ```
+#[derive(Clone, Default)]
+pub struct CustomAttributes {
+    attributes: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+}
+
+impl CustomAttributes {
+    /// Adds an attribute, replacing the attribute of the same type if any.
+    pub fn with_attribute<T: Any + Send + Sync>(mut self, value: T) -> Self {
+        self.attributes.insert(TypeId::of::<T>(), Arc::new(value));
+        self
+    }
+
+    /// Returns the attribute of type `T`, if any.
+    pub fn get<T: Any>(&self) -> Option<&T> {
+        self.attributes
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref::<T>())
+    }
+
+    /// Returns `true` if there is an attribute of type `T`.
+    pub fn contains<T: Any>(&self) -> bool {
+        self.attributes.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Returns an iterator over the attributes, with the [`TypeId`] of their type.
+    pub fn iter(&self) -> impl Iterator<Item = (TypeId, &dyn Any)> {
+        self.attributes
+            .iter()
+            .map(|(type_id, value)| (*type_id, &**value as &dyn Any))
+    }
+
+    /// Returns the number of attributes.
+    pub fn len(&self) -> usize {
+        self.attributes.len()
+    }
+
+    /// Returns `true` if there are no attributes.
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty()
+    }
+}
+
+impl Debug for CustomAttributes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CustomAttributes")
+            .field("len", &self.len())
+            .finish()
+    }
+}
@@ -0,0 +1,404 @@
+use crate::{GetPath, Reflect, ReflectMut, ReflectRef};
+use thiserror::Error;
+
+/// The changes between two reflected values, computed by [`diff`].
+///
+/// Each change applies to the value at a [path](GetPath) of the diffed value, so that only the
+/// changed fields are recorded. A diff can be [applied](ReflectDiff::apply) to a value, and
+/// serialized with [`ReflectDiffSerializer`](crate::serde::ReflectDiffSerializer).
+#[derive(Debug, Default)]
+pub struct ReflectDiff {
+    changes: Vec<(String, ReflectChange)>,
+}
+
+/// A change to a value of a [`ReflectDiff`].
+#[derive(Debug)]
+pub enum ReflectChange {
+    /// The value was replaced by another value.
+    ///
+    /// This is recorded for values which are not compared field by field, like
+    /// [`ReflectRef::Value`]s, or for values of different shapes.
+    Replace(Box<dyn Reflect>),
+    /// Values were pushed to the [`List`](crate::List).
+    ListPush(Vec<Box<dyn Reflect>>),
+    /// The [`List`](crate::List) was truncated to the given length.
+    ListTruncate(usize),
+    /// An entry was inserted in the [`Map`](crate::Map), or the value of an entry changed.
+    MapInsert {
+        key: Box<dyn Reflect>,
+        value: Box<dyn Reflect>,
+    },
+    /// The entry with the given key was removed from the [`Map`](crate::Map).
+    MapRemove(Box<dyn Reflect>),
+}
+
+/// An error applying a [`ReflectDiff`] to a value of a different shape than the diffed values,
+/// or which can't be changed the way the diff requires.
+#[derive(Debug, Error)]
+pub enum ReflectDiffError {
+    #[error("the value has no field at path `{path}`: {error}")]
+    InvalidPath { path: String, error: String },
+    #[error("expected a list at path `{path}`, but found a different reflect value")]
+    ExpectedList { path: String },
+    #[error("expected a map at path `{path}`, but found a different reflect value")]
+    ExpectedMap { path: String },
+    #[error("the list or map at path `{path}` doesn't support removing values")]
+    RemoveUnsupported { path: String },
+}
+
+impl ReflectDiff {
+    /// Creates a diff from changes, given with the path of the value they apply to.
+    pub fn from_changes(changes: Vec<(String, ReflectChange)>) -> Self {
+        ReflectDiff { changes }
+    }
+
+    /// Returns `true` if the diffed values are equal.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns the number of changes.
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Returns an iterator over the changes, with the path of the value they apply to.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &ReflectChange)> {
+        self.changes
+            .iter()
+            .map(|(path, change)| (path.as_str(), change))
+    }
+
+    /// Applies the changes to `value`, turning a value equal to the first diffed value into the
+    /// second one.
+    ///
+    /// The changes before the first error are applied.
+    ///
+    /// # Panics
+    ///
+    /// Like [`Reflect::apply`], replacing a value with a value of another type may panic.
+    pub fn apply(&self, value: &mut dyn Reflect) -> Result<(), ReflectDiffError> {
+        for (path, change) in &self.changes {
+            let target = value
+                .path_mut(path)
+                .map_err(|error| ReflectDiffError::InvalidPath {
+                    path: path.clone(),
+                    error: error.to_string(),
+                })?;
+            match change {
+                ReflectChange::Replace(value) => target.apply(&**value),
+                ReflectChange::ListPush(values) => match target.reflect_mut() {
+                    ReflectMut::List(list) => {
+                        for value in values {
+                            list.push(value.clone_value());
+                        }
+                    }
+                    _ => return Err(ReflectDiffError::ExpectedList { path: path.clone() }),
+                },
+                ReflectChange::ListTruncate(len) => match target.reflect_mut() {
+                    ReflectMut::List(list) => {
+                        while list.len() > *len {
+                            if list.pop().is_none() {
+                                return Err(ReflectDiffError::RemoveUnsupported {
+                                    path: path.clone(),
+                                });
+                            }
+                        }
+                    }
+                    _ => return Err(ReflectDiffError::ExpectedList { path: path.clone() }),
+                },
+                ReflectChange::MapInsert { key, value } => match target.reflect_mut() {
+                    ReflectMut::Map(map) => {
+                        map.insert_boxed(key.clone_value(), value.clone_value());
+                    }
+                    _ => return Err(ReflectDiffError::ExpectedMap { path: path.clone() }),
+                },
+                ReflectChange::MapRemove(key) => match target.reflect_mut() {
+                    ReflectMut::Map(map) => {
+                        if map.remove(&**key).is_none() && map.get(&**key).is_some() {
+                            return Err(ReflectDiffError::RemoveUnsupported { path: path.clone() });
+                        }
+                    }
+                    _ => return Err(ReflectDiffError::ExpectedMap { path: path.clone() }),
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Computes the changes from `a` to `b`, which are usually values of the same type.
+///
+/// [`Struct`](crate::Struct)s, [`TupleStruct`](crate::TupleStruct)s, [`Tuple`](crate::Tuple)s,
+/// [`List`](crate::List)s and [`Array`](crate::Array)s are compared field by field, and the
+/// entries of [`Map`](crate::Map)s are compared by key. Other values are compared with
+/// [`Reflect::reflect_partial_eq`], and replaced if they are not known to be equal, so values
+/// which don't reflect [`PartialEq`] are always replaced.
+///
+/// ```
+/// # use bevy_reflect::{diff, Reflect};
+/// #[derive(Reflect, Clone)]
+/// struct Player {
+///     name: String,
+///     health: u32,
+/// }
+///
+/// let before = Player { name: "Alice".to_string(), health: 10 };
+/// let mut after = before.clone();
+/// after.health = 7;
+///
+/// let diff = diff(&before, &after);
+/// assert_eq!(diff.iter().map(|(path, _)| path).collect::<Vec<_>>(), vec![".health"]);
+///
+/// let mut player = before.clone();
+/// diff.apply(&mut player).unwrap();
+/// assert_eq!(player.health, 7);
+/// ```
+pub fn diff(a: &dyn Reflect, b: &dyn Reflect) -> ReflectDiff {
+    let mut diff = ReflectDiff::default();
+    diff_at(&mut String::new(), a, b, &mut diff.changes);
+    diff
+}
+
+fn diff_at(
+    path: &mut String,
+    a: &dyn Reflect,
+    b: &dyn Reflect,
+    changes: &mut Vec<(String, ReflectChange)>,
+) {
+    let len = path.len();
+    match (a.reflect_ref(), b.reflect_ref()) {
+        (ReflectRef::Struct(a), ReflectRef::Struct(b))
+            if a.field_len() == b.field_len()
+                && (0..b.field_len()).all(|index| a.field(b.name_at(index).unwrap()).is_some()) =>
+        {
+            for (index, b_field) in b.iter_fields().enumerate() {
+                let name = b.name_at(index).unwrap();
+                path.push('.');
+                path.push_str(name);
+                diff_at(path, a.field(name).unwrap(), b_field, changes);
+                path.truncate(len);
+            }
+        }
+        (ReflectRef::TupleStruct(a), ReflectRef::TupleStruct(b))
+            if a.field_len() == b.field_len() =>
+        {
+            for (index, (a_field, b_field)) in a.iter_fields().zip(b.iter_fields()).enumerate() {
+                path.push_str(&format!(".{}", index));
+                diff_at(path, a_field, b_field, changes);
+                path.truncate(len);
+            }
+        }
+        (ReflectRef::Tuple(a), ReflectRef::Tuple(b)) if a.field_len() == b.field_len() => {
+            for (index, (a_field, b_field)) in a.iter_fields().zip(b.iter_fields()).enumerate() {
+                path.push_str(&format!(".{}", index));
+                diff_at(path, a_field, b_field, changes);
+                path.truncate(len);
+            }
+        }
+        (ReflectRef::List(a), ReflectRef::List(b)) => {
+            for (index, (a_item, b_item)) in a.iter().zip(b.iter()).enumerate() {
+                path.push_str(&format!("[{}]", index));
+                diff_at(path, a_item, b_item, changes);
+                path.truncate(len);
+            }
+            if b.len() > a.len() {
+                let values = b.iter().skip(a.len()).map(Reflect::clone_value).collect();
+                changes.push((path.clone(), ReflectChange::ListPush(values)));
+            } else if b.len() < a.len() {
+                changes.push((path.clone(), ReflectChange::ListTruncate(b.len())));
+            }
+        }
+        (ReflectRef::Array(a), ReflectRef::Array(b)) if a.len() == b.len() => {
+            for (index, (a_item, b_item)) in a.iter().zip(b.iter()).enumerate() {
+                path.push_str(&format!("[{}]", index));
+                diff_at(path, a_item, b_item, changes);
+                path.truncate(len);
+            }
+        }
+        (ReflectRef::Map(a), ReflectRef::Map(b)) => {
+            // map entries can't be reached by a path, so changed entries are inserted whole
+            for (key, b_value) in b.iter() {
+                let changed = match a.get(key) {
+                    Some(a_value) => !diff(a_value, b_value).is_empty(),
+                    None => true,
+                };
+                if changed {
+                    changes.push((
+                        path.clone(),
+                        ReflectChange::MapInsert {
+                            key: key.clone_value(),
+                            value: b_value.clone_value(),
+                        },
+                    ));
+                }
+            }
+            for (key, _) in a.iter() {
+                if b.get(key).is_none() {
+                    changes.push((path.clone(), ReflectChange::MapRemove(key.clone_value())));
+                }
+            }
+        }
+        (ReflectRef::Value(_), ReflectRef::Value(_)) if a.reflect_partial_eq(b) == Some(true) => {}
+        _ => changes.push((path.clone(), ReflectChange::Replace(b.clone_value()))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        self as bevy_reflect,
+        serde::{ReflectDiffDeserializer, ReflectDiffSerializer},
+        FromReflect, TypeRegistry,
+    };
+    use ::serde::de::DeserializeSeed;
+    use bevy_utils::HashMap;
+
+    #[derive(Reflect, FromReflect, Clone, Debug, PartialEq)]
+    struct Item {
+        name: String,
+        count: u32,
+    }
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    struct Inventory {
+        items: Vec<Item>,
+        slots: [u32; 2],
+        position: (f32, f32),
+        tags: HashMap<String, u32>,
+    }
+
+    fn inventory() -> Inventory {
+        Inventory {
+            items: vec![
+                Item {
+                    name: "sword".to_string(),
+                    count: 1,
+                },
+                Item {
+                    name: "arrow".to_string(),
+                    count: 20,
+                },
+            ],
+            slots: [0, 1],
+            position: (1.0, 2.0),
+            tags: [("quest".to_string(), 1), ("junk".to_string(), 2)]
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn diff_and_apply() {
+        let before = inventory();
+        assert!(diff(&before, &before.clone()).is_empty());
+
+        let mut after = before.clone();
+        after.items[1].count = 19;
+        after.items.push(Item {
+            name: "shield".to_string(),
+            count: 1,
+        });
+        after.slots[1] = 2;
+        after.position.0 = 3.0;
+        after.tags.remove("junk");
+        after.tags.insert("quest".to_string(), 2);
+
+        let changes = diff(&before, &after);
+        let mut paths: Vec<&str> = changes.iter().map(|(path, _)| path).collect();
+        paths.sort_unstable();
+        assert_eq!(
+            paths,
+            vec![
+                ".items",
+                ".items[1].count",
+                ".position.0",
+                ".slots[1]",
+                ".tags",
+                ".tags"
+            ]
+        );
+
+        let mut value = before.clone();
+        changes.apply(&mut value).unwrap();
+        assert_eq!(value, after);
+
+        // the reverse diff undoes the changes
+        diff(&after, &before).apply(&mut value).unwrap();
+        assert_eq!(value, before);
+    }
+
+    #[test]
+    fn serialize_diff() {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Item>();
+        registry.register::<String>();
+        registry.register::<u32>();
+        registry.register::<f32>();
+
+        let before = inventory();
+        let mut after = before.clone();
+        after.items.push(Item {
+            name: "shield".to_string(),
+            count: 1,
+        });
+        after.position.1 = 5.0;
+        after.tags.remove("junk");
+        after.tags.insert("new".to_string(), 3);
+        let changes = diff(&before, &after);
+
+        let ron = ron::to_string(&ReflectDiffSerializer::new(&changes, &registry)).unwrap();
+        let mut deserializer = ron::de::Deserializer::from_str(&ron).unwrap();
+        let changes = ReflectDiffDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        let mut value = before;
+        changes.apply(&mut value).unwrap();
+        assert_eq!(value, after);
+    }
+
+    #[test]
+    fn deserialize_value_without_map_insert() {
+        let mut registry = TypeRegistry::default();
+        registry.register::<u32>();
+
+        let deserialize = |change: &str| {
+            let ron = format!(
+                r#"[{{"path": ".a", {}, "value": {{"type": "u32", "value": 2}}}}]"#,
+                change
+            );
+            let mut deserializer = ron::de::Deserializer::from_str(&ron).unwrap();
+            ReflectDiffDeserializer::new(&registry).deserialize(&mut deserializer)
+        };
+
+        assert!(deserialize(r#""map_insert": {"type": "u32", "value": 1}"#).is_ok());
+        for change in [
+            r#""replace": {"type": "u32", "value": 1}"#,
+            r#""list_push": [{"type": "u32", "value": 1}]"#,
+            r#""map_remove": {"type": "u32", "value": 1}"#,
+        ] {
+            assert!(deserialize(change).is_err(), "{}", change);
+        }
+    }
+
+    #[test]
+    fn apply_to_other_shape() {
+        let mut after = inventory();
+        after.items.clear();
+        let changes = diff(&inventory(), &after);
+        assert!(matches!(
+            changes.iter().next(),
+            Some((".items", ReflectChange::ListTruncate(0)))
+        ));
+
+        let mut item = Item {
+            name: "sword".to_string(),
+            count: 1,
+        };
+        assert!(matches!(
+            changes.apply(&mut item),
+            Err(ReflectDiffError::InvalidPath { path, .. }) if path == ".items"
+        ));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::Reflect;
+use crate::{CustomAttributes, Reflect};
 use std::any::{Any, TypeId};
 use std::borrow::Cow;
 
@@ -8,6 +8,7 @@ pub struct NamedField {
     name: Cow<'static, str>,
     type_name: &'static str,
     type_id: TypeId,
+    custom_attributes: CustomAttributes,
 }
 
 impl NamedField {
@@ -17,9 +18,16 @@ impl NamedField {
             name: name.into(),
             type_name: std::any::type_name::<T>(),
             type_id: TypeId::of::<T>(),
+            custom_attributes: CustomAttributes::default(),
         }
     }
 
+    /// Sets the [custom attributes](CustomAttributes) of the field.
+    pub fn with_custom_attributes(mut self, custom_attributes: CustomAttributes) -> Self {
+        self.custom_attributes = custom_attributes;
+        self
+    }
+
     /// The name of the field.
     pub fn name(&self) -> &Cow<'static, str> {
         &self.name
@@ -41,6 +49,16 @@ impl NamedField {
     pub fn is<T: Any>(&self) -> bool {
         TypeId::of::<T>() == self.type_id
     }
+
+    /// The [custom attributes](CustomAttributes) of the field.
+    pub fn custom_attributes(&self) -> &CustomAttributes {
+        &self.custom_attributes
+    }
+
+    /// Returns the [custom attribute](CustomAttributes) of type `T` of the field, if any.
+    pub fn get_attribute<T: Any>(&self) -> Option<&T> {
+        self.custom_attributes.get::<T>()
+    }
 }
 
 /// The unnamed field of a reflected tuple or tuple struct.
@@ -49,6 +67,7 @@ pub struct UnnamedField {
     index: usize,
     type_name: &'static str,
     type_id: TypeId,
+    custom_attributes: CustomAttributes,
 }
 
 impl UnnamedField {
@@ -57,9 +76,16 @@ impl UnnamedField {
             index,
             type_name: std::any::type_name::<T>(),
             type_id: TypeId::of::<T>(),
+            custom_attributes: CustomAttributes::default(),
         }
     }
 
+    /// Sets the [custom attributes](CustomAttributes) of the field.
+    pub fn with_custom_attributes(mut self, custom_attributes: CustomAttributes) -> Self {
+        self.custom_attributes = custom_attributes;
+        self
+    }
+
     /// Returns the index of the field.
     pub fn index(&self) -> usize {
         self.index
@@ -81,4 +107,14 @@ impl UnnamedField {
     pub fn is<T: Any>(&self) -> bool {
         TypeId::of::<T>() == self.type_id
     }
+
+    /// The [custom attributes](CustomAttributes) of the field.
+    pub fn custom_attributes(&self) -> &CustomAttributes {
+        &self.custom_attributes
+    }
+
+    /// Returns the [custom attribute](CustomAttributes) of type `T` of the field, if any.
+    pub fn get_attribute<T: Any>(&self) -> Option<&T> {
+        self.custom_attributes.get::<T>()
+    }
 }
@@ -53,6 +53,10 @@ where
         });
         SmallVec::push(self, value);
     }
+
+    fn pop(&mut self) -> Option<Box<dyn Reflect>> {
+        SmallVec::pop(self).map(|value| Box::new(value) as Box<dyn Reflect>)
+    }
 }
 
 impl<T: smallvec::Array + Send + Sync + 'static> Reflect for SmallVec<T>
@@ -1,6 +1,8 @@
 #![doc = include_str!("../README.md")]
 
 mod array;
+mod custom_attributes;
+mod diff;
 mod fields;
 mod list;
 mod map;
@@ -40,12 +42,15 @@ pub mod prelude {
 }
 
 pub use array::*;
+pub use custom_attributes::*;
+pub use diff::*;
 pub use fields::*;
 pub use impls::*;
 pub use list::*;
 pub use map::*;
 pub use path::*;
 pub use reflect::*;
+pub use std_traits::ReflectDefault;
 pub use struct_trait::*;
 pub use tuple::*;
 pub use tuple_struct::*;
@@ -530,6 +535,71 @@ mod tests {
         assert_eq!(y, Bar { x: 2 });
     }
 
+    #[test]
+    fn type_migrations() {
+        #[derive(Reflect, Debug, PartialEq)]
+        struct Health {
+            current: u32,
+            max: u32,
+        }
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<Health>();
+        registry.register_alias("game::HitPoints", std::any::TypeId::of::<Health>());
+        let registration = registry.get_with_name_mut("game::HitPoints").unwrap();
+        registration.set_version(2);
+        // version 0 named the current health `hp`
+        registration.add_migration(0, |mut value| {
+            value
+                .downcast_mut::<DynamicStruct>()
+                .unwrap()
+                .rename_field("hp", "current");
+            value
+        });
+        // version 1 had no maximum health
+        registration.add_migration(1, |mut value| {
+            let health = value.downcast_mut::<DynamicStruct>().unwrap();
+            let current = *health.get_field::<u32>("current").unwrap();
+            health.insert("max", current);
+            value
+        });
+
+        let mut old = DynamicStruct::default();
+        old.insert("unused", 1u8);
+        old.insert("hp", 7u32);
+        let registration = registry.get_with_name("game::HitPoints").unwrap();
+        let migrated = registration.migrate(Box::new(old), 0);
+        let mut health = Health { current: 0, max: 0 };
+        health.apply(&*migrated);
+        assert_eq!(health, Health { current: 7, max: 7 });
+
+        let mut current = DynamicStruct::default();
+        current.insert("current", 3u32);
+        current.insert("max", 5u32);
+        let migrated = registration.migrate(Box::new(current), 2);
+        health.apply(&*migrated);
+        assert_eq!(health, Health { current: 3, max: 5 });
+    }
+
+    #[test]
+    fn dynamic_struct_remove_and_rename() {
+        let mut dynamic_struct = DynamicStruct::default();
+        dynamic_struct.insert("a", 1u32);
+        dynamic_struct.insert("b", 2u32);
+        dynamic_struct.insert("c", 3u32);
+
+        assert_eq!(
+            dynamic_struct.remove("a").unwrap().downcast::<u32>().ok(),
+            Some(Box::new(1))
+        );
+        assert!(dynamic_struct.remove("a").is_none());
+        assert!(dynamic_struct.rename_field("c", "b"));
+        assert!(!dynamic_struct.rename_field("c", "d"));
+        assert_eq!(dynamic_struct.field_len(), 1);
+        assert_eq!(dynamic_struct.name_at(0), Some("b"));
+        assert_eq!(dynamic_struct.get_field::<u32>("b"), Some(&3));
+    }
+
     #[test]
     fn dynamic_names() {
         let list = Vec::<usize>::new();
@@ -804,6 +874,62 @@ mod tests {
         assert!(info.is::<MyDynamic>());
     }
 
+    #[test]
+    fn reflect_custom_attributes() {
+        #[derive(Debug, PartialEq)]
+        struct Tooltip(&'static str);
+
+        #[derive(Reflect)]
+        struct MyStruct {
+            #[reflect(@Tooltip("speed"), @0..10)]
+            foo: usize,
+            bar: usize,
+        }
+
+        #[derive(Reflect)]
+        struct MyTupleStruct(#[reflect(@Tooltip("first"))] usize);
+
+        let info = match MyStruct::type_info() {
+            TypeInfo::Struct(info) => info,
+            _ => panic!("expected struct info"),
+        };
+        let foo = info.field("foo").unwrap();
+        assert_eq!(foo.custom_attributes().len(), 2);
+        assert_eq!(foo.get_attribute::<Tooltip>(), Some(&Tooltip("speed")));
+        assert_eq!(foo.get_attribute::<std::ops::Range<i32>>(), Some(&(0..10)));
+        assert!(info.field("bar").unwrap().custom_attributes().is_empty());
+
+        let info = match MyTupleStruct::type_info() {
+            TypeInfo::TupleStruct(info) => info,
+            _ => panic!("expected tuple struct info"),
+        };
+        assert_eq!(
+            info.field_at(0).unwrap().get_attribute::<Tooltip>(),
+            Some(&Tooltip("first"))
+        );
+    }
+
+    #[test]
+    fn reflect_default() {
+        let mut registry = TypeRegistry::default();
+        registry.register::<u32>();
+        registry.register::<Vec<String>>();
+
+        let reflect_default = registry
+            .get_type_data::<ReflectDefault>(std::any::TypeId::of::<u32>())
+            .unwrap();
+        assert_eq!(reflect_default.default().downcast_ref::<u32>(), Some(&0));
+
+        let reflect_default = registry
+            .get_type_data::<ReflectDefault>(std::any::TypeId::of::<Vec<String>>())
+            .unwrap();
+        assert!(reflect_default
+            .default()
+            .downcast_ref::<Vec<String>>()
+            .unwrap()
+            .is_empty());
+    }
+
     #[test]
     fn as_reflect() {
         trait TestTrait: Reflect {}
@@ -9,12 +9,18 @@ use crate::{
 
 /// An ordered, mutable list of [Reflect] items. This corresponds to types like [`std::vec::Vec`].
 ///
-/// This is a sub-trait of [`Array`] as it implements a [`push`](List::push) function, allowing
-/// it's internal size to grow.
+/// This is a sub-trait of [`Array`] as it implements [`push`](List::push) and [`pop`](List::pop)
+/// functions, allowing it's internal size to change. Implementations must support both.
 pub trait List: Reflect + Array {
     /// Appends an element to the list.
     fn push(&mut self, value: Box<dyn Reflect>);
 
+    /// Removes the last element from the list and returns it, or `None` if it is empty.
+    ///
+    /// Every list must implement this, as applying a [`ReflectChange`](crate::ReflectChange)
+    /// that truncates the list relies on it.
+    fn pop(&mut self) -> Option<Box<dyn Reflect>>;
+
     /// Clones the list, producing a [`DynamicList`].
     fn clone_dynamic(&self) -> DynamicList {
         DynamicList {
@@ -151,6 +157,10 @@ impl List for DynamicList {
         DynamicList::push_box(self, value);
     }
 
+    fn pop(&mut self) -> Option<Box<dyn Reflect>> {
+        self.values.pop()
+    }
+
     fn clone_dynamic(&self) -> DynamicList {
         DynamicList {
             name: self.name.clone(),
@@ -16,7 +16,8 @@ use crate::{DynamicInfo, Reflect, ReflectMut, ReflectRef, TypeInfo, Typed};
 /// of `Map` entries is not guaranteed to be stable across runs or between
 /// instances.
 ///
-/// This trait corresponds to types like [`std::collections::HashMap`].
+/// This trait corresponds to types like [`std::collections::HashMap`]. Implementations must
+/// support removing entries with [`remove`](Map::remove).
 pub trait Map: Reflect {
     /// Returns a reference to the value associated with the given key.
     ///
@@ -54,6 +55,15 @@ pub trait Map: Reflect {
         key: Box<dyn Reflect>,
         value: Box<dyn Reflect>,
     ) -> Option<Box<dyn Reflect>>;
+
+    /// Removes an entry from the map.
+    ///
+    /// If the map did not have this key present, `None` is returned.
+    /// If the map did have this key present, the removed value is returned.
+    ///
+    /// Every map must implement this, as applying a [`ReflectChange`](crate::ReflectChange)
+    /// that removes an entry relies on it.
+    fn remove(&mut self, key: &dyn Reflect) -> Option<Box<dyn Reflect>>;
 }
 
 /// A container for compile-time map info.
@@ -226,6 +236,18 @@ impl Map for DynamicMap {
             }
         }
     }
+
+    fn remove(&mut self, key: &dyn Reflect) -> Option<Box<dyn Reflect>> {
+        let index = self
+            .indices
+            .remove(&key.reflect_hash().expect(HASH_ERROR))?;
+        for value_index in self.indices.values_mut() {
+            if *value_index > index {
+                *value_index -= 1;
+            }
+        }
+        Some(self.values.remove(index).1)
+    }
 }
 
 impl Reflect for DynamicMap {
@@ -15,6 +15,8 @@ pub enum ReflectPathError<'a> {
         index: usize,
         tuple_struct_index: usize,
     },
+    #[error("the current tuple doesn't have a field with the given index")]
+    InvalidTupleIndex { index: usize, tuple_index: usize },
     #[error("the current list doesn't have a value at the given index")]
     InvalidListIndex { index: usize, list_index: usize },
     #[error("encountered an unexpected token")]
@@ -23,7 +25,7 @@ pub enum ReflectPathError<'a> {
     ExpectedToken { index: usize, token: &'a str },
     #[error("expected a struct, but found a different reflect value")]
     ExpectedStruct { index: usize },
-    #[error("expected a list, but found a different reflect value")]
+    #[error("expected a list or an array, but found a different reflect value")]
     ExpectedList { index: usize },
     #[error("failed to parse a usize")]
     IndexParseError(#[from] ParseIntError),
@@ -36,7 +38,7 @@ pub enum ReflectPathError<'a> {
 /// Path strings use Rust syntax:
 /// - [`Struct`] items are accessed with a dot and a field name: `.field_name`
 /// - [`TupleStruct`] and [`Tuple`] items are accessed with a dot and a number: `.0`
-/// - [`List`] items are accessed with brackets: `[0]`
+/// - [`List`] and [`Array`] items are accessed with brackets: `[0]`
 ///
 /// If the initial path element is a field of a struct, tuple struct, or tuple,
 /// the initial '.' may be omitted.
@@ -49,6 +51,7 @@ pub enum ReflectPathError<'a> {
 /// [`TupleStruct`]: crate::TupleStruct
 /// [`Tuple`]: crate::Tuple
 /// [`List`]: crate::List
+/// [`Array`]: crate::Array
 pub trait GetPath {
     /// Returns a reference to the value specified by `path`.
     ///
@@ -131,6 +134,16 @@ impl GetPath for dyn Reflect {
                                 )?;
                                 current = list_item;
                             }
+                            ReflectRef::Array(reflect_array) => {
+                                let list_index = value.parse::<usize>()?;
+                                let array_item = reflect_array.get(list_index).ok_or(
+                                    ReflectPathError::InvalidListIndex {
+                                        index: current_index,
+                                        list_index,
+                                    },
+                                )?;
+                                current = array_item;
+                            }
                             _ => {
                                 return Err(ReflectPathError::ExpectedList {
                                     index: current_index,
@@ -197,6 +210,16 @@ impl GetPath for dyn Reflect {
                                 )?;
                                 current = list_item;
                             }
+                            ReflectMut::Array(reflect_array) => {
+                                let list_index = value.parse::<usize>()?;
+                                let array_item = reflect_array.get_mut(list_index).ok_or(
+                                    ReflectPathError::InvalidListIndex {
+                                        index: current_index,
+                                        list_index,
+                                    },
+                                )?;
+                                current = array_item;
+                            }
                             _ => {
                                 return Err(ReflectPathError::ExpectedStruct {
                                     index: current_index,
@@ -256,6 +279,15 @@ fn read_field<'r, 'p>(
                 },
             )?)
         }
+        ReflectRef::Tuple(reflect_tuple) => {
+            let tuple_index = field.parse::<usize>()?;
+            Ok(reflect_tuple
+                .field(tuple_index)
+                .ok_or(ReflectPathError::InvalidTupleIndex {
+                    index: current_index,
+                    tuple_index,
+                })?)
+        }
         _ => Err(ReflectPathError::ExpectedStruct {
             index: current_index,
         }),
@@ -285,6 +317,15 @@ fn read_field_mut<'r, 'p>(
                 },
             )?)
         }
+        ReflectMut::Tuple(reflect_tuple) => {
+            let tuple_index = field.parse::<usize>()?;
+            Ok(reflect_tuple
+                .field_mut(tuple_index)
+                .ok_or(ReflectPathError::InvalidTupleIndex {
+                    index: current_index,
+                    tuple_index,
+                })?)
+        }
         _ => Err(ReflectPathError::ExpectedStruct {
             index: current_index,
         }),
@@ -349,6 +390,7 @@ mod tests {
             x: B,
             y: Vec<C>,
             z: D,
+            t: (u8, [usize; 2]),
         }
 
         #[derive(Reflect)]
@@ -376,6 +418,7 @@ mod tests {
             },
             y: vec![C { baz: 1.0 }, C { baz: 2.0 }],
             z: D(E(10.0, 42)),
+            t: (1, [2, 3]),
         };
 
         assert_eq!(*a.get_path::<usize>("w").unwrap(), 1);
@@ -383,9 +426,20 @@ mod tests {
         assert_eq!(*a.get_path::<f32>("x.bar.baz").unwrap(), 3.14);
         assert_eq!(*a.get_path::<f32>("y[1].baz").unwrap(), 2.0);
         assert_eq!(*a.get_path::<usize>("z.0.1").unwrap(), 42);
+        assert_eq!(*a.get_path::<usize>("t.1[1]").unwrap(), 3);
 
         *a.get_path_mut::<f32>("y[1].baz").unwrap() = 3.0;
         assert_eq!(a.y[1].baz, 3.0);
+        *a.get_path_mut::<usize>("t.1[0]").unwrap() = 4;
+        assert_eq!(a.t, (1, [4, 3]));
+
+        assert_eq!(
+            a.path("t.2").err().unwrap(),
+            ReflectPathError::InvalidTupleIndex {
+                index: 2,
+                tuple_index: 2
+            }
+        );
 
         assert_eq!(
             a.path("x.notreal").err().unwrap(),
@@ -1,6 +1,7 @@
 use crate::{
-    serde::type_fields, DynamicArray, DynamicList, DynamicMap, DynamicStruct, DynamicTuple,
-    DynamicTupleStruct, Map, Reflect, ReflectDeserialize, TypeRegistry,
+    serde::{diff_fields, type_fields},
+    DynamicArray, DynamicList, DynamicMap, DynamicStruct, DynamicTuple, DynamicTupleStruct, Map,
+    Reflect, ReflectChange, ReflectDeserialize, ReflectDiff, TypeRegistry,
 };
 use erased_serde::Deserializer;
 use serde::de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor};
@@ -507,3 +508,181 @@ impl<'a, 'de> Visitor<'de> for TupleVisitor<'a> {
         Ok(tuple)
     }
 }
+
+/// Deserializes a [`ReflectDiff`] serialized with
+/// [`ReflectDiffSerializer`](crate::serde::ReflectDiffSerializer).
+pub struct ReflectDiffDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> ReflectDiffDeserializer<'a> {
+    pub fn new(registry: &'a TypeRegistry) -> Self {
+        ReflectDiffDeserializer { registry }
+    }
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for ReflectDiffDeserializer<'a> {
+    type Value = ReflectDiff;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(ReflectDiffVisitor {
+            registry: self.registry,
+        })
+    }
+}
+
+struct ReflectDiffVisitor<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for ReflectDiffVisitor<'a> {
+    type Value = ReflectDiff;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("reflect diff")
+    }
+
+    fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let mut changes = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(change) = seq.next_element_seed(ReflectChangeDeserializer {
+            registry: self.registry,
+        })? {
+            changes.push(change);
+        }
+        Ok(ReflectDiff::from_changes(changes))
+    }
+}
+
+struct ReflectChangeDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for ReflectChangeDeserializer<'a> {
+    type Value = (String, ReflectChange);
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(ReflectChangeVisitor {
+            registry: self.registry,
+        })
+    }
+}
+
+struct ReflectChangeVisitor<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for ReflectChangeVisitor<'a> {
+    type Value = (String, ReflectChange);
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("reflect change")
+    }
+
+    fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error>
+    where
+        V: MapAccess<'de>,
+    {
+        let mut path: Option<String> = None;
+        let mut change = None;
+        let mut map_key = None;
+        let mut map_value = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                diff_fields::PATH => {
+                    path = Some(map.next_value()?);
+                }
+                diff_fields::REPLACE => {
+                    let value = map.next_value_seed(ReflectDeserializer::new(self.registry))?;
+                    change = Some(ReflectChange::Replace(value));
+                }
+                diff_fields::LIST_PUSH => {
+                    let values = map.next_value_seed(ValuesDeserializer {
+                        registry: self.registry,
+                    })?;
+                    change = Some(ReflectChange::ListPush(values));
+                }
+                diff_fields::LIST_TRUNCATE => {
+                    change = Some(ReflectChange::ListTruncate(map.next_value()?));
+                }
+                diff_fields::MAP_INSERT => {
+                    map_key = Some(map.next_value_seed(ReflectDeserializer::new(self.registry))?);
+                }
+                diff_fields::VALUE => {
+                    map_value = Some(map.next_value_seed(ReflectDeserializer::new(self.registry))?);
+                }
+                diff_fields::MAP_REMOVE => {
+                    let key = map.next_value_seed(ReflectDeserializer::new(self.registry))?;
+                    change = Some(ReflectChange::MapRemove(key));
+                }
+                _ => return Err(de::Error::unknown_field(key.as_str(), diff_fields::ALL)),
+            }
+        }
+
+        let path = path.ok_or_else(|| de::Error::missing_field(diff_fields::PATH))?;
+        let change = match (change, map_key, map_value) {
+            (None, Some(key), Some(value)) => ReflectChange::MapInsert { key, value },
+            (None, Some(_), None) => return Err(de::Error::missing_field(diff_fields::VALUE)),
+            (Some(change), None, None) => change,
+            (Some(_), None, Some(_)) => {
+                return Err(de::Error::custom(
+                    "The 'value' field is only allowed along with the 'map_insert' field",
+                ))
+            }
+            _ => return Err(de::Error::custom(
+                "Changes must have the 'path' field and one of the following fields: 'replace', 'list_push', 'list_truncate', 'map_insert', 'map_remove'",
+            )),
+        };
+        Ok((path, change))
+    }
+}
+
+struct ValuesDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for ValuesDeserializer<'a> {
+    type Value = Vec<Box<dyn Reflect>>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(ValuesVisitor {
+            registry: self.registry,
+        })
+    }
+}
+
+struct ValuesVisitor<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for ValuesVisitor<'a> {
+    type Value = Vec<Box<dyn Reflect>>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("sequence of reflect values")
+    }
+
+    fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let mut values = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(value) = seq.next_element_seed(ReflectDeserializer {
+            registry: self.registry,
+        })? {
+            values.push(value);
+        }
+        Ok(values)
+    }
+}
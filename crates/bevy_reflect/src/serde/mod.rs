@@ -1,7 +1,11 @@
 mod de;
+#[cfg(feature = "json_schema")]
+mod schema;
 mod ser;
 
 pub use de::*;
+#[cfg(feature = "json_schema")]
+pub use schema::*;
 pub use ser::*;
 
 pub(crate) mod type_fields {
@@ -14,3 +18,23 @@ pub(crate) mod type_fields {
     pub const ARRAY: &str = "array";
     pub const VALUE: &str = "value";
 }
+
+pub(crate) mod diff_fields {
+    pub const PATH: &str = "path";
+    pub const REPLACE: &str = "replace";
+    pub const LIST_PUSH: &str = "list_push";
+    pub const LIST_TRUNCATE: &str = "list_truncate";
+    pub const MAP_INSERT: &str = "map_insert";
+    pub const MAP_REMOVE: &str = "map_remove";
+    pub const VALUE: &str = "value";
+
+    pub const ALL: &[&str] = &[
+        PATH,
+        REPLACE,
+        LIST_PUSH,
+        LIST_TRUNCATE,
+        MAP_INSERT,
+        MAP_REMOVE,
+        VALUE,
+    ];
+}
@@ -0,0 +1,501 @@
+use crate::{serde::type_fields, TypeInfo, TypeRegistry};
+use serde_json::{json, Map, Value};
+use std::any::TypeId;
+
+/// The JSON Schema dialect of the documents returned by [`registry_schema`] and [`type_schema`].
+pub const SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Returns a JSON Schema document validating reflected values of any type of the `registry`, in
+/// the format written by [`ReflectSerializer`](crate::serde::ReflectSerializer).
+///
+/// The schema of each registered type is defined in the `$defs` of the document, under its
+/// [type name](std::any::type_name), so that other schemas can [reference](schema_ref) them.
+///
+/// Maps are serialized with reflected keys, which JSON objects can't represent, so the schema
+/// of a map only validates its values.
+///
+/// Requires the `json_schema` feature.
+pub fn registry_schema(registry: &TypeRegistry) -> Value {
+    let mut builder = SchemaBuilder::new(registry);
+    let mut type_names: Vec<&str> = registry
+        .iter()
+        .map(|registration| registration.type_name())
+        .collect();
+    type_names.sort_unstable();
+    let any_of: Vec<Value> = type_names
+        .iter()
+        .map(|type_name| schema_ref(type_name))
+        .collect();
+    for registration in registry.iter() {
+        builder.define(registration.type_info());
+    }
+
+    let mut schema = builder.document();
+    schema.insert("anyOf".to_string(), Value::Array(any_of));
+    Value::Object(schema)
+}
+
+/// Returns a JSON Schema document validating reflected values of the type with the given
+/// [`TypeId`], in the format written by [`ReflectSerializer`](crate::serde::ReflectSerializer).
+///
+/// The document defines the schemas of the types the type refers to in its `$defs`, like
+/// [`registry_schema`].
+///
+/// Returns `None` if the type is not registered.
+pub fn type_schema(type_id: TypeId, registry: &TypeRegistry) -> Option<Value> {
+    let type_info = registry.get_type_info(type_id)?;
+    let mut builder = SchemaBuilder::new(registry);
+    builder.define(type_info);
+
+    let mut schema = builder.document();
+    schema.insert(
+        "$ref".to_string(),
+        Value::String(def_pointer(type_info.type_name())),
+    );
+    Some(Value::Object(schema))
+}
+
+/// Returns a JSON Schema referencing the schema of the type named `type_name` in the `$defs` of
+/// a document returned by [`registry_schema`] or [`type_schema`].
+pub fn schema_ref(type_name: &str) -> Value {
+    json!({ "$ref": def_pointer(type_name) })
+}
+
+fn def_pointer(type_name: &str) -> String {
+    let mut reference = String::from("#/$defs/");
+    // escape the type name as a JSON pointer, then as a URI fragment
+    for character in type_name.chars() {
+        match character {
+            '~' => reference.push_str("~0"),
+            '/' => reference.push_str("~1"),
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '.' | '_' | ':' => reference.push(character),
+            _ => {
+                let mut bytes = [0; 4];
+                for byte in character.encode_utf8(&mut bytes).bytes() {
+                    reference.push_str(&format!("%{:02X}", byte));
+                }
+            }
+        }
+    }
+    reference
+}
+
+struct SchemaBuilder<'a> {
+    registry: &'a TypeRegistry,
+    defs: Map<String, Value>,
+}
+
+impl<'a> SchemaBuilder<'a> {
+    fn new(registry: &'a TypeRegistry) -> Self {
+        SchemaBuilder {
+            registry,
+            defs: Map::new(),
+        }
+    }
+
+    fn document(self) -> Map<String, Value> {
+        let mut schema = Map::new();
+        schema.insert("$schema".to_string(), json!(SCHEMA_DIALECT));
+        schema.insert("$defs".to_string(), Value::Object(self.defs));
+        schema
+    }
+
+    /// Defines the schema of the type, and of the registered types it refers to.
+    fn define(&mut self, type_info: &TypeInfo) {
+        if self.defs.contains_key(type_info.type_name()) {
+            return;
+        }
+        // inserted first, for recursive types
+        self.defs
+            .insert(type_info.type_name().to_string(), Value::Bool(true));
+        let schema = match type_info {
+            TypeInfo::Struct(info) => {
+                let properties: Map<String, Value> = info
+                    .iter()
+                    .map(|field| {
+                        let schema = self.field(field.type_name(), field.type_id());
+                        (field.name().to_string(), schema)
+                    })
+                    .collect();
+                reflected(
+                    info.type_name(),
+                    type_fields::STRUCT,
+                    json!({
+                        "type": "object",
+                        "properties": properties,
+                        "additionalProperties": false,
+                    }),
+                )
+            }
+            TypeInfo::TupleStruct(info) => {
+                let fields: Vec<Value> = info
+                    .iter()
+                    .map(|field| self.field(field.type_name(), field.type_id()))
+                    .collect();
+                reflected(info.type_name(), type_fields::TUPLE_STRUCT, tuple(fields))
+            }
+            TypeInfo::Tuple(info) => {
+                let fields: Vec<Value> = info
+                    .iter()
+                    .map(|field| self.field(field.type_name(), field.type_id()))
+                    .collect();
+                reflected(info.type_name(), type_fields::TUPLE, tuple(fields))
+            }
+            TypeInfo::List(info) => {
+                let item = self.field(info.item_type_name(), info.item_type_id());
+                reflected(
+                    info.type_name(),
+                    type_fields::LIST,
+                    json!({ "type": "array", "items": item }),
+                )
+            }
+            TypeInfo::Array(info) => {
+                let item = self.field(info.item_type_name(), info.item_type_id());
+                reflected(
+                    info.type_name(),
+                    type_fields::ARRAY,
+                    json!({
+                        "type": "array",
+                        "items": item,
+                        "minItems": info.capacity(),
+                        "maxItems": info.capacity(),
+                    }),
+                )
+            }
+            TypeInfo::Map(info) => {
+                let value = self.field(info.value_type_name(), info.value_type_id());
+                reflected(
+                    info.type_name(),
+                    type_fields::MAP,
+                    json!({ "type": "object", "additionalProperties": value }),
+                )
+            }
+            TypeInfo::Value(info) => reflected(
+                info.type_name(),
+                type_fields::VALUE,
+                value_schema(info.type_id()),
+            ),
+            // dynamic types are serialized in the format of the type they represent
+            TypeInfo::Dynamic(_) => Value::Bool(true),
+        };
+        self.defs.insert(type_info.type_name().to_string(), schema);
+    }
+
+    /// Returns the schema of a field, referencing the schema of its type if it is registered.
+    fn field(&mut self, type_name: &str, type_id: TypeId) -> Value {
+        match self.registry.get_type_info(type_id) {
+            Some(type_info) => {
+                self.define(type_info);
+                schema_ref(type_name)
+            }
+            None => json!({
+                "type": "object",
+                "properties": { type_fields::TYPE: { "const": type_name } },
+                "required": [type_fields::TYPE],
+            }),
+        }
+    }
+}
+
+/// The schema of a reflected value, a `type` name and its data under the `kind` of the type.
+fn reflected(type_name: &str, kind: &str, data: Value) -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            type_fields::TYPE: { "const": type_name },
+            kind: data,
+        },
+        "required": [type_fields::TYPE, kind],
+        "additionalProperties": false,
+    })
+}
+
+fn tuple(fields: Vec<Value>) -> Value {
+    let len = fields.len();
+    json!({
+        "type": "array",
+        "prefixItems": fields,
+        "minItems": len,
+        "maxItems": len,
+    })
+}
+
+/// The schema of the serialized data of a value type, which is only known for primitives.
+fn value_schema(type_id: TypeId) -> Value {
+    macro_rules! integers {
+        ($($ty:ty),*) => {
+            $(
+                if type_id == TypeId::of::<$ty>() {
+                    return json!({
+                        "type": "integer",
+                        "minimum": <$ty>::MIN,
+                        "maximum": <$ty>::MAX,
+                    });
+                }
+            )*
+        };
+    }
+    integers!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+    if type_id == TypeId::of::<u128>() || type_id == TypeId::of::<i128>() {
+        json!({ "type": "integer" })
+    } else if type_id == TypeId::of::<f32>() || type_id == TypeId::of::<f64>() {
+        json!({ "type": "number" })
+    } else if type_id == TypeId::of::<bool>() {
+        json!({ "type": "boolean" })
+    } else if type_id == TypeId::of::<String>() {
+        json!({ "type": "string" })
+    } else if type_id == TypeId::of::<char>() {
+        json!({ "type": "string", "minLength": 1, "maxLength": 1 })
+    } else {
+        Value::Bool(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{self as bevy_reflect, serde::ReflectSerializer, FromReflect, Reflect};
+    use bevy_utils::HashMap;
+
+    #[derive(Reflect)]
+    struct Player {
+        name: String,
+        health: u8,
+        position: [f32; 2],
+        inventory: Vec<Item>,
+        counts: HashMap<String, u32>,
+        flags: (bool, char),
+    }
+
+    #[derive(Reflect, FromReflect)]
+    struct Item(u32);
+
+    fn registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Player>();
+        registry.register::<Item>();
+        registry.register::<String>();
+        registry.register::<u8>();
+        registry.register::<u32>();
+        registry.register::<f32>();
+        registry.register::<bool>();
+        registry.register::<char>();
+        registry.register::<[f32; 2]>();
+        registry.register::<Vec<Item>>();
+        registry.register::<HashMap<String, u32>>();
+        registry
+    }
+
+    #[test]
+    fn struct_schema() {
+        let registry = registry();
+        let schema = type_schema(TypeId::of::<Player>(), &registry).unwrap();
+        assert_eq!(schema["$schema"], SCHEMA_DIALECT);
+        assert_eq!(
+            schema["$ref"],
+            schema_ref(std::any::type_name::<Player>())["$ref"]
+        );
+
+        let defs = &schema["$defs"];
+        let player = &defs[std::any::type_name::<Player>()];
+        assert_eq!(player["required"], json!(["type", "struct"]));
+        let fields = &player["properties"]["struct"]["properties"];
+        assert_eq!(fields["name"], schema_ref(std::any::type_name::<String>()));
+        assert_eq!(
+            defs[std::any::type_name::<u8>()]["properties"]["value"],
+            json!({ "type": "integer", "minimum": 0, "maximum": 255 })
+        );
+        assert_eq!(
+            defs[std::any::type_name::<[f32; 2]>()]["properties"]["array"]["maxItems"],
+            2
+        );
+        assert_eq!(
+            defs[std::any::type_name::<Item>()]["properties"]["tuple_struct"]["prefixItems"],
+            json!([schema_ref(std::any::type_name::<u32>())])
+        );
+        // the tuple is not registered, so only its type name is known
+        assert_eq!(
+            fields["flags"]["properties"]["type"]["const"],
+            std::any::type_name::<(bool, char)>()
+        );
+        // unreferenced types are not defined
+        assert!(defs.get(std::any::type_name::<f64>()).is_none());
+    }
+
+    /// Validates `value` against `schema`, for the keywords used by [`SchemaBuilder`].
+    fn validate(document: &Value, schema: &Value, value: &Value) -> bool {
+        let schema = match schema {
+            Value::Bool(valid) => return *valid,
+            Value::Object(schema) => schema,
+            _ => panic!("invalid schema: {}", schema),
+        };
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            let pointer = reference.strip_prefix('#').unwrap();
+            let mut decoded = Vec::new();
+            let mut bytes = pointer.bytes();
+            while let Some(byte) = bytes.next() {
+                if byte == b'%' {
+                    let hex = [bytes.next().unwrap(), bytes.next().unwrap()];
+                    let hex = std::str::from_utf8(&hex).unwrap();
+                    decoded.push(u8::from_str_radix(hex, 16).unwrap());
+                } else {
+                    decoded.push(byte);
+                }
+            }
+            let pointer = String::from_utf8(decoded).unwrap();
+            let target = document.pointer(&pointer).expect("dangling reference");
+            if !validate(document, target, value) {
+                return false;
+            }
+        }
+        if let Some(any_of) = schema.get("anyOf").and_then(Value::as_array) {
+            if !any_of
+                .iter()
+                .any(|schema| validate(document, schema, value))
+            {
+                return false;
+            }
+        }
+        if let Some(constant) = schema.get("const") {
+            if constant != value {
+                return false;
+            }
+        }
+        if let Some(kind) = schema.get("type").and_then(Value::as_str) {
+            let valid = match kind {
+                "object" => value.is_object(),
+                "array" => value.is_array(),
+                "string" => value.is_string(),
+                "boolean" => value.is_boolean(),
+                "number" => value.is_number(),
+                "integer" => value.is_i64() || value.is_u64(),
+                _ => panic!("unknown type: {}", kind),
+            };
+            if !valid {
+                return false;
+            }
+        }
+        if let Some(number) = value.as_f64() {
+            let minimum = schema.get("minimum").and_then(Value::as_f64);
+            let maximum = schema.get("maximum").and_then(Value::as_f64);
+            if matches!(minimum, Some(minimum) if number < minimum)
+                || matches!(maximum, Some(maximum) if number > maximum)
+            {
+                return false;
+            }
+        }
+        if let Some(string) = value.as_str() {
+            let len = string.chars().count() as u64;
+            let min = schema.get("minLength").and_then(Value::as_u64);
+            let max = schema.get("maxLength").and_then(Value::as_u64);
+            if matches!(min, Some(min) if len < min) || matches!(max, Some(max) if len > max) {
+                return false;
+            }
+        }
+        if let Some(object) = value.as_object() {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            if let Some(required) = schema.get("required").and_then(Value::as_array) {
+                if !required
+                    .iter()
+                    .all(|key| object.contains_key(key.as_str().unwrap()))
+                {
+                    return false;
+                }
+            }
+            for (key, field) in object {
+                let field_schema = properties
+                    .and_then(|properties| properties.get(key))
+                    .or_else(|| schema.get("additionalProperties"));
+                if let Some(field_schema) = field_schema {
+                    if !validate(document, field_schema, field) {
+                        return false;
+                    }
+                }
+            }
+        }
+        if let Some(array) = value.as_array() {
+            let len = array.len() as u64;
+            let min = schema.get("minItems").and_then(Value::as_u64);
+            let max = schema.get("maxItems").and_then(Value::as_u64);
+            if matches!(min, Some(min) if len < min) || matches!(max, Some(max) if len > max) {
+                return false;
+            }
+            let prefix_items = schema
+                .get("prefixItems")
+                .and_then(Value::as_array)
+                .map_or(&[][..], Vec::as_slice);
+            for (index, item) in array.iter().enumerate() {
+                let item_schema = prefix_items.get(index).or_else(|| schema.get("items"));
+                if let Some(item_schema) = item_schema {
+                    if !validate(document, item_schema, item) {
+                        return false;
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    fn player() -> Player {
+        Player {
+            name: "Ferris".to_string(),
+            health: 100,
+            position: [1.0, 2.5],
+            inventory: vec![Item(3), Item(7)],
+            // reflected map keys can't be written as JSON object keys
+            counts: HashMap::default(),
+            flags: (true, 'x'),
+        }
+    }
+
+    #[test]
+    fn serialized_values_match_schema() {
+        let registry = registry();
+        let schema = registry_schema(&registry);
+        let player = player();
+        let serialized = serde_json::to_value(ReflectSerializer::new(&player, &registry)).unwrap();
+        assert!(validate(&schema, &schema, &serialized));
+        let schema = type_schema(TypeId::of::<Player>(), &registry).unwrap();
+        assert!(validate(&schema, &schema, &serialized));
+
+        // an out of range field
+        let mut invalid = serialized.clone();
+        invalid["struct"]["health"]["value"] = json!(300);
+        assert!(!validate(&schema, &schema, &invalid));
+
+        // a missing field type
+        let mut invalid = serialized.clone();
+        invalid["struct"]["name"]
+            .as_object_mut()
+            .unwrap()
+            .remove("type");
+        assert!(!validate(&schema, &schema, &invalid));
+
+        // a list item of another type
+        let mut invalid = serialized.clone();
+        invalid["struct"]["inventory"]["list"][1] =
+            serde_json::to_value(ReflectSerializer::new(&String::from("sword"), &registry))
+                .unwrap();
+        assert!(!validate(&schema, &schema, &invalid));
+
+        // an array of the wrong length
+        let mut invalid = serialized.clone();
+        invalid["struct"]["position"]["array"]
+            .as_array_mut()
+            .unwrap()
+            .pop();
+        assert!(!validate(&schema, &schema, &invalid));
+
+        // an unknown field
+        let mut invalid = serialized;
+        invalid["struct"]["mana"] = json!({ "type": "u8", "value": 3 });
+        assert!(!validate(&schema, &schema, &invalid));
+    }
+
+    #[test]
+    fn schema_ref_escapes_type_names() {
+        let reference = schema_ref("alloc::vec::Vec<a/b~c>");
+        assert_eq!(reference["$ref"], "#/$defs/alloc::vec::Vec%3Ca~1b~0c%3E");
+    }
+}
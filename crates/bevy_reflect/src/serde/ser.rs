@@ -1,6 +1,7 @@
 use crate::{
-    serde::type_fields, Array, List, Map, Reflect, ReflectRef, ReflectSerialize, Struct, Tuple,
-    TupleStruct, TypeRegistry,
+    serde::{diff_fields, type_fields},
+    Array, List, Map, Reflect, ReflectChange, ReflectDiff, ReflectRef, ReflectSerialize, Struct,
+    Tuple, TupleStruct, TypeRegistry,
 };
 use serde::{
     ser::{SerializeMap, SerializeSeq},
@@ -366,3 +367,109 @@ impl<'a> Serialize for ArrayValueSerializer<'a> {
         state.end()
     }
 }
+
+/// Serializes a [`ReflectDiff`] as a sequence of changes, each being a map with the `path` of
+/// the changed value and the kind of the change, the changed values being serialized with
+/// [`ReflectSerializer`].
+pub struct ReflectDiffSerializer<'a> {
+    pub diff: &'a ReflectDiff,
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> ReflectDiffSerializer<'a> {
+    pub fn new(diff: &'a ReflectDiff, registry: &'a TypeRegistry) -> Self {
+        ReflectDiffSerializer { diff, registry }
+    }
+}
+
+impl<'a> Serialize for ReflectDiffSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.diff.len()))?;
+        for (path, change) in self.diff.iter() {
+            state.serialize_element(&ReflectChangeSerializer {
+                path,
+                change,
+                registry: self.registry,
+            })?;
+        }
+        state.end()
+    }
+}
+
+struct ReflectChangeSerializer<'a> {
+    path: &'a str,
+    change: &'a ReflectChange,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for ReflectChangeSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let len = match self.change {
+            ReflectChange::MapInsert { .. } => 3,
+            _ => 2,
+        };
+        let mut state = serializer.serialize_map(Some(len))?;
+        state.serialize_entry(diff_fields::PATH, self.path)?;
+        match self.change {
+            ReflectChange::Replace(value) => {
+                state.serialize_entry(
+                    diff_fields::REPLACE,
+                    &ReflectSerializer::new(&**value, self.registry),
+                )?;
+            }
+            ReflectChange::ListPush(values) => {
+                state.serialize_entry(
+                    diff_fields::LIST_PUSH,
+                    &ValuesSerializer {
+                        values,
+                        registry: self.registry,
+                    },
+                )?;
+            }
+            ReflectChange::ListTruncate(len) => {
+                state.serialize_entry(diff_fields::LIST_TRUNCATE, len)?;
+            }
+            ReflectChange::MapInsert { key, value } => {
+                state.serialize_entry(
+                    diff_fields::MAP_INSERT,
+                    &ReflectSerializer::new(&**key, self.registry),
+                )?;
+                state.serialize_entry(
+                    diff_fields::VALUE,
+                    &ReflectSerializer::new(&**value, self.registry),
+                )?;
+            }
+            ReflectChange::MapRemove(key) => {
+                state.serialize_entry(
+                    diff_fields::MAP_REMOVE,
+                    &ReflectSerializer::new(&**key, self.registry),
+                )?;
+            }
+        }
+        state.end()
+    }
+}
+
+struct ValuesSerializer<'a> {
+    values: &'a [Box<dyn Reflect>],
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for ValuesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.values.len()))?;
+        for value in self.values {
+            state.serialize_element(&ReflectSerializer::new(&**value, self.registry))?;
+        }
+        state.end()
+    }
+}
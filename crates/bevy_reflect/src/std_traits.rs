@@ -2,13 +2,36 @@ use crate::{FromType, Reflect};
 
 /// A struct used to provide the default value of a type.
 ///
-/// A [`ReflectDefault`] for type `T` can be obtained via [`FromType::from_type`].
+/// A [`ReflectDefault`] for type `T` can be obtained via [`FromType::from_type`]. It is
+/// registered with `#[reflect(Default)]`, and for the standard types implementing [`Default`],
+/// so that default values of registered types can be created from their [`TypeId`]:
+///
+/// ```
+/// # use bevy_reflect::{Reflect, ReflectDefault, TypeRegistry};
+/// # use std::any::TypeId;
+/// #[derive(Reflect, Default)]
+/// #[reflect(Default)]
+/// struct Light {
+///     intensity: f32,
+/// }
+///
+/// let mut registry = TypeRegistry::default();
+/// registry.register::<Light>();
+/// let reflect_default = registry
+///     .get_type_data::<ReflectDefault>(TypeId::of::<Light>())
+///     .unwrap();
+/// let light = reflect_default.default();
+/// assert!(light.is::<Light>());
+/// ```
+///
+/// [`TypeId`]: std::any::TypeId
 #[derive(Clone)]
 pub struct ReflectDefault {
     default: fn() -> Box<dyn Reflect>,
 }
 
 impl ReflectDefault {
+    /// Returns the default value of the type.
     pub fn default(&self) -> Box<dyn Reflect> {
         (self.default)()
     }
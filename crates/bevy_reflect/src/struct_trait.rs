@@ -276,6 +276,38 @@ impl DynamicStruct {
             self.insert_boxed(name, Box::new(value));
         }
     }
+
+    /// Removes the field named `name` from the struct, returning its value if it existed.
+    ///
+    /// The following fields are shifted to keep the order of the fields.
+    pub fn remove(&mut self, name: &str) -> Option<Box<dyn Reflect>> {
+        let index = self.field_indices.remove(name)?;
+        self.field_names.remove(index);
+        for field_index in self.field_indices.values_mut() {
+            if *field_index > index {
+                *field_index -= 1;
+            }
+        }
+        Some(self.fields.remove(index))
+    }
+
+    /// Renames the field named `from` to `to`, keeping its position.
+    ///
+    /// Returns `false` if the struct has no field named `from`. If a field named `to` already
+    /// exists, it is removed.
+    pub fn rename_field(&mut self, from: &str, to: &str) -> bool {
+        if !self.field_indices.contains_key(from) {
+            return false;
+        }
+        if from != to {
+            self.remove(to);
+        }
+        let index = self.field_indices.remove(from).unwrap();
+        let to: Cow<'static, str> = Cow::Owned(to.to_string());
+        self.field_names[index] = to.clone();
+        self.field_indices.insert(to, index);
+        true
+    }
 }
 
 impl Struct for DynamicStruct {
@@ -12,6 +12,7 @@ pub struct TypeRegistry {
     short_name_to_id: HashMap<String, TypeId>,
     full_name_to_id: HashMap<String, TypeId>,
     ambiguous_names: HashSet<String>,
+    aliases: HashMap<String, TypeId>,
 }
 
 // TODO:  remove this wrapper once we migrate to Atelier Assets and the Scene AssetLoader doesn't
@@ -49,6 +50,7 @@ impl TypeRegistry {
             short_name_to_id: Default::default(),
             full_name_to_id: Default::default(),
             ambiguous_names: Default::default(),
+            aliases: Default::default(),
         }
     }
 
@@ -104,6 +106,18 @@ impl TypeRegistry {
             .insert(registration.type_id(), registration);
     }
 
+    /// Removes the registration of the type with the given [`TypeId`], returning it if it existed.
+    ///
+    /// [`TypeId`]: std::any::TypeId
+    pub fn remove(&mut self, type_id: TypeId) -> Option<TypeRegistration> {
+        let registration = self.registrations.remove(&type_id)?;
+        if self.short_name_to_id.get(registration.short_name()) == Some(&type_id) {
+            self.short_name_to_id.remove(registration.short_name());
+        }
+        self.full_name_to_id.remove(registration.type_name());
+        Some(registration)
+    }
+
     /// Registers the type data `D` for type `T`.
     ///
     /// Most of the time [`TypeRegistry::register`] can be used instead to register a type you derived [`Reflect`] for.
@@ -152,26 +166,51 @@ impl TypeRegistry {
     }
 
     /// Returns a reference to the [`TypeRegistration`] of the type with the
-    /// given name.
+    /// given name, or with the given [alias](TypeRegistry::register_alias).
     ///
     /// If no type with the given name has been registered, returns `None`.
     pub fn get_with_name(&self, type_name: &str) -> Option<&TypeRegistration> {
         self.full_name_to_id
             .get(type_name)
+            .or_else(|| self.aliases.get(type_name))
             .and_then(|id| self.get(*id))
     }
 
     /// Returns a mutable reference to the [`TypeRegistration`] of the type with
-    /// the given name.
+    /// the given name, or with the given [alias](TypeRegistry::register_alias).
     ///
     /// If no type with the given name has been registered, returns `None`.
     pub fn get_with_name_mut(&mut self, type_name: &str) -> Option<&mut TypeRegistration> {
         self.full_name_to_id
             .get(type_name)
+            .or_else(|| self.aliases.get(type_name))
             .cloned()
             .and_then(move |id| self.get_mut(id))
     }
 
+    /// Registers `alias` as another name of the type with the given [`TypeId`], such as its name
+    /// before it was renamed, so that [`TypeRegistry::get_with_name`] finds it.
+    ///
+    /// The names of registered types take precedence over aliases.
+    ///
+    /// ```rust
+    /// # use bevy_reflect::{Reflect, TypeRegistry};
+    /// # use std::any::TypeId;
+    /// #[derive(Reflect)]
+    /// struct Health(u32);
+    ///
+    /// let mut type_registry = TypeRegistry::default();
+    /// type_registry.register::<Health>();
+    /// type_registry.register_alias("game::HitPoints", TypeId::of::<Health>());
+    /// let registration = type_registry.get_with_name("game::HitPoints").unwrap();
+    /// assert_eq!(registration.type_id(), TypeId::of::<Health>());
+    /// ```
+    ///
+    /// [`TypeId`]: std::any::TypeId
+    pub fn register_alias(&mut self, alias: impl Into<String>, type_id: TypeId) {
+        self.aliases.insert(alias.into(), type_id);
+    }
+
     /// Returns a reference to the [`TypeRegistration`] of the type with
     /// the given short name.
     ///
@@ -270,8 +309,14 @@ pub struct TypeRegistration {
     short_name: String,
     data: HashMap<TypeId, Box<dyn TypeData>>,
     type_info: &'static TypeInfo,
+    version: u32,
+    migrations: HashMap<u32, TypeMigration>,
 }
 
+/// A function upgrading reflected data of a type from a version to the next one, see
+/// [`TypeRegistration::add_migration`].
+pub type TypeMigration = fn(Box<dyn Reflect>) -> Box<dyn Reflect>;
+
 impl TypeRegistration {
     /// Returns the [`TypeId`] of the type.
     ///
@@ -320,6 +365,8 @@ impl TypeRegistration {
             data: HashMap::default(),
             short_name: bevy_utils::get_short_name(type_name),
             type_info: T::type_info(),
+            version: 0,
+            migrations: HashMap::default(),
         }
     }
 
@@ -336,6 +383,65 @@ impl TypeRegistration {
     pub fn type_name(&self) -> &'static str {
         self.type_info.type_name()
     }
+
+    /// Returns the version of the shape of the type, `0` unless it was
+    /// [set](TypeRegistration::set_version).
+    ///
+    /// Serialized data records the version of its types, so that data of older versions can be
+    /// [migrated](TypeRegistration::migrate).
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Sets the version of the shape of the type.
+    ///
+    /// The version should be increased when the reflected shape of the type changes, along with a
+    /// [migration](TypeRegistration::add_migration) from the previous version.
+    pub fn set_version(&mut self, version: u32) {
+        self.version = version;
+    }
+
+    /// Adds a migration upgrading reflected data of the type from `from_version` to the next
+    /// version, replacing the previous migration from that version.
+    ///
+    /// Migrations usually receive dynamic data, such as a [`DynamicStruct`], and return it in the
+    /// shape of the next version.
+    ///
+    /// ```rust
+    /// # use bevy_reflect::{DynamicStruct, Reflect, TypeRegistration};
+    /// #[derive(Reflect)]
+    /// struct Health {
+    ///     // named `hit_points` in version 0
+    ///     current: u32,
+    /// }
+    ///
+    /// let mut registration = TypeRegistration::of::<Health>();
+    /// registration.set_version(1);
+    /// registration.add_migration(0, |mut value| {
+    ///     if let Some(health) = value.downcast_mut::<DynamicStruct>() {
+    ///         health.rename_field("hit_points", "current");
+    ///     }
+    ///     value
+    /// });
+    /// ```
+    ///
+    /// [`DynamicStruct`]: crate::DynamicStruct
+    pub fn add_migration(&mut self, from_version: u32, migration: TypeMigration) {
+        self.migrations.insert(from_version, migration);
+    }
+
+    /// Upgrades reflected data of the type from `version` to the current
+    /// [version](TypeRegistration::version), applying the migrations of each version in order.
+    ///
+    /// Versions without a migration leave the data unchanged.
+    pub fn migrate(&self, mut value: Box<dyn Reflect>, version: u32) -> Box<dyn Reflect> {
+        for version in version..self.version {
+            if let Some(migration) = self.migrations.get(&version) {
+                value = migration(value);
+            }
+        }
+        value
+    }
 }
 
 impl Clone for TypeRegistration {
@@ -349,6 +455,8 @@ impl Clone for TypeRegistration {
             data,
             short_name: self.short_name.clone(),
             type_info: self.type_info,
+            version: self.version,
+            migrations: self.migrations.clone(),
         }
     }
 }
@@ -524,7 +632,7 @@ impl<T: Reflect> FromType<T> for ReflectFromPtr {
 mod test {
     use std::ptr::NonNull;
 
-    use crate::{GetTypeRegistration, ReflectFromPtr, TypeRegistration};
+    use crate::{GetTypeRegistration, ReflectFromPtr, TypeRegistration, TypeRegistry};
     use bevy_ptr::{Ptr, PtrMut};
     use bevy_utils::HashMap;
 
@@ -607,4 +715,24 @@ mod test {
             "Option<HashMap<Option<String>, (String, Option<String>)>>"
         );
     }
+
+    #[test]
+    fn test_remove_registration() {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Option<f64>>();
+        assert!(registry.get_with_short_name("Option<f64>").is_some());
+
+        let removed = registry.remove(std::any::TypeId::of::<Option<f64>>());
+        assert_eq!(
+            removed.unwrap().type_name(),
+            std::any::type_name::<Option<f64>>()
+        );
+        assert!(registry
+            .get(std::any::TypeId::of::<Option<f64>>())
+            .is_none());
+        assert!(registry.get_with_short_name("Option<f64>").is_none());
+        assert!(registry
+            .get_with_name(std::any::type_name::<Option<f64>>())
+            .is_none());
+    }
 }
@@ -18,8 +18,11 @@ pub struct SkinnedMesh {
 
 impl MapEntities for SkinnedMesh {
     fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+        // Joints can be in the outside world, in which case they should not be mapped.
         for joint in &mut self.joints {
-            *joint = entity_map.get(*joint)?;
+            if let Ok(mapped_joint) = entity_map.get(*joint) {
+                *joint = mapped_joint;
+            }
         }
 
         Ok(())
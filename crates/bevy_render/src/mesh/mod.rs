@@ -15,6 +15,12 @@ pub struct MeshPlugin;
 impl Plugin for MeshPlugin {
     fn build(&self, app: &mut App) {
         app.add_asset::<Mesh>()
+            .register_asset_size_hint::<Mesh>(|mesh| {
+                mesh.attributes()
+                    .map(|(_, values)| values.get_bytes().len())
+                    .sum::<usize>()
+                    + mesh.get_index_buffer_bytes().map_or(0, <[u8]>::len)
+            })
             .add_asset::<skinning::SkinnedMeshInverseBindposes>()
             .register_type::<skinning::SkinnedMesh>()
             .add_plugin(RenderAssetPlugin::<Mesh>::default());
@@ -558,6 +558,7 @@ impl PipelineCache {
                     }
                 }
                 AssetEvent::Removed { handle } => cache.remove_shader(handle),
+                AssetEvent::LoadedWithDependencies { .. } => {}
             }
         }
     }
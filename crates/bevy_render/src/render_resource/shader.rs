@@ -1,4 +1,6 @@
-use bevy_asset::{AssetLoader, AssetPath, Handle, LoadContext, LoadedAsset};
+use bevy_asset::{
+    AssetLoader, AssetPath, Handle, LoadContext, LoadedAsset, ASSET_SOURCE_SEPARATOR,
+};
 use bevy_reflect::{TypeUuid, Uuid};
 use bevy_utils::{tracing::error, BoxedFuture, HashMap};
 use naga::back::wgsl::WriterFlags;
@@ -6,9 +8,7 @@ use naga::valid::Capabilities;
 use naga::{valid::ModuleInfo, Module};
 use once_cell::sync::Lazy;
 use regex::Regex;
-use std::{
-    borrow::Cow, collections::HashSet, marker::Copy, ops::Deref, path::PathBuf, str::FromStr,
-};
+use std::{borrow::Cow, collections::HashSet, marker::Copy, ops::Deref};
 use thiserror::Error;
 use wgpu::Features;
 use wgpu::{util::make_spirv, ShaderModuleDescriptor, ShaderSource};
@@ -42,6 +42,8 @@ pub struct Shader {
     source: Source,
     import_path: Option<ShaderImport>,
     imports: Vec<ShaderImport>,
+    /// The asset source the shader was loaded from, which its asset path imports are relative to.
+    asset_source: Option<String>,
 }
 
 impl Shader {
@@ -52,6 +54,7 @@ impl Shader {
             imports: shader_imports.imports,
             import_path: shader_imports.import_path,
             source: Source::Wgsl(source),
+            asset_source: None,
         }
     }
 
@@ -62,6 +65,7 @@ impl Shader {
             imports: shader_imports.imports,
             import_path: shader_imports.import_path,
             source: Source::Glsl(source, stage),
+            asset_source: None,
         }
     }
 
@@ -70,6 +74,7 @@ impl Shader {
             imports: Vec::new(),
             import_path: None,
             source: Source::SpirV(source.into()),
+            asset_source: None,
         }
     }
 
@@ -91,6 +96,32 @@ impl Shader {
     pub fn imports(&self) -> impl ExactSizeIterator<Item = &ShaderImport> {
         self.imports.iter()
     }
+
+    /// Sets the asset source the shader was loaded from.
+    ///
+    /// Like the dependencies of other assets, `#import "path"` lines without a `source://` prefix
+    /// then refer to a shader of this source.
+    fn set_asset_source(&mut self, source: Option<&str>) {
+        self.asset_source = source.map(ToString::to_string);
+        let imports = std::mem::take(&mut self.imports);
+        self.imports = imports
+            .into_iter()
+            .map(|import| match import {
+                ShaderImport::AssetPath(path) => self.resolve_asset_import(&path),
+                import => import,
+            })
+            .collect();
+    }
+
+    /// Returns the import of the asset at `path`, relative to the asset source of the shader.
+    fn resolve_asset_import(&self, path: &str) -> ShaderImport {
+        match &self.asset_source {
+            Some(source) if AssetPath::from(path).source().is_none() => {
+                ShaderImport::AssetPath(format!("{}{}{}", source, ASSET_SOURCE_SEPARATOR, path))
+            }
+            _ => ShaderImport::AssetPath(path.to_string()),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -257,21 +288,27 @@ impl AssetLoader for ShaderLoader {
                 _ => panic!("unhandled extension: {}", ext),
             };
 
+            shader.set_asset_source(load_context.source());
             let shader_imports = SHADER_IMPORT_PROCESSOR.get_imports(&shader);
             if shader_imports.import_path.is_some() {
                 shader.import_path = shader_imports.import_path;
             } else {
-                shader.import_path = Some(ShaderImport::AssetPath(
-                    load_context.path().to_string_lossy().to_string(),
-                ));
-            }
-            let mut asset = LoadedAsset::new(shader);
-            for import in shader_imports.imports {
-                if let ShaderImport::AssetPath(asset_path) = import {
-                    let path = PathBuf::from_str(&asset_path)?;
-                    asset.add_dependency(path.into());
-                }
+                let path = load_context.path().to_string_lossy();
+                shader.import_path = Some(ShaderImport::AssetPath(match load_context.source() {
+                    Some(source) => format!("{}{}{}", source, ASSET_SOURCE_SEPARATOR, path),
+                    None => path.to_string(),
+                }));
             }
+            let dependencies: Vec<AssetPath<'static>> = shader
+                .imports()
+                .filter_map(|import| match import {
+                    ShaderImport::AssetPath(asset_path) => {
+                        Some(AssetPath::from(asset_path.as_str()).to_owned())
+                    }
+                    ShaderImport::Custom(_) => None,
+                })
+                .collect();
+            let asset = LoadedAsset::new(shader).with_dependencies(dependencies);
 
             load_context.set_default_asset(asset);
             Ok(())
@@ -429,7 +466,7 @@ impl ShaderProcessor {
                     .import_asset_path_regex
                     .captures(line)
                 {
-                    let import = ShaderImport::AssetPath(cap.get(1).unwrap().as_str().to_string());
+                    let import = shader.resolve_asset_import(cap.get(1).unwrap().as_str());
                     self.apply_import(
                         import_handles,
                         shaders,
@@ -892,6 +929,51 @@ fn bar() { }
         assert_eq!(result.get_wgsl_source().unwrap(), EXPECTED);
     }
 
+    #[test]
+    fn process_import_from_asset_source() {
+        #[rustfmt::skip]
+        const FOO: &str = r"
+fn foo() { }
+";
+        #[rustfmt::skip]
+        const EXPECTED: &str = r"
+
+fn foo() { }
+fn bar() { }
+";
+        let processor = ShaderProcessor::default();
+        let mut shaders = HashMap::default();
+        let mut import_handles = HashMap::default();
+        let foo_handle = Handle::<Shader>::default();
+        shaders.insert(foo_handle.clone_weak(), Shader::from_wgsl(FOO));
+        // the import path given to `embedded://shaders/foo.wgsl` when loaded
+        import_handles.insert(
+            ShaderImport::AssetPath("embedded://shaders/foo.wgsl".to_string()),
+            foo_handle.clone_weak(),
+        );
+
+        // imported from the default source with its source
+        let shader = Shader::from_wgsl("\n#import \"embedded://shaders/foo.wgsl\"\nfn bar() { }\n");
+        let result = processor
+            .process(&shader, &[], &shaders, &import_handles)
+            .unwrap();
+        assert_eq!(result.get_wgsl_source().unwrap(), EXPECTED);
+
+        // imported from the same source without it
+        let mut shader = Shader::from_wgsl("\n#import \"shaders/foo.wgsl\"\nfn bar() { }\n");
+        shader.set_asset_source(Some("embedded"));
+        assert_eq!(
+            shader.imports().collect::<Vec<_>>(),
+            vec![&ShaderImport::AssetPath(
+                "embedded://shaders/foo.wgsl".to_string()
+            )]
+        );
+        let result = processor
+            .process(&shader, &[], &shaders, &import_handles)
+            .unwrap();
+        assert_eq!(result.get_wgsl_source().unwrap(), EXPECTED);
+    }
+
     #[test]
     fn process_import_glsl() {
         #[rustfmt::skip]
@@ -2,11 +2,12 @@ use anyhow::Result;
 use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
 use bevy_ecs::prelude::{FromWorld, World};
 use bevy_utils::BoxedFuture;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::{
     renderer::RenderDevice,
-    texture::{Image, ImageType, TextureError},
+    texture::{Image, ImageSampler, ImageType, TextureError},
 };
 
 use super::CompressedImageFormats;
@@ -17,6 +18,63 @@ pub struct ImageTextureLoader {
     supported_compressed_formats: CompressedImageFormats,
 }
 
+/// Settings of the [`ImageTextureLoader`], passed with
+/// [`AssetServer::load_with_settings`](bevy_asset::AssetServer::load_with_settings).
+///
+/// ```no_run
+/// # use bevy_asset::{AssetServer, Handle};
+/// # use bevy_render::texture::{Image, ImageLoaderSampler, ImageLoaderSettings};
+/// # fn load(asset_server: &AssetServer) {
+/// let normal_map: Handle<Image> = asset_server
+///     .load_with_settings::<Image, ImageLoaderSettings>("normal.png", |settings| {
+///         settings.is_srgb = false;
+///         settings.sampler = ImageLoaderSampler::Nearest;
+///     });
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ImageLoaderSettings {
+    /// Whether the image holds sRGB colors, rather than linear data such as a normal map.
+    pub is_srgb: bool,
+    /// The sampler to use for the image.
+    pub sampler: ImageLoaderSampler,
+}
+
+impl Default for ImageLoaderSettings {
+    fn default() -> Self {
+        Self {
+            is_srgb: true,
+            sampler: ImageLoaderSampler::Default,
+        }
+    }
+}
+
+/// The sampler of an image loaded by the [`ImageTextureLoader`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ImageLoaderSampler {
+    /// The default sampler, from the [`ImageSettings`](super::ImageSettings) resource.
+    #[default]
+    Default,
+    /// A sampler with `Linear` min and mag filters.
+    Linear,
+    /// A sampler with `Nearest` min and mag filters.
+    Nearest,
+}
+
+impl From<ImageLoaderSampler> for ImageSampler {
+    fn from(sampler: ImageLoaderSampler) -> Self {
+        match sampler {
+            ImageLoaderSampler::Default => ImageSampler::Default,
+            ImageLoaderSampler::Linear => {
+                ImageSampler::Descriptor(ImageSampler::linear_descriptor())
+            }
+            ImageLoaderSampler::Nearest => {
+                ImageSampler::Descriptor(ImageSampler::nearest_descriptor())
+            }
+        }
+    }
+}
+
 const FILE_EXTENSIONS: &[&str] = &[
     #[cfg(feature = "basis-universal")]
     "basis",
@@ -45,17 +103,19 @@ impl AssetLoader for ImageTextureLoader {
         Box::pin(async move {
             // use the file extension for the image type
             let ext = load_context.path().extension().unwrap().to_str().unwrap();
+            let settings = load_context.settings::<ImageLoaderSettings>()?;
 
-            let dyn_img = Image::from_buffer(
+            let mut dyn_img = Image::from_buffer(
                 bytes,
                 ImageType::Extension(ext),
                 self.supported_compressed_formats,
-                true,
+                settings.is_srgb,
             )
             .map_err(|err| FileTextureError {
                 error: err,
                 path: format!("{}", load_context.path().display()),
             })?;
+            dyn_img.sampler_descriptor = settings.sampler.into();
 
             load_context.set_default_asset(LoadedAsset::new(dyn_img));
             Ok(())
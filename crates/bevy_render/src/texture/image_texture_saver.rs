@@ -0,0 +1,82 @@
+use anyhow::Result;
+use bevy_asset::AssetSaver;
+use std::io::Cursor;
+use thiserror::Error;
+
+use crate::texture::{image_texture_conversion::texture_to_image, Image};
+
+/// Saves [`Image`]s as PNG files.
+///
+/// Only 8-bit images with one, two or four channels can be saved.
+#[derive(Clone, Default)]
+pub struct ImageTextureSaver;
+
+/// An error when saving an [`Image`].
+#[derive(Error, Debug)]
+pub enum ImageSaverError {
+    /// The format of the image can not be saved.
+    #[error("cannot save images of format {0:?}")]
+    UnsupportedFormat(wgpu::TextureFormat),
+}
+
+impl AssetSaver for ImageTextureSaver {
+    type Asset = Image;
+
+    fn save(&self, image: &Image) -> Result<Vec<u8>> {
+        let dynamic_image = texture_to_image(image).ok_or(ImageSaverError::UnsupportedFormat(
+            image.texture_descriptor.format,
+        ))?;
+        let mut bytes = Cursor::new(Vec::new());
+        dynamic_image.write_to(&mut bytes, image::ImageOutputFormat::Png)?;
+        Ok(bytes.into_inner())
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["png"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::texture::{CompressedImageFormats, ImageType};
+    use wgpu::{Extent3d, TextureDimension, TextureFormat};
+
+    fn image(format: TextureFormat, pixel: &[u8]) -> Image {
+        let size = Extent3d {
+            width: 2,
+            height: 3,
+            depth_or_array_layers: 1,
+        };
+        Image::new_fill(size, TextureDimension::D2, pixel, format)
+    }
+
+    #[test]
+    fn save_png() {
+        let saved = image(TextureFormat::Rgba8UnormSrgb, &[10, 20, 30, 255]);
+        let bytes = ImageTextureSaver.save(&saved).unwrap();
+
+        let loaded = Image::from_buffer(
+            &bytes,
+            ImageType::Extension("png"),
+            CompressedImageFormats::NONE,
+            true,
+        )
+        .unwrap();
+        assert_eq!(
+            loaded.texture_descriptor.size,
+            saved.texture_descriptor.size
+        );
+        assert_eq!(loaded.data, saved.data);
+    }
+
+    #[test]
+    fn save_unsupported_format() {
+        let saved = image(TextureFormat::R32Float, &[0; 4]);
+        let err = ImageTextureSaver.save(&saved).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<ImageSaverError>(),
+            Some(ImageSaverError::UnsupportedFormat(TextureFormat::R32Float))
+        ));
+    }
+}
@@ -8,6 +8,8 @@ mod hdr_texture_loader;
 #[allow(clippy::module_inception)]
 mod image;
 mod image_texture_loader;
+#[cfg(feature = "png")]
+mod image_texture_saver;
 #[cfg(feature = "ktx2")]
 mod ktx2;
 mod texture_cache;
@@ -24,6 +26,8 @@ pub use hdr_texture_loader::*;
 
 pub use fallback_image::*;
 pub use image_texture_loader::*;
+#[cfg(feature = "png")]
+pub use image_texture_saver::*;
 pub use texture_cache::*;
 
 use crate::{
@@ -53,6 +57,11 @@ impl Plugin for ImagePlugin {
             app.init_asset_loader::<ImageTextureLoader>();
         }
 
+        #[cfg(feature = "png")]
+        {
+            app.add_asset_saver(ImageTextureSaver);
+        }
+
         #[cfg(feature = "hdr")]
         {
             app.init_asset_loader::<HdrTextureLoader>();
@@ -61,7 +70,8 @@ impl Plugin for ImagePlugin {
         app.add_plugin(RenderAssetPlugin::<Image>::with_prepare_asset_label(
             PrepareAssetLabel::PreAssetPrepare,
         ))
-        .add_asset::<Image>();
+        .add_asset::<Image>()
+        .register_asset_size_hint::<Image>(|image| image.data.len());
         app.world
             .resource_mut::<Assets<Image>>()
             .set_untracked(DEFAULT_IMAGE_HANDLE, Image::default());
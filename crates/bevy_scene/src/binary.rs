@@ -0,0 +1,537 @@
+//! A compact binary format for [`DynamicScene`]s, the `.scn.bin` files.
+//!
+//! Binary scenes have the same structure as RON scenes, but the type and field names are stored
+//! once in a string table, and values are encoded with [`bincode`] through their reflected
+//! [`Serialize`](serde::Serialize) and [`Deserialize`](serde::Deserialize) implementations. Like
+//! RON scenes, every type of a binary scene must be registered in the [`TypeRegistry`].
+//!
+//! A binary scene starts with [`MAGIC`], followed by the little-endian `u32` version of the
+//! format, so that scenes saved by older versions of Bevy can still be read. Like RON scenes,
+//! binary scenes record the [version](bevy_reflect::TypeRegistration::version) of their resource
+//! and component types, and are migrated to the current version of the types when read.
+
+use crate::{
+    migration::{migrate_scene, scene_versions},
+    DynamicEntity, DynamicScene, SceneMigrationError,
+};
+use bevy_reflect::{
+    DynamicArray, DynamicList, DynamicMap, DynamicStruct, DynamicTuple, DynamicTupleStruct, Map,
+    Reflect, ReflectDeserialize, ReflectRef, ReflectSerialize, TypeRegistry,
+};
+use bevy_utils::HashMap;
+use bincode::Options;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// The bytes binary scenes start with.
+pub const MAGIC: [u8; 4] = *b"BSCN";
+
+/// The version of the binary scene format written by [`serialize`].
+pub const VERSION: u32 = 2;
+
+/// An error reading or writing a binary scene.
+#[derive(Error, Debug)]
+pub enum BinarySceneError {
+    #[error("not a binary scene")]
+    InvalidHeader,
+    #[error(
+        "binary scene version {version} is not supported, the latest supported version is {}",
+        VERSION
+    )]
+    UnsupportedVersion { version: u32 },
+    #[error("type `{type_name}` is not registered")]
+    UnregisteredType { type_name: String },
+    #[error("type `{type_name}` did not register ReflectSerialize")]
+    UnregisteredSerialize { type_name: String },
+    #[error("type `{type_name}` did not register ReflectDeserialize")]
+    UnregisteredDeserialize { type_name: String },
+    #[error("string table index {index} is out of bounds")]
+    InvalidStringIndex { index: u32 },
+    #[error(transparent)]
+    Migration(#[from] SceneMigrationError),
+    #[error(transparent)]
+    Bincode(#[from] bincode::Error),
+}
+
+/// Serializes `scene` into a binary scene.
+pub fn serialize(
+    scene: &DynamicScene,
+    type_registry: &TypeRegistry,
+) -> Result<Vec<u8>, BinarySceneError> {
+    let mut encoder = Encoder {
+        type_registry,
+        strings: StringTable::default(),
+    };
+    let resources = scene
+        .resources
+        .iter()
+        .map(|resource| encoder.encode(&**resource))
+        .collect::<Result<_, _>>()?;
+    let entities = scene
+        .entities
+        .iter()
+        .map(|entity| {
+            Ok(BinaryEntity {
+                entity: entity.entity,
+                components: entity
+                    .components
+                    .iter()
+                    .map(|component| encoder.encode(&**component))
+                    .collect::<Result<_, BinarySceneError>>()?,
+            })
+        })
+        .collect::<Result<_, BinarySceneError>>()?;
+    let versions = scene_versions(scene, type_registry)
+        .into_iter()
+        .map(|(type_name, version)| (encoder.strings.index(&type_name), version))
+        .collect();
+    let binary_scene = BinaryScene {
+        strings: encoder.strings.strings,
+        resources,
+        entities,
+        versions,
+    };
+
+    let mut bytes = MAGIC.to_vec();
+    bytes.extend_from_slice(&VERSION.to_le_bytes());
+    options().serialize_into(&mut bytes, &binary_scene)?;
+    Ok(bytes)
+}
+
+/// Deserializes a binary scene.
+pub fn deserialize(
+    bytes: &[u8],
+    type_registry: &TypeRegistry,
+) -> Result<DynamicScene, BinarySceneError> {
+    if bytes.len() < 8 || bytes[..4] != MAGIC {
+        return Err(BinarySceneError::InvalidHeader);
+    }
+    let version = u32::from_le_bytes(bytes[4..8].try_into().unwrap());
+    let binary_scene: BinaryScene = match version {
+        1 => options().deserialize::<BinarySceneV1>(&bytes[8..])?.into(),
+        VERSION => options().deserialize(&bytes[8..])?,
+        _ => return Err(BinarySceneError::UnsupportedVersion { version }),
+    };
+
+    let decoder = Decoder {
+        type_registry,
+        strings: &binary_scene.strings,
+    };
+    let mut scene = DynamicScene {
+        resources: binary_scene
+            .resources
+            .iter()
+            .map(|resource| decoder.decode(resource))
+            .collect::<Result<_, _>>()?,
+        entities: binary_scene
+            .entities
+            .iter()
+            .map(|entity| {
+                Ok(DynamicEntity {
+                    entity: entity.entity,
+                    components: entity
+                        .components
+                        .iter()
+                        .map(|component| decoder.decode(component))
+                        .collect::<Result<_, BinarySceneError>>()?,
+                })
+            })
+            .collect::<Result<_, BinarySceneError>>()?,
+    };
+    let versions = binary_scene
+        .versions
+        .iter()
+        .map(|(type_name, version)| Ok((decoder.string(*type_name)?.to_string(), *version)))
+        .collect::<Result<HashMap<_, _>, BinarySceneError>>()?;
+    migrate_scene(&mut scene, &versions, type_registry)?;
+    Ok(scene)
+}
+
+fn options() -> impl Options {
+    bincode::DefaultOptions::new()
+}
+
+#[derive(Serialize, Deserialize)]
+struct BinaryScene {
+    strings: Vec<String>,
+    resources: Vec<BinaryValue>,
+    entities: Vec<BinaryEntity>,
+    /// The versions of the resource and component types whose version is not `0`, their type
+    /// name being an index in the string table.
+    versions: Vec<(u32, u32)>,
+}
+
+/// Version 1 of the format, which did not record the versions of types.
+#[derive(Deserialize)]
+struct BinarySceneV1 {
+    strings: Vec<String>,
+    resources: Vec<BinaryValue>,
+    entities: Vec<BinaryEntity>,
+}
+
+impl From<BinarySceneV1> for BinaryScene {
+    fn from(scene: BinarySceneV1) -> Self {
+        BinaryScene {
+            strings: scene.strings,
+            resources: scene.resources,
+            entities: scene.entities,
+            versions: Vec::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct BinaryEntity {
+    entity: u32,
+    components: Vec<BinaryValue>,
+}
+
+/// A reflected value, whose type and field names are indices in the string table.
+#[derive(Serialize, Deserialize)]
+enum BinaryValue {
+    Struct {
+        type_name: u32,
+        fields: Vec<(u32, BinaryValue)>,
+    },
+    TupleStruct {
+        type_name: u32,
+        fields: Vec<BinaryValue>,
+    },
+    Tuple {
+        type_name: u32,
+        fields: Vec<BinaryValue>,
+    },
+    List {
+        type_name: u32,
+        items: Vec<BinaryValue>,
+    },
+    Array {
+        type_name: u32,
+        items: Vec<BinaryValue>,
+    },
+    Map {
+        type_name: u32,
+        entries: Vec<(BinaryValue, BinaryValue)>,
+    },
+    Value {
+        type_name: u32,
+        bytes: Vec<u8>,
+    },
+}
+
+#[derive(Default)]
+struct StringTable {
+    strings: Vec<String>,
+    indices: HashMap<String, u32>,
+}
+
+impl StringTable {
+    fn index(&mut self, string: &str) -> u32 {
+        if let Some(index) = self.indices.get(string) {
+            return *index;
+        }
+        let index = self.strings.len() as u32;
+        self.strings.push(string.to_string());
+        self.indices.insert(string.to_string(), index);
+        index
+    }
+}
+
+struct Encoder<'a> {
+    type_registry: &'a TypeRegistry,
+    strings: StringTable,
+}
+
+impl<'a> Encoder<'a> {
+    fn encode(&mut self, value: &dyn Reflect) -> Result<BinaryValue, BinarySceneError> {
+        let type_name = self.strings.index(value.type_name());
+        Ok(match value.reflect_ref() {
+            ReflectRef::Struct(value) => BinaryValue::Struct {
+                type_name,
+                fields: (0..value.field_len())
+                    .map(|index| {
+                        let name = self.strings.index(value.name_at(index).unwrap());
+                        Ok((name, self.encode(value.field_at(index).unwrap())?))
+                    })
+                    .collect::<Result<_, BinarySceneError>>()?,
+            },
+            ReflectRef::TupleStruct(value) => BinaryValue::TupleStruct {
+                type_name,
+                fields: value
+                    .iter_fields()
+                    .map(|field| self.encode(field))
+                    .collect::<Result<_, _>>()?,
+            },
+            ReflectRef::Tuple(value) => BinaryValue::Tuple {
+                type_name,
+                fields: value
+                    .iter_fields()
+                    .map(|field| self.encode(field))
+                    .collect::<Result<_, _>>()?,
+            },
+            ReflectRef::List(value) => BinaryValue::List {
+                type_name,
+                items: value
+                    .iter()
+                    .map(|item| self.encode(item))
+                    .collect::<Result<_, _>>()?,
+            },
+            ReflectRef::Array(value) => BinaryValue::Array {
+                type_name,
+                items: value
+                    .iter()
+                    .map(|item| self.encode(item))
+                    .collect::<Result<_, _>>()?,
+            },
+            ReflectRef::Map(value) => BinaryValue::Map {
+                type_name,
+                entries: value
+                    .iter()
+                    .map(|(key, value)| Ok((self.encode(key)?, self.encode(value)?)))
+                    .collect::<Result<_, BinarySceneError>>()?,
+            },
+            ReflectRef::Value(value) => {
+                let reflect_serialize = self
+                    .type_registry
+                    .get_type_data::<ReflectSerialize>(value.type_id())
+                    .ok_or_else(|| BinarySceneError::UnregisteredSerialize {
+                        type_name: value.type_name().to_string(),
+                    })?;
+                BinaryValue::Value {
+                    type_name,
+                    bytes: options()
+                        .serialize(reflect_serialize.get_serializable(value).borrow())?,
+                }
+            }
+        })
+    }
+}
+
+struct Decoder<'a> {
+    type_registry: &'a TypeRegistry,
+    strings: &'a [String],
+}
+
+impl<'a> Decoder<'a> {
+    fn string(&self, index: u32) -> Result<&'a str, BinarySceneError> {
+        self.strings
+            .get(index as usize)
+            .map(String::as_str)
+            .ok_or(BinarySceneError::InvalidStringIndex { index })
+    }
+
+    fn decode(&self, value: &BinaryValue) -> Result<Box<dyn Reflect>, BinarySceneError> {
+        Ok(match value {
+            BinaryValue::Struct { type_name, fields } => {
+                let mut dynamic_struct = DynamicStruct::default();
+                dynamic_struct.set_name(self.string(*type_name)?.to_string());
+                for (name, field) in fields {
+                    dynamic_struct.insert_boxed(self.string(*name)?, self.decode(field)?);
+                }
+                Box::new(dynamic_struct)
+            }
+            BinaryValue::TupleStruct { type_name, fields } => {
+                let mut tuple_struct = DynamicTupleStruct::default();
+                tuple_struct.set_name(self.string(*type_name)?.to_string());
+                for field in fields {
+                    tuple_struct.insert_boxed(self.decode(field)?);
+                }
+                Box::new(tuple_struct)
+            }
+            BinaryValue::Tuple { type_name, fields } => {
+                let mut tuple = DynamicTuple::default();
+                tuple.set_name(self.string(*type_name)?.to_string());
+                for field in fields {
+                    tuple.insert_boxed(self.decode(field)?);
+                }
+                Box::new(tuple)
+            }
+            BinaryValue::List { type_name, items } => {
+                let mut list = DynamicList::default();
+                list.set_name(self.string(*type_name)?.to_string());
+                for item in items {
+                    list.push_box(self.decode(item)?);
+                }
+                Box::new(list)
+            }
+            BinaryValue::Array { type_name, items } => {
+                let mut array = DynamicArray::new(
+                    items
+                        .iter()
+                        .map(|item| self.decode(item))
+                        .collect::<Result<_, _>>()?,
+                );
+                array.set_name(self.string(*type_name)?.to_string());
+                Box::new(array)
+            }
+            BinaryValue::Map { type_name, entries } => {
+                let mut map = DynamicMap::default();
+                map.set_name(self.string(*type_name)?.to_string());
+                for (key, value) in entries {
+                    map.insert_boxed(self.decode(key)?, self.decode(value)?);
+                }
+                Box::new(map)
+            }
+            BinaryValue::Value { type_name, bytes } => {
+                let type_name = self.string(*type_name)?;
+                let registration =
+                    self.type_registry.get_with_name(type_name).ok_or_else(|| {
+                        BinarySceneError::UnregisteredType {
+                            type_name: type_name.to_string(),
+                        }
+                    })?;
+                let reflect_deserialize =
+                    registration.data::<ReflectDeserialize>().ok_or_else(|| {
+                        BinarySceneError::UnregisteredDeserialize {
+                            type_name: type_name.to_string(),
+                        }
+                    })?;
+                let mut deserializer = bincode::Deserializer::from_slice(bytes, options());
+                reflect_deserialize.deserialize(&mut deserializer)?
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{entity::EntityMap, prelude::*, reflect::ReflectResource};
+    use bevy_reflect::{TupleStruct, TypeRegistryArc};
+    use std::any::TypeId;
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Inventory {
+        name: String,
+        items: Vec<u32>,
+        position: [f32; 3],
+        pair: (u8, String),
+        counts: HashMap<String, u32>,
+    }
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Health(u32);
+
+    #[derive(Reflect, Default, Debug, PartialEq)]
+    #[reflect(Resource)]
+    struct Score(u32);
+
+    fn inventory() -> Inventory {
+        Inventory {
+            name: "backpack".to_string(),
+            items: vec![1, 2, 3],
+            position: [1.0, 2.0, 3.0],
+            pair: (4, "four".to_string()),
+            counts: [("arrows".to_string(), 12)].into_iter().collect(),
+        }
+    }
+
+    fn scene(registry: &TypeRegistryArc) -> DynamicScene {
+        let mut world = World::new();
+        world.insert_resource(Score(42));
+        world.spawn().insert(inventory()).insert(Health(7));
+        for health in 0..10 {
+            world.spawn().insert(Health(health));
+        }
+        DynamicScene::from_world_with_resource_filter(
+            &world,
+            registry,
+            &crate::SceneFilter::deny_all().allow::<Score>(),
+        )
+    }
+
+    #[test]
+    fn round_trip() {
+        let registry = type_registry![Inventory, Health, Score, String, f32];
+        let scene = scene(&registry);
+        let ron = scene.serialize_ron(&registry).unwrap();
+        let bytes = scene.serialize_binary(&registry).unwrap();
+        assert!(bytes.len() < ron.len() / 4);
+
+        let scene = deserialize(&bytes, &registry.read()).unwrap();
+        assert_eq!(scene.serialize_ron(&registry).unwrap(), ron);
+
+        let mut world = World::new();
+        world.insert_resource(registry);
+        scene
+            .write_to_world(&mut world, &mut EntityMap::default())
+            .unwrap();
+        assert_eq!(world.resource::<Score>(), &Score(42));
+        let mut query = world.query::<&Inventory>();
+        assert_eq!(query.single(&world), &inventory());
+        let mut query = world.query::<&Health>();
+        assert_eq!(query.iter(&world).count(), 11);
+    }
+
+    #[test]
+    fn header() {
+        let registry = type_registry![Inventory, Health, Score, String, f32];
+        let mut bytes = scene(&registry).serialize_binary(&registry).unwrap();
+        assert!(matches!(
+            deserialize(b"(entities: [])", &registry.read()),
+            Err(BinarySceneError::InvalidHeader)
+        ));
+
+        // version 1 scenes are version 2 scenes without the trailing versions table, which is
+        // empty here
+        let mut v1_bytes = bytes.clone();
+        assert_eq!(v1_bytes.pop(), Some(0));
+        v1_bytes[4..8].copy_from_slice(&1u32.to_le_bytes());
+        assert!(deserialize(&v1_bytes, &registry.read()).is_ok());
+
+        bytes[4..8].copy_from_slice(&(VERSION + 1).to_le_bytes());
+        assert!(matches!(
+            deserialize(&bytes, &registry.read()),
+            Err(BinarySceneError::UnsupportedVersion { version }) if version == VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn migrations() {
+        let registry = type_registry![Inventory, Health, Score, String, f32];
+        let bytes = scene(&registry).serialize_binary(&registry).unwrap();
+
+        // health used to be stored halved
+        {
+            let mut registry = registry.write();
+            let registration = registry.get_mut(TypeId::of::<Health>()).unwrap();
+            registration.set_version(1);
+            registration.add_migration(0, |mut value| {
+                let health = value.downcast_mut::<DynamicTupleStruct>().unwrap();
+                let halved = *health.field(0).unwrap().downcast_ref::<u32>().unwrap();
+                *health.field_mut(0).unwrap().downcast_mut::<u32>().unwrap() = halved * 2;
+                value
+            });
+        }
+        let scene = deserialize(&bytes, &registry.read()).unwrap();
+        let mut world = World::new();
+        world.insert_resource(registry.clone());
+        scene
+            .write_to_world(&mut world, &mut EntityMap::default())
+            .unwrap();
+        let mut query = world.query_filtered::<&Health, Without<Inventory>>();
+        let mut healths: Vec<u32> = query.iter(&world).map(|health| health.0).collect();
+        healths.sort_unstable();
+        assert_eq!(
+            healths,
+            (0..10).map(|health| health * 2).collect::<Vec<_>>()
+        );
+
+        // scenes record the version of their types, and newer versions are rejected
+        let bytes = scene.serialize_binary(&registry).unwrap();
+        assert!(deserialize(&bytes, &registry.read()).is_ok());
+        registry
+            .write()
+            .get_mut(TypeId::of::<Health>())
+            .unwrap()
+            .set_version(0);
+        assert!(matches!(
+            deserialize(&bytes, &registry.read()),
+            Err(BinarySceneError::Migration(
+                SceneMigrationError::NewerVersion { version: 1, .. }
+            ))
+        ));
+    }
+}
@@ -1,14 +1,22 @@
-use crate::{serde::SceneSerializer, Scene, SceneSpawnError};
+use crate::{
+    binary::{self, BinarySceneError},
+    serde::SceneSerializer,
+    DynamicSceneBuilder, Scene, SceneFilter, SceneSpawnError,
+};
 use anyhow::Result;
 use bevy_ecs::{
-    entity::EntityMap,
-    reflect::{ReflectComponent, ReflectMapEntities},
+    entity::{Entity, EntityMap},
+    reflect::{ReflectComponent, ReflectMapEntities, ReflectResource},
     world::World,
 };
+use bevy_hierarchy::{BuildWorldChildren, Parent};
 use bevy_reflect::{Reflect, TypeRegistryArc, TypeUuid};
+use bevy_utils::HashMap;
 use serde::Serialize;
+use std::any::TypeId;
 
-/// A collection of serializable dynamic entities, each with its own run-time defined set of components.
+/// A collection of serializable resources and dynamic entities, each with its own run-time defined
+/// set of components.
 /// To spawn a dynamic scene, you can use either:
 /// * [`SceneSpawner::spawn_dynamic`](crate::SceneSpawner::spawn_dynamic)
 /// * adding the [`DynamicSceneBundle`](crate::DynamicSceneBundle) to an entity
@@ -18,6 +26,8 @@ use serde::Serialize;
 #[derive(Default, TypeUuid)]
 #[uuid = "749479b1-fb8c-4ff8-a775-623aa76014f5"]
 pub struct DynamicScene {
+    /// Boxed resources that implement the `Reflect` trait and reflect `Resource`.
+    pub resources: Vec<Box<dyn Reflect>>,
     pub entities: Vec<DynamicEntity>,
 }
 
@@ -37,66 +47,147 @@ impl DynamicScene {
     }
 
     /// Create a new dynamic scene from a given world.
+    ///
+    /// No resources are extracted, as an app world has many resources, like `Time`, that spawning
+    /// the scene should not overwrite. Use [`DynamicScene::from_world_with_resource_filter`] to
+    /// choose the resources to extract.
     pub fn from_world(world: &World, type_registry: &TypeRegistryArc) -> Self {
-        let mut scene = DynamicScene::default();
-        let type_registry = type_registry.read();
-
-        for archetype in world.archetypes().iter() {
-            let entities_offset = scene.entities.len();
-
-            // Create a new dynamic entity for each entity of the given archetype
-            // and insert it into the dynamic scene.
-            for entity in archetype.entities() {
-                scene.entities.push(DynamicEntity {
-                    entity: entity.id(),
-                    components: Vec::new(),
-                });
-            }
-
-            // Add each reflection-powered component to the entity it belongs to.
-            for component_id in archetype.components() {
-                let reflect_component = world
-                    .components()
-                    .get_info(component_id)
-                    .and_then(|info| type_registry.get(info.type_id().unwrap()))
-                    .and_then(|registration| registration.data::<ReflectComponent>());
-                if let Some(reflect_component) = reflect_component {
-                    for (i, entity) in archetype.entities().iter().enumerate() {
-                        if let Some(component) = reflect_component.reflect(world, *entity) {
-                            scene.entities[entities_offset + i]
-                                .components
-                                .push(component.clone_value());
-                        }
-                    }
-                }
-            }
-        }
+        Self::from_world_with_resource_filter(world, type_registry, &SceneFilter::deny_all())
+    }
 
-        scene
+    /// Create a new dynamic scene from a given world, including only the reflected resources
+    /// allowed by `resource_filter`.
+    ///
+    /// Resources are captured if their type is registered with `#[reflect(Resource)]`. To choose
+    /// the extracted entities and components too, use a [`DynamicSceneBuilder`].
+    pub fn from_world_with_resource_filter(
+        world: &World,
+        type_registry: &TypeRegistryArc,
+        resource_filter: &SceneFilter,
+    ) -> Self {
+        DynamicSceneBuilder::from_world_with_type_registry(world, type_registry.clone())
+            .with_resource_filter(resource_filter.clone())
+            .extract_all_entities()
+            .extract_resources()
+            .build()
     }
 
-    /// Write the dynamic entities and their corresponding components to the given world.
+    /// Write the resources, the dynamic entities and their corresponding components to the given
+    /// world.
+    ///
+    /// Entities referenced by the written components are mapped with the `entity_map`, using
+    /// [`ReflectMapEntities`]. Only the written components are mapped, so the scene can be
+    /// written again with the same `entity_map`. References to entities that are not part of the
+    /// scene are kept as is when the component supports it, as [`Parent`](bevy_hierarchy::Parent)
+    /// does.
     ///
     /// This method will return a `SceneSpawnError` if either a type is not registered
-    /// or doesn't reflect the `Component` trait.
+    /// or doesn't reflect the `Component` or `Resource` trait.
     pub fn write_to_world(
         &self,
         world: &mut World,
         entity_map: &mut EntityMap,
+    ) -> Result<(), SceneSpawnError> {
+        self.write_changes_to_world(&DynamicScene::default(), world, entity_map)
+    }
+
+    /// Write the changes from the `previous` version of this scene to the given world, where the
+    /// previous version was written with the same `entity_map`.
+    ///
+    /// Entities are matched by their scene entity id, and components by their type:
+    /// * entities removed from the scene are despawned, and added entities are spawned,
+    /// * components removed from an entity are removed, and added components are inserted,
+    /// * components whose value changed are applied, while unchanged components keep their
+    ///   current value in the world.
+    ///
+    /// Components that the previous version of an entity did not have are left alone, as they
+    /// were not written by the scene. Likewise, resources are only written if they changed.
+    pub fn write_changes_to_world(
+        &self,
+        previous: &DynamicScene,
+        world: &mut World,
+        entity_map: &mut EntityMap,
     ) -> Result<(), SceneSpawnError> {
         let registry = world.resource::<TypeRegistryArc>().clone();
         let type_registry = registry.read();
 
+        for resource in &self.resources {
+            if is_unchanged(find_value(&previous.resources, &**resource), &**resource) {
+                continue;
+            }
+            let registration = type_registry
+                .get_with_name(resource.type_name())
+                .ok_or_else(|| SceneSpawnError::UnregisteredType {
+                    type_name: resource.type_name().to_string(),
+                })?;
+            let reflect_resource = registration.data::<ReflectResource>().ok_or_else(|| {
+                SceneSpawnError::UnregisteredResource {
+                    type_name: resource.type_name().to_string(),
+                }
+            })?;
+
+            // If the world already has the given resource, just apply the (possibly) new value,
+            // otherwise insert the resource.
+            reflect_resource.apply_or_insert(world, &**resource);
+        }
+
+        // Despawn the entities removed from the scene.
+        for previous_entity in &previous.entities {
+            if self
+                .entities
+                .iter()
+                .any(|scene_entity| scene_entity.entity == previous_entity.entity)
+            {
+                continue;
+            }
+            if let Some(entity) = entity_map.remove(Entity::from_raw(previous_entity.entity)) {
+                if let Some(parent) = world.get::<Parent>(entity).map(|parent| parent.get()) {
+                    if world.get_entity(parent).is_some() {
+                        world.entity_mut(parent).remove_children(&[entity]);
+                    }
+                }
+                world.despawn(entity);
+            }
+        }
+
+        // The entities written with each component type that maps entities
+        let mut entities_to_map: HashMap<TypeId, Vec<Entity>> = HashMap::default();
+
         for scene_entity in &self.entities {
             // Fetch the entity with the given entity id from the `entity_map`
             // or spawn a new entity with a transiently unique id if there is
             // no corresponding entry.
             let entity = *entity_map
-                .entry(bevy_ecs::entity::Entity::from_raw(scene_entity.entity))
+                .entry(Entity::from_raw(scene_entity.entity))
                 .or_insert_with(|| world.spawn().id());
+            // The entity may have been despawned since the scene was written.
+            if world.get_entity(entity).is_none() {
+                continue;
+            }
+            let previous_components = previous
+                .entities
+                .iter()
+                .find(|previous_entity| previous_entity.entity == scene_entity.entity)
+                .map_or(&[][..], |previous_entity| &previous_entity.components);
+
+            // Remove the components removed from the entity.
+            for previous_component in previous_components {
+                if find_value(&scene_entity.components, &**previous_component).is_some() {
+                    continue;
+                }
+                if let Some(reflect_component) = type_registry
+                    .get_with_name(previous_component.type_name())
+                    .and_then(|registration| registration.data::<ReflectComponent>())
+                {
+                    reflect_component.remove(world, entity);
+                }
+            }
 
-            // Apply/ add each component to the given entity.
+            // Apply/ add each changed component to the given entity.
             for component in &scene_entity.components {
+                if is_unchanged(find_value(previous_components, &**component), &**component) {
+                    continue;
+                }
                 let registration = type_registry
                     .get_with_name(component.type_name())
                     .ok_or_else(|| SceneSpawnError::UnregisteredType {
@@ -113,13 +204,23 @@ impl DynamicScene {
                 // just apply the (possibly) new value, otherwise add the
                 // component to the entity.
                 reflect_component.apply_or_insert(world, entity, &**component);
+
+                if registration.data::<ReflectMapEntities>().is_some() {
+                    entities_to_map
+                        .entry(registration.type_id())
+                        .or_default()
+                        .push(entity);
+                }
             }
         }
 
-        for registration in type_registry.iter() {
-            if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
+        for (type_id, entities) in entities_to_map {
+            if let Some(map_entities_reflect) = type_registry
+                .get(type_id)
+                .and_then(|registration| registration.data::<ReflectMapEntities>())
+            {
                 map_entities_reflect
-                    .map_entities(world, entity_map)
+                    .map_specific_entities(world, entity_map, &entities)
                     .unwrap();
             }
         }
@@ -127,11 +228,57 @@ impl DynamicScene {
         Ok(())
     }
 
+    /// Clones the scene, with dynamic versions of its resources and components.
+    pub fn clone_dynamic(&self) -> Self {
+        DynamicScene {
+            resources: self
+                .resources
+                .iter()
+                .map(|resource| resource.clone_value())
+                .collect(),
+            entities: self
+                .entities
+                .iter()
+                .map(|entity| DynamicEntity {
+                    entity: entity.entity,
+                    components: entity
+                        .components
+                        .iter()
+                        .map(|component| component.clone_value())
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+
     // TODO: move to AssetSaver when it is implemented
     /// Serialize this dynamic scene into rust object notation (ron).
     pub fn serialize_ron(&self, registry: &TypeRegistryArc) -> Result<String, ron::Error> {
         serialize_ron(SceneSerializer::new(self, registry))
     }
+
+    /// Serialize this dynamic scene into the compact [`binary`](crate::binary) format.
+    pub fn serialize_binary(
+        &self,
+        registry: &TypeRegistryArc,
+    ) -> Result<Vec<u8>, BinarySceneError> {
+        binary::serialize(self, &registry.read())
+    }
+}
+
+/// Finds the value of the same type as `value` in `values`.
+fn find_value<'a>(values: &'a [Box<dyn Reflect>], value: &dyn Reflect) -> Option<&'a dyn Reflect> {
+    values
+        .iter()
+        .find(|candidate| candidate.type_name() == value.type_name())
+        .map(|candidate| &**candidate)
+}
+
+/// Returns `true` if `value` is known to be equal to its `previous` value.
+fn is_unchanged(previous: Option<&dyn Reflect>, value: &dyn Reflect) -> bool {
+    previous
+        .and_then(|previous| previous.reflect_partial_eq(value))
+        .unwrap_or(false)
 }
 
 /// Serialize a given Rust data structure into rust object notation (ron).
@@ -145,3 +292,190 @@ where
         .new_line("\n".to_string());
     ron::ser::to_string_pretty(&serialize, pretty_config)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{
+        entity::{MapEntities, MapEntitiesError},
+        prelude::*,
+        world::FromWorld,
+    };
+    use bevy_hierarchy::{BuildWorldChildren, Children, Parent};
+
+    /// A component referencing an entity that may not be part of the scene.
+    #[derive(Component, Reflect, Debug, PartialEq)]
+    #[reflect(Component, MapEntities)]
+    struct Target(Entity);
+
+    impl FromWorld for Target {
+        fn from_world(_world: &mut World) -> Self {
+            Target(Entity::from_raw(u32::MAX))
+        }
+    }
+
+    impl MapEntities for Target {
+        fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+            if let Ok(mapped_entity) = entity_map.get(self.0) {
+                self.0 = mapped_entity;
+            }
+            Ok(())
+        }
+    }
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Speed(u32);
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Health(u32);
+
+    /// A component added at runtime, which scenes do not own.
+    #[derive(Component)]
+    struct Runtime;
+
+    /// A root with a child, both targeting the root, and an entity outside of the scene.
+    fn scene(registry: &TypeRegistryArc, outside: Entity) -> DynamicScene {
+        let mut world = World::new();
+        let root = world.spawn().insert(Target(outside)).id();
+        world.entity_mut(root).with_children(|parent| {
+            parent.spawn().insert(Target(root));
+        });
+        DynamicScene::from_world(&world, registry)
+    }
+
+    #[test]
+    fn spawn_many_times() {
+        let registry = type_registry![Speed, Health, Target, Parent, Children];
+        let outside = Entity::from_raw(1000);
+        let scene = scene(&registry, outside);
+
+        let mut world = World::new();
+        world.insert_resource(registry.clone());
+        // an unrelated hierarchy, with the same ids as the scene entities
+        let unrelated_parent = world.spawn().id();
+        let unrelated_child = world.spawn().id();
+        world
+            .entity_mut(unrelated_parent)
+            .push_children(&[unrelated_child]);
+
+        let mut instances = Vec::new();
+        for _ in 0..100 {
+            let mut entity_map = EntityMap::default();
+            scene.write_to_world(&mut world, &mut entity_map).unwrap();
+            instances.push(entity_map);
+        }
+
+        for entity_map in &instances {
+            let root = entity_map.get(Entity::from_raw(0)).unwrap();
+            let child = entity_map.get(Entity::from_raw(1)).unwrap();
+            assert_eq!(world.get::<Target>(root), Some(&Target(outside)));
+            assert_eq!(world.get::<Target>(child), Some(&Target(root)));
+            assert_eq!(
+                world.get::<Parent>(child).map(|parent| parent.get()),
+                Some(root)
+            );
+            assert_eq!(&**world.get::<Children>(root).unwrap(), &[child]);
+        }
+        assert_eq!(
+            world
+                .get::<Parent>(unrelated_child)
+                .map(|parent| parent.get()),
+            Some(unrelated_parent)
+        );
+        assert_eq!(
+            &**world.get::<Children>(unrelated_parent).unwrap(),
+            &[unrelated_child]
+        );
+    }
+
+    #[test]
+    fn write_again_keeps_outside_references() {
+        let registry = type_registry![Speed, Health, Target, Parent, Children];
+        let scene = scene(&registry, Entity::from_raw(1000));
+
+        let mut world = World::new();
+        world.insert_resource(registry);
+        let outside_parent = world.spawn().id();
+
+        let mut entity_map = EntityMap::default();
+        scene.write_to_world(&mut world, &mut entity_map).unwrap();
+        let root = entity_map.get(Entity::from_raw(0)).unwrap();
+        let child = entity_map.get(Entity::from_raw(1)).unwrap();
+        // the scene root is parented outside of the scene, with the id of a scene entity
+        assert_eq!(outside_parent, Entity::from_raw(0));
+        world.entity_mut(outside_parent).push_children(&[root]);
+
+        scene.write_to_world(&mut world, &mut entity_map).unwrap();
+        assert_eq!(
+            world.get::<Parent>(root).map(|parent| parent.get()),
+            Some(outside_parent)
+        );
+        assert_eq!(
+            world.get::<Parent>(child).map(|parent| parent.get()),
+            Some(root)
+        );
+        assert_eq!(world.get::<Target>(child), Some(&Target(root)));
+    }
+
+    #[test]
+    fn write_changes_preserves_runtime_state() {
+        fn entity(entity: u32, components: Vec<Box<dyn Reflect>>) -> DynamicEntity {
+            DynamicEntity { entity, components }
+        }
+        let previous = DynamicScene {
+            resources: Vec::new(),
+            entities: vec![
+                entity(0, vec![Box::new(Speed(1)), Box::new(Health(10))]),
+                entity(1, vec![Box::new(Speed(1)), Box::new(Health(10))]),
+            ],
+        };
+        let scene = DynamicScene {
+            resources: Vec::new(),
+            entities: vec![
+                // the speed changed and the health was removed
+                entity(0, vec![Box::new(Speed(2))]),
+                // the entity 1 was removed and the entity 2 added
+                entity(2, vec![Box::new(Health(20))]),
+            ],
+        };
+
+        let mut world = World::new();
+        world.insert_resource(type_registry![Speed, Health, Target, Parent, Children]);
+        let mut entity_map = EntityMap::default();
+        previous
+            .write_to_world(&mut world, &mut entity_map)
+            .unwrap();
+        let first = entity_map.get(Entity::from_raw(0)).unwrap();
+        let removed = entity_map.get(Entity::from_raw(1)).unwrap();
+        world.entity_mut(first).insert(Runtime);
+
+        scene
+            .write_changes_to_world(&previous, &mut world, &mut entity_map)
+            .unwrap();
+        assert_eq!(world.get::<Speed>(first), Some(&Speed(2)));
+        assert_eq!(world.get::<Health>(first), None);
+        assert!(world.get::<Runtime>(first).is_some());
+        assert!(world.get_entity(removed).is_none());
+        assert!(entity_map.get(Entity::from_raw(1)).is_err());
+        let added = entity_map.get(Entity::from_raw(2)).unwrap();
+        assert_eq!(world.get::<Health>(added), Some(&Health(20)));
+
+        // unchanged components keep the value they have at runtime
+        world.get_mut::<Speed>(first).unwrap().0 = 5;
+        world.get_mut::<Health>(added).unwrap().0 = 15;
+        let next = DynamicScene {
+            resources: Vec::new(),
+            entities: vec![
+                entity(0, vec![Box::new(Speed(2))]),
+                entity(2, vec![Box::new(Health(20)), Box::new(Speed(3))]),
+            ],
+        };
+        next.write_changes_to_world(&scene, &mut world, &mut entity_map)
+            .unwrap();
+        assert_eq!(world.get::<Speed>(first), Some(&Speed(5)));
+        assert_eq!(world.get::<Health>(added), Some(&Health(15)));
+        assert_eq!(world.get::<Speed>(added), Some(&Speed(3)));
+    }
+}
@@ -0,0 +1,419 @@
+use crate::{DynamicEntity, DynamicScene, SceneFilter};
+use bevy_ecs::{
+    entity::Entity,
+    reflect::{ReflectComponent, ReflectResource},
+    world::World,
+};
+use bevy_hierarchy::Children;
+use bevy_reflect::{Reflect, TypeRegistryArc};
+use bevy_utils::HashSet;
+use std::any::Any;
+
+/// A component processor of a [`DynamicSceneBuilder`], see
+/// [`DynamicSceneBuilder::with_component_processor`].
+type ComponentProcessor<'w> = Box<dyn Fn(Entity, &dyn Reflect) -> Option<Box<dyn Reflect>> + 'w>;
+
+/// A [`DynamicScene`] builder, extracting chosen entities and resources from a [`World`].
+///
+/// Components and resources are extracted if their type is registered with
+/// `#[reflect(Component)]` or `#[reflect(Resource)]`, and allowed by the filters of the builder.
+/// Entities are only extracted once, even when several methods extract them.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_reflect::{Reflect, TypeRegistryArc};
+/// # use bevy_scene::DynamicSceneBuilder;
+/// # #[derive(Component, Reflect, Default)]
+/// # #[reflect(Component)]
+/// # struct Player;
+/// # #[derive(Component)]
+/// # struct Camera;
+/// # let mut world = World::new();
+/// # world.insert_resource(TypeRegistryArc::default());
+/// # world.spawn().insert(Player);
+/// // extract the players, without their cameras
+/// let mut query = world.query_filtered::<Entity, With<Player>>();
+/// let scene = DynamicSceneBuilder::from_world(&world)
+///     .deny::<Camera>()
+///     .extract_entities(query.iter(&world))
+///     .extract_resources()
+///     .build();
+/// ```
+pub struct DynamicSceneBuilder<'w> {
+    world: &'w World,
+    type_registry: TypeRegistryArc,
+    entities: Vec<DynamicEntity>,
+    extracted_entities: HashSet<Entity>,
+    resources: Vec<Box<dyn Reflect>>,
+    component_filter: SceneFilter,
+    resource_filter: SceneFilter,
+    component_processors: Vec<ComponentProcessor<'w>>,
+}
+
+impl<'w> DynamicSceneBuilder<'w> {
+    /// Creates a builder extracting from `world`, using its [`TypeRegistryArc`] resource.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the world does not have a [`TypeRegistryArc`] resource.
+    pub fn from_world(world: &'w World) -> Self {
+        Self::from_world_with_type_registry(world, world.resource::<TypeRegistryArc>().clone())
+    }
+
+    /// Creates a builder extracting from `world`, using the given type registry.
+    pub fn from_world_with_type_registry(world: &'w World, type_registry: TypeRegistryArc) -> Self {
+        DynamicSceneBuilder {
+            world,
+            type_registry,
+            entities: Vec::new(),
+            extracted_entities: HashSet::default(),
+            resources: Vec::new(),
+            component_filter: SceneFilter::allow_all(),
+            resource_filter: SceneFilter::allow_all(),
+            component_processors: Vec::new(),
+        }
+    }
+
+    /// Sets the filter of the components to extract, replacing the previous one.
+    #[must_use]
+    pub fn with_component_filter(mut self, filter: SceneFilter) -> Self {
+        self.component_filter = filter;
+        self
+    }
+
+    /// Sets the filter of the resources to extract, replacing the previous one.
+    #[must_use]
+    pub fn with_resource_filter(mut self, filter: SceneFilter) -> Self {
+        self.resource_filter = filter;
+        self
+    }
+
+    /// Allows the extraction of the component `T`.
+    ///
+    /// Use [`SceneFilter::deny_all`] with [`with_component_filter`] to only extract allowed
+    /// components.
+    ///
+    /// [`with_component_filter`]: DynamicSceneBuilder::with_component_filter
+    #[must_use]
+    pub fn allow<T: Any>(mut self) -> Self {
+        self.component_filter = self.component_filter.allow::<T>();
+        self
+    }
+
+    /// Denies the extraction of the component `T`.
+    #[must_use]
+    pub fn deny<T: Any>(mut self) -> Self {
+        self.component_filter = self.component_filter.deny::<T>();
+        self
+    }
+
+    /// Allows the extraction of the resource `T`.
+    ///
+    /// Use [`SceneFilter::deny_all`] with [`with_resource_filter`] to only extract allowed
+    /// resources.
+    ///
+    /// [`with_resource_filter`]: DynamicSceneBuilder::with_resource_filter
+    #[must_use]
+    pub fn allow_resource<T: Any>(mut self) -> Self {
+        self.resource_filter = self.resource_filter.allow::<T>();
+        self
+    }
+
+    /// Denies the extraction of the resource `T`.
+    #[must_use]
+    pub fn deny_resource<T: Any>(mut self) -> Self {
+        self.resource_filter = self.resource_filter.deny::<T>();
+        self
+    }
+
+    /// Adds a processor called on each allowed component when it is extracted, along with its
+    /// entity. The processor returns the value to extract, or `None` to strip the component.
+    ///
+    /// Processors are called in the order they were added, each with the value returned by the
+    /// previous one, and only apply to the entities extracted after they were added.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_reflect::{Reflect, TypeRegistryArc};
+    /// # use bevy_scene::DynamicSceneBuilder;
+    /// # #[derive(Component, Reflect, Default)]
+    /// # #[reflect(Component)]
+    /// # struct Health(u32);
+    /// # let mut world = World::new();
+    /// # world.insert_resource(TypeRegistryArc::default());
+    /// // save every entity at full health
+    /// let scene = DynamicSceneBuilder::from_world(&world)
+    ///     .with_component_processor(|_, component| {
+    ///         if component.is::<Health>() {
+    ///             return Some(Box::new(Health(100)));
+    ///         }
+    ///         Some(component.clone_value())
+    ///     })
+    ///     .extract_all_entities()
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub fn with_component_processor(
+        mut self,
+        processor: impl Fn(Entity, &dyn Reflect) -> Option<Box<dyn Reflect>> + 'w,
+    ) -> Self {
+        self.component_processors.push(Box::new(processor));
+        self
+    }
+
+    /// Extracts an entity and its allowed components.
+    ///
+    /// Entities that do not exist are ignored.
+    #[must_use]
+    pub fn extract_entity(self, entity: Entity) -> Self {
+        self.extract_entities(std::iter::once(entity))
+    }
+
+    /// Extracts entities and their allowed components, such as the results of a query.
+    ///
+    /// Entities that do not exist are ignored.
+    #[must_use]
+    pub fn extract_entities(mut self, entities: impl IntoIterator<Item = Entity>) -> Self {
+        let type_registry = self.type_registry.clone();
+        let type_registry = type_registry.read();
+
+        for entity in entities {
+            if self.extracted_entities.contains(&entity) {
+                continue;
+            }
+            let archetype = match self
+                .world
+                .entities()
+                .get(entity)
+                .and_then(|location| self.world.archetypes().get(location.archetype_id))
+            {
+                Some(archetype) => archetype,
+                None => continue,
+            };
+
+            let mut dynamic_entity = DynamicEntity {
+                entity: entity.id(),
+                components: Vec::new(),
+            };
+            for component_id in archetype.components() {
+                let reflect_component = self
+                    .world
+                    .components()
+                    .get_info(component_id)
+                    .and_then(|info| info.type_id())
+                    .filter(|type_id| self.component_filter.is_allowed_by_id(*type_id))
+                    .and_then(|type_id| type_registry.get(type_id))
+                    .and_then(|registration| registration.data::<ReflectComponent>());
+                let component = match reflect_component
+                    .and_then(|reflect_component| reflect_component.reflect(self.world, entity))
+                {
+                    Some(component) => component,
+                    None => continue,
+                };
+                let mut processed: Option<Box<dyn Reflect>> = None;
+                let mut stripped = false;
+                for processor in &self.component_processors {
+                    match processor(entity, processed.as_deref().unwrap_or(component)) {
+                        Some(value) => processed = Some(value),
+                        None => {
+                            stripped = true;
+                            break;
+                        }
+                    }
+                }
+                if !stripped {
+                    dynamic_entity
+                        .components
+                        .push(processed.unwrap_or_else(|| component.clone_value()));
+                }
+            }
+
+            self.extracted_entities.insert(entity);
+            self.entities.push(dynamic_entity);
+        }
+        self
+    }
+
+    /// Extracts an entity and all of its descendants, with their allowed components.
+    #[must_use]
+    pub fn extract_hierarchy(self, root: Entity) -> Self {
+        let mut entities = vec![root];
+        let mut next = 0;
+        while let Some(&entity) = entities.get(next) {
+            if let Some(children) = self.world.get::<Children>(entity) {
+                entities.extend(children.iter().copied());
+            }
+            next += 1;
+        }
+        self.extract_entities(entities)
+    }
+
+    /// Extracts all entities of the world and their allowed components.
+    #[must_use]
+    pub fn extract_all_entities(self) -> Self {
+        let world = self.world;
+        self.extract_entities(
+            world
+                .archetypes()
+                .iter()
+                .flat_map(|archetype| archetype.entities().iter().copied()),
+        )
+    }
+
+    /// Extracts the allowed resources of the world.
+    ///
+    /// Resources that were already extracted are extracted again, replacing their previous
+    /// value.
+    #[must_use]
+    pub fn extract_resources(mut self) -> Self {
+        let type_registry = self.type_registry.read();
+        let mut resources = Vec::new();
+        for registration in type_registry.iter() {
+            if !self
+                .resource_filter
+                .is_allowed_by_id(registration.type_id())
+            {
+                continue;
+            }
+            if let Some(resource) = registration
+                .data::<ReflectResource>()
+                .and_then(|reflect_resource| reflect_resource.reflect(self.world))
+            {
+                resources.push(resource.clone_value());
+            }
+        }
+        drop(type_registry);
+
+        self.resources.retain(|resource| {
+            !resources
+                .iter()
+                .any(|extracted| extracted.type_name() == resource.type_name())
+        });
+        self.resources.extend(resources);
+        self
+    }
+
+    /// Builds the [`DynamicScene`] of the extracted entities and resources.
+    pub fn build(self) -> DynamicScene {
+        DynamicScene {
+            resources: self.resources,
+            entities: self.entities,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::prelude::*;
+    use bevy_hierarchy::BuildWorldChildren;
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Health(u32);
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Name(String);
+
+    #[derive(Reflect, Default)]
+    #[reflect(Resource)]
+    struct Score(u32);
+
+    #[derive(Reflect, Default)]
+    #[reflect(Resource)]
+    struct Seed(u64);
+
+    fn world() -> World {
+        let mut world = World::new();
+        world.insert_resource(type_registry![Health, Name, Children, Score, Seed]);
+        world.insert_resource(Score(1));
+        world.insert_resource(Seed(2));
+        world
+    }
+
+    #[test]
+    fn extract_filtered_entities() {
+        let mut world = world();
+        let player = world
+            .spawn()
+            .insert_bundle((Health(10), Name("player".to_string())))
+            .id();
+        world.spawn().insert(Health(20));
+
+        let scene = DynamicSceneBuilder::from_world(&world)
+            .deny::<Name>()
+            .extract_entity(player)
+            .extract_entity(player)
+            .build();
+        assert_eq!(scene.entities.len(), 1);
+        assert_eq!(scene.entities[0].entity, player.id());
+        assert_eq!(scene.entities[0].components.len(), 1);
+        assert_eq!(
+            scene.entities[0].components[0].type_name(),
+            std::any::type_name::<Health>()
+        );
+        assert!(scene.resources.is_empty());
+    }
+
+    #[test]
+    fn extract_hierarchy() {
+        let mut world = world();
+        let mut grandchild = None;
+        let root = world
+            .spawn()
+            .insert(Health(1))
+            .with_children(|parent| {
+                parent.spawn().insert(Health(2)).with_children(|parent| {
+                    grandchild = Some(parent.spawn().insert(Health(3)).id());
+                });
+            })
+            .id();
+        world.spawn().insert(Health(4));
+
+        let scene = DynamicSceneBuilder::from_world(&world)
+            .with_component_filter(SceneFilter::deny_all().allow::<Health>())
+            .extract_hierarchy(root)
+            .build();
+        assert_eq!(scene.entities.len(), 3);
+        assert_eq!(scene.entities[0].entity, root.id());
+        assert_eq!(scene.entities[2].entity, grandchild.unwrap().id());
+        assert!(scene
+            .entities
+            .iter()
+            .all(|entity| entity.components.len() == 1));
+    }
+
+    #[test]
+    fn process_components_and_filter_resources() {
+        let mut world = world();
+        world
+            .spawn()
+            .insert_bundle((Health(10), Name("player".to_string())));
+
+        let scene = DynamicSceneBuilder::from_world(&world)
+            .with_component_processor(|_, component| {
+                if component.is::<Name>() {
+                    return None;
+                }
+                match component.downcast_ref::<Health>() {
+                    Some(health) => Some(Box::new(Health(health.0 * 10))),
+                    None => Some(component.clone_value()),
+                }
+            })
+            .deny_resource::<Seed>()
+            .extract_all_entities()
+            .extract_resources()
+            .build();
+        assert_eq!(scene.entities[0].components.len(), 1);
+        assert_eq!(
+            scene.entities[0].components[0].downcast_ref::<Health>(),
+            Some(&Health(100))
+        );
+        assert_eq!(scene.resources.len(), 1);
+        assert_eq!(
+            scene.resources[0].type_name(),
+            std::any::type_name::<Score>()
+        );
+    }
+}
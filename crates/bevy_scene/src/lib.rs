@@ -1,19 +1,45 @@
+/// Creates a [`TypeRegistryArc`](bevy_reflect::TypeRegistryArc) with the given types registered.
+#[cfg(test)]
+macro_rules! type_registry {
+    ($($ty:ty),* $(,)?) => {{
+        let registry = bevy_reflect::TypeRegistryArc::default();
+        {
+            let mut registry = registry.write();
+            $(registry.register::<$ty>();)*
+        }
+        registry
+    }};
+}
+
+pub mod binary;
 mod bundle;
 mod dynamic_scene;
+mod dynamic_scene_builder;
+mod migration;
 mod scene;
+mod scene_filter;
 mod scene_loader;
+mod scene_saver;
 mod scene_spawner;
+mod scene_template;
 pub mod serde;
 
 pub use bundle::*;
 pub use dynamic_scene::*;
+pub use dynamic_scene_builder::*;
+pub use migration::SceneMigrationError;
 pub use scene::*;
+pub use scene_filter::*;
 pub use scene_loader::*;
+pub use scene_saver::*;
 pub use scene_spawner::*;
+pub use scene_template::*;
 
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{DynamicScene, DynamicSceneBundle, Scene, SceneBundle, SceneSpawner};
+    pub use crate::{
+        DynamicScene, DynamicSceneBuilder, DynamicSceneBundle, Scene, SceneBundle, SceneSpawner,
+    };
 }
 
 use bevy_app::prelude::*;
@@ -28,7 +54,10 @@ impl Plugin for ScenePlugin {
         app.add_asset::<DynamicScene>()
             .add_asset::<Scene>()
             .init_asset_loader::<SceneLoader>()
+            .init_asset_saver::<SceneSaver>()
+            .init_asset_saver::<BinarySceneSaver>()
             .init_resource::<SceneSpawner>()
+            .add_event::<SceneInstanceUpdated>()
             .add_system_to_stage(
                 CoreStage::PreUpdate,
                 scene_spawner_system.exclusive_system().at_end(),
@@ -0,0 +1,92 @@
+use crate::DynamicScene;
+use bevy_reflect::{DynamicStruct, DynamicTupleStruct, Reflect, TypeRegistry};
+use bevy_utils::HashMap;
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+/// An error migrating the resources and components of a scene to the current version of their
+/// types.
+#[derive(Error, Debug)]
+pub enum SceneMigrationError {
+    #[error("type `{type_name}` has version {version} in the scene, but its registered version is {registered_version}")]
+    NewerVersion {
+        type_name: String,
+        version: u32,
+        registered_version: u32,
+    },
+}
+
+/// Returns the versions of the resource and component types of `scene` to record in a scene
+/// file, which are those whose [version](bevy_reflect::TypeRegistration::version) is not `0`.
+pub(crate) fn scene_versions(
+    scene: &DynamicScene,
+    type_registry: &TypeRegistry,
+) -> BTreeMap<String, u32> {
+    scene
+        .resources
+        .iter()
+        .chain(scene.entities.iter().flat_map(|entity| &entity.components))
+        .filter_map(|value| {
+            let registration = type_registry.get_with_name(value.type_name())?;
+            (registration.version() != 0)
+                .then(|| (value.type_name().to_string(), registration.version()))
+        })
+        .collect()
+}
+
+/// Upgrades the resources and components of `scene` from the `versions` recorded in its file to
+/// the current version of their types, and renames those recorded with an
+/// [alias](TypeRegistry::register_alias) of their type.
+///
+/// Types missing from `versions` are at version `0`. Only the resources and components
+/// themselves are migrated, not the values of their fields.
+pub(crate) fn migrate_scene(
+    scene: &mut DynamicScene,
+    versions: &HashMap<String, u32>,
+    type_registry: &TypeRegistry,
+) -> Result<(), SceneMigrationError> {
+    for values in std::iter::once(&mut scene.resources).chain(
+        scene
+            .entities
+            .iter_mut()
+            .map(|entity| &mut entity.components),
+    ) {
+        for value in values.iter_mut() {
+            let placeholder: Box<dyn Reflect> = Box::new(());
+            let current = std::mem::replace(value, placeholder);
+            *value = migrate(current, versions, type_registry)?;
+        }
+    }
+    Ok(())
+}
+
+fn migrate(
+    value: Box<dyn Reflect>,
+    versions: &HashMap<String, u32>,
+    type_registry: &TypeRegistry,
+) -> Result<Box<dyn Reflect>, SceneMigrationError> {
+    // unregistered types are reported when the scene is written to a world
+    let registration = match type_registry.get_with_name(value.type_name()) {
+        Some(registration) => registration,
+        None => return Ok(value),
+    };
+    let version = versions.get(value.type_name()).copied().unwrap_or(0);
+    if version > registration.version() {
+        return Err(SceneMigrationError::NewerVersion {
+            type_name: value.type_name().to_string(),
+            version,
+            registered_version: registration.version(),
+        });
+    }
+
+    let mut value = registration.migrate(value, version);
+    if value.type_name() != registration.type_name() {
+        let type_name = registration.type_name().to_string();
+        if let Some(dynamic_struct) = value.downcast_mut::<DynamicStruct>() {
+            dynamic_struct.set_name(type_name);
+        } else if let Some(tuple_struct) = value.downcast_mut::<DynamicTupleStruct>() {
+            tuple_struct.set_name(type_name);
+        }
+    }
+    Ok(value)
+}
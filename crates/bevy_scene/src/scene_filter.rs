@@ -0,0 +1,95 @@
+use bevy_utils::HashSet;
+use std::any::{Any, TypeId};
+
+/// Selects which types are extracted into a [`DynamicScene`](crate::DynamicScene).
+///
+/// ```
+/// # use bevy_scene::SceneFilter;
+/// # struct Score;
+/// # struct Time;
+/// // only extract the `Score` resource
+/// let filter = SceneFilter::deny_all().allow::<Score>();
+/// assert!(filter.is_allowed::<Score>());
+///
+/// // extract every resource but `Time`
+/// let filter = SceneFilter::allow_all().deny::<Time>();
+/// assert!(!filter.is_allowed::<Time>());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SceneFilter {
+    /// Only the listed types are allowed.
+    Allowlist(HashSet<TypeId>),
+    /// Every type except the listed ones is allowed.
+    Denylist(HashSet<TypeId>),
+}
+
+impl Default for SceneFilter {
+    fn default() -> Self {
+        Self::allow_all()
+    }
+}
+
+impl SceneFilter {
+    /// Creates a filter allowing every type.
+    pub fn allow_all() -> Self {
+        SceneFilter::Denylist(HashSet::default())
+    }
+
+    /// Creates a filter denying every type.
+    pub fn deny_all() -> Self {
+        SceneFilter::Allowlist(HashSet::default())
+    }
+
+    /// Allows the type `T`.
+    #[must_use]
+    pub fn allow<T: Any>(self) -> Self {
+        self.allow_by_id(TypeId::of::<T>())
+    }
+
+    /// Allows the type with the given [`TypeId`].
+    #[must_use]
+    pub fn allow_by_id(mut self, type_id: TypeId) -> Self {
+        match &mut self {
+            SceneFilter::Allowlist(list) => {
+                list.insert(type_id);
+            }
+            SceneFilter::Denylist(list) => {
+                list.remove(&type_id);
+            }
+        }
+        self
+    }
+
+    /// Denies the type `T`.
+    #[must_use]
+    pub fn deny<T: Any>(self) -> Self {
+        self.deny_by_id(TypeId::of::<T>())
+    }
+
+    /// Denies the type with the given [`TypeId`].
+    #[must_use]
+    pub fn deny_by_id(mut self, type_id: TypeId) -> Self {
+        match &mut self {
+            SceneFilter::Allowlist(list) => {
+                list.remove(&type_id);
+            }
+            SceneFilter::Denylist(list) => {
+                list.insert(type_id);
+            }
+        }
+        self
+    }
+
+    /// Returns `true` if the type `T` is allowed.
+    pub fn is_allowed<T: Any>(&self) -> bool {
+        self.is_allowed_by_id(TypeId::of::<T>())
+    }
+
+    /// Returns `true` if the type with the given [`TypeId`] is allowed.
+    pub fn is_allowed_by_id(&self, type_id: TypeId) -> bool {
+        match self {
+            SceneFilter::Allowlist(list) => list.contains(&type_id),
+            SceneFilter::Denylist(list) => !list.contains(&type_id),
+        }
+    }
+}
@@ -1,16 +1,46 @@
-use crate::serde::SceneDeserializer;
+use crate::{
+    binary, serde::SceneTemplateDeserializer, DynamicScene, SceneSpawnError, SceneTemplate,
+};
 use anyhow::Result;
-use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy_asset::{AssetLoader, AssetPath, LoadContext, LoadedAsset};
 use bevy_ecs::world::{FromWorld, World};
 use bevy_reflect::TypeRegistryArc;
 use bevy_utils::BoxedFuture;
 use serde::de::DeserializeSeed;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
 
+/// Loads [`DynamicScene`]s from `.scn.ron` files, and from binary `.scn.bin` files, see the
+/// [`binary`] module.
+///
+/// The scenes a scene file derives from or nests, see [`SceneTemplate`], are read and composed
+/// with it, and are also loaded as dependencies of the scene.
 #[derive(Debug)]
 pub struct SceneLoader {
     type_registry: TypeRegistryArc,
 }
 
+/// An error resolving the scenes referenced by a scene file.
+#[derive(Error, Debug)]
+pub enum SceneLoaderError {
+    #[error("scenes reference each other in a cycle: {}", format_cycle(.cycle))]
+    Cycle { cycle: Vec<PathBuf> },
+    #[error("scene `{path}` is in another asset source, scenes can only reference scenes of their own asset source")]
+    OtherAssetSource { path: String },
+    #[error("scene does not contain the entity {entity} a nested scene is parented to")]
+    NonExistentParent { entity: u32 },
+    #[error(transparent)]
+    Spawn(#[from] SceneSpawnError),
+}
+
+fn format_cycle(cycle: &[PathBuf]) -> String {
+    cycle
+        .iter()
+        .map(|path| format!("`{}`", path.display()))
+        .collect::<Vec<_>>()
+        .join(" -> ")
+}
+
 impl FromWorld for SceneLoader {
     fn from_world(world: &mut World) -> Self {
         let type_registry = world.resource::<TypeRegistryArc>();
@@ -27,17 +57,242 @@ impl AssetLoader for SceneLoader {
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<()>> {
         Box::pin(async move {
-            let mut deserializer = ron::de::Deserializer::from_bytes(bytes)?;
-            let scene_deserializer = SceneDeserializer {
-                type_registry: &*self.type_registry.read(),
-            };
-            let scene = scene_deserializer.deserialize(&mut deserializer)?;
-            load_context.set_default_asset(LoadedAsset::new(scene));
+            let template = self.deserialize(load_context.path(), bytes)?;
+            let dependencies: Vec<AssetPath<'static>> = template
+                .references()
+                .map(|path| load_context.resolve_path(path).to_owned())
+                .collect();
+            let mut path_stack = vec![load_context.path().to_path_buf()];
+            let scene = self
+                .resolve(load_context, template, &mut path_stack)
+                .await?;
+            load_context.set_default_asset(LoadedAsset::new(scene).with_dependencies(dependencies));
             Ok(())
         })
     }
 
     fn extensions(&self) -> &[&str] {
-        &["scn", "scn.ron"]
+        &["scn", "scn.ron", "scn.bin"]
+    }
+}
+
+impl SceneLoader {
+    fn deserialize(&self, path: &Path, bytes: &[u8]) -> Result<SceneTemplate> {
+        // binary scenes do not reference other scenes
+        let is_binary = bytes.starts_with(&binary::MAGIC)
+            || path.to_string_lossy().to_lowercase().ends_with(".scn.bin");
+        if is_binary {
+            return Ok(SceneTemplate {
+                scene: binary::deserialize(bytes, &self.type_registry.read())?,
+                ..Default::default()
+            });
+        }
+        let mut deserializer = ron::de::Deserializer::from_bytes(bytes)?;
+        let template_deserializer = SceneTemplateDeserializer {
+            type_registry: &*self.type_registry.read(),
+        };
+        Ok(template_deserializer.deserialize(&mut deserializer)?)
+    }
+
+    /// Resolves the scenes referenced by `template`, `path_stack` being the paths of the scenes
+    /// that reference it.
+    fn resolve<'a>(
+        &'a self,
+        load_context: &'a LoadContext,
+        template: SceneTemplate,
+        path_stack: &'a mut Vec<PathBuf>,
+    ) -> BoxedFuture<'a, Result<DynamicScene>> {
+        Box::pin(async move {
+            let base = match &template.base {
+                Some(path) => Some(self.load_reference(load_context, path, path_stack).await?),
+                None => None,
+            };
+            let mut instances = Vec::with_capacity(template.instances.len());
+            for instance in &template.instances {
+                instances.push(
+                    self.load_reference(load_context, &instance.scene, path_stack)
+                        .await?,
+                );
+            }
+            Ok(template.compose(base.as_ref(), &instances, &self.type_registry)?)
+        })
+    }
+
+    async fn load_reference(
+        &self,
+        load_context: &LoadContext<'_>,
+        path: &str,
+        path_stack: &mut Vec<PathBuf>,
+    ) -> Result<DynamicScene> {
+        let asset_path = AssetPath::from(path);
+        if asset_path.source().is_some() && asset_path.source() != load_context.source() {
+            return Err(SceneLoaderError::OtherAssetSource {
+                path: path.to_string(),
+            }
+            .into());
+        }
+        let path = asset_path.path();
+        if path_stack.iter().any(|stacked_path| stacked_path == path) {
+            let mut cycle = path_stack.clone();
+            cycle.push(path.to_path_buf());
+            return Err(SceneLoaderError::Cycle { cycle }.into());
+        }
+
+        let bytes = load_context.read_asset_bytes(path).await?;
+        let template = self.deserialize(path, &bytes)?;
+        path_stack.push(path.to_path_buf());
+        let scene = self.resolve(load_context, template, path_stack).await;
+        path_stack.pop();
+        scene
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DynamicScene, ScenePlugin};
+    use bevy_app::App;
+    use bevy_asset::{AssetPlugin, AssetServer, Assets, Handle, LoadState, MemoryAssetIo};
+    use bevy_ecs::{entity::EntityMap, prelude::*};
+    use bevy_reflect::{Reflect, TypeRegistryArc};
+    use bevy_tasks::IoTaskPool;
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Score(u32);
+
+    fn scene(base: Option<&str>, score: u32) -> Vec<u8> {
+        let base = base.map_or(String::new(), |base| format!("base: \"{}\",", base));
+        format!(
+            r#"({}
+                entities: [
+                    (
+                        entity: 0,
+                        components: [
+                            {{
+                                "type": "{}",
+                                "tuple_struct": [
+                                    {{
+                                        "type": "u32",
+                                        "value": {},
+                                    }},
+                                ],
+                            }},
+                        ],
+                    ),
+                ],
+            )"#,
+            base,
+            std::any::type_name::<Score>(),
+            score
+        )
+        .into_bytes()
+    }
+
+    fn load(files: &[(&str, Vec<u8>)], path: &str) -> (App, Handle<DynamicScene>) {
+        IoTaskPool::init(Default::default);
+        let asset_io = MemoryAssetIo::default();
+        for (path, scene) in files {
+            asset_io.insert(path, scene.clone());
+        }
+        let mut app = App::new();
+        app.insert_resource(AssetServer::new(asset_io))
+            .init_resource::<TypeRegistryArc>()
+            .add_plugin(AssetPlugin)
+            .add_plugin(ScenePlugin);
+        app.world
+            .resource::<TypeRegistryArc>()
+            .write()
+            .register::<Score>();
+
+        let handle = app.world.resource::<AssetServer>().load(path);
+        for _ in 0..1000 {
+            app.update();
+            let load_state = app.world.resource::<AssetServer>().get_load_state(&handle);
+            if matches!(load_state, LoadState::Loaded | LoadState::Failed) {
+                break;
+            }
+            std::thread::yield_now();
+        }
+        (app, handle)
+    }
+
+    #[test]
+    fn derived_scene() {
+        let (mut app, handle) = load(
+            &[
+                ("base.scn.ron", scene(None, 1)),
+                ("derived.scn.ron", scene(Some("base.scn.ron"), 2)),
+            ],
+            "derived.scn.ron",
+        );
+        let asset_server = app.world.resource::<AssetServer>();
+        assert_eq!(asset_server.get_load_state(&handle), LoadState::Loaded);
+        let base = asset_server.get_handle::<DynamicScene, _>("base.scn.ron");
+        assert_eq!(asset_server.get_load_state(&base), LoadState::Loaded);
+
+        app.world
+            .resource_scope(|world, scenes: Mut<Assets<DynamicScene>>| {
+                let scene = scenes.get(&handle).unwrap();
+                assert_eq!(scene.entities.len(), 1);
+                scene.write_to_world(world, &mut EntityMap::default())
+            })
+            .unwrap();
+        let mut query = app.world.query::<&Score>();
+        assert_eq!(query.iter(&app.world).collect::<Vec<_>>(), vec![&Score(2)]);
+    }
+
+    #[test]
+    fn binary_base_scene() {
+        let registry = TypeRegistryArc::default();
+        registry.write().register::<Score>();
+        let mut world = World::new();
+        world.spawn().insert(Score(1));
+        let base = DynamicScene::from_world(&world, &registry)
+            .serialize_binary(&registry)
+            .unwrap();
+
+        let (app, handle) = load(
+            &[
+                ("base.scn.bin", base),
+                ("derived.scn.ron", scene(Some("base.scn.bin"), 2)),
+            ],
+            "derived.scn.ron",
+        );
+        let asset_server = app.world.resource::<AssetServer>();
+        assert_eq!(asset_server.get_load_state(&handle), LoadState::Loaded);
+        let base = asset_server.get_handle::<DynamicScene, _>("base.scn.bin");
+        assert_eq!(asset_server.get_load_state(&base), LoadState::Loaded);
+    }
+
+    #[test]
+    fn binary_scene_with_uppercase_extension() {
+        let registry = TypeRegistryArc::default();
+        registry.write().register::<Score>();
+        let mut world = World::new();
+        world.spawn().insert(Score(1));
+        let scene = DynamicScene::from_world(&world, &registry)
+            .serialize_binary(&registry)
+            .unwrap();
+
+        let (app, handle) = load(&[("Level.SCN.BIN", scene)], "Level.SCN.BIN");
+        assert_eq!(
+            app.world.resource::<AssetServer>().get_load_state(&handle),
+            LoadState::Loaded
+        );
+    }
+
+    #[test]
+    fn cycle() {
+        let (app, handle) = load(
+            &[
+                ("a.scn.ron", scene(Some("b.scn.ron"), 1)),
+                ("b.scn.ron", scene(Some("a.scn.ron"), 2)),
+            ],
+            "a.scn.ron",
+        );
+        assert_eq!(
+            app.world.resource::<AssetServer>().get_load_state(&handle),
+            LoadState::Failed
+        );
     }
 }
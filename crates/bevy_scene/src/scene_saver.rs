@@ -0,0 +1,114 @@
+use crate::DynamicScene;
+use anyhow::Result;
+use bevy_asset::AssetSaver;
+use bevy_ecs::world::{FromWorld, World};
+use bevy_reflect::TypeRegistryArc;
+
+/// Saves [`DynamicScene`]s to `.scn.ron` files.
+#[derive(Debug)]
+pub struct SceneSaver {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for SceneSaver {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.resource::<TypeRegistryArc>();
+        SceneSaver {
+            type_registry: (*type_registry).clone(),
+        }
+    }
+}
+
+impl AssetSaver for SceneSaver {
+    type Asset = DynamicScene;
+
+    fn save(&self, scene: &DynamicScene) -> Result<Vec<u8>> {
+        Ok(scene.serialize_ron(&self.type_registry)?.into_bytes())
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["scn", "scn.ron"]
+    }
+}
+
+/// Saves [`DynamicScene`]s to binary `.scn.bin` files, see the [`binary`](crate::binary) module.
+#[derive(Debug)]
+pub struct BinarySceneSaver {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for BinarySceneSaver {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.resource::<TypeRegistryArc>();
+        BinarySceneSaver {
+            type_registry: (*type_registry).clone(),
+        }
+    }
+}
+
+impl AssetSaver for BinarySceneSaver {
+    type Asset = DynamicScene;
+
+    fn save(&self, scene: &DynamicScene) -> Result<Vec<u8>> {
+        Ok(scene.serialize_binary(&self.type_registry)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["scn.bin"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{binary, serde::SceneDeserializer};
+    use bevy_ecs::{prelude::*, reflect::ReflectComponent};
+    use bevy_reflect::Reflect;
+    use serde::de::DeserializeSeed;
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Health(u32);
+
+    fn saved_scene(world: &mut World) -> DynamicScene {
+        world.insert_resource(type_registry![Health]);
+        world.spawn().insert(Health(7));
+        DynamicScene::from_world(world, world.resource::<TypeRegistryArc>())
+    }
+
+    fn assert_health(scene: &DynamicScene) {
+        assert_eq!(scene.entities.len(), 1);
+        let component = &scene.entities[0].components[0];
+        assert_eq!(component.type_name(), std::any::type_name::<Health>());
+        assert_eq!(component.reflect_partial_eq(&Health(7)), Some(true));
+    }
+
+    #[test]
+    fn save_ron_scene() {
+        let mut world = World::new();
+        let scene = saved_scene(&mut world);
+        let saver = SceneSaver::from_world(&mut world);
+        let bytes = saver.save(&scene).unwrap();
+
+        let registry = world.resource::<TypeRegistryArc>().read();
+        let mut deserializer = ron::de::Deserializer::from_bytes(&bytes).unwrap();
+        let loaded = SceneDeserializer {
+            type_registry: &registry,
+        }
+        .deserialize(&mut deserializer)
+        .unwrap();
+        assert_health(&loaded);
+    }
+
+    #[test]
+    fn save_binary_scene() {
+        let mut world = World::new();
+        let scene = saved_scene(&mut world);
+        let saver = BinarySceneSaver::from_world(&mut world);
+        let bytes = saver.save(&scene).unwrap();
+
+        let loaded =
+            binary::deserialize(&bytes, &world.resource::<TypeRegistryArc>().read()).unwrap();
+        assert_health(&loaded);
+    }
+}
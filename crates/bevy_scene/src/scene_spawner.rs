@@ -10,17 +10,29 @@ use bevy_ecs::{
 use bevy_hierarchy::{AddChild, Parent};
 use bevy_reflect::TypeRegistryArc;
 use bevy_utils::{tracing::error, HashMap};
+use std::{any::TypeId, sync::Arc};
 use thiserror::Error;
 use uuid::Uuid;
 
-#[derive(Debug)]
 struct InstanceInfo {
     entity_map: EntityMap,
+    /// The version of the dynamic scene the instance was written with, to update it when the
+    /// scene changes.
+    scene: Option<Arc<DynamicScene>>,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct InstanceId(Uuid);
 
+/// Sent when a spawned instance of a [`DynamicScene`] was updated after the scene asset changed.
+#[derive(Debug)]
+pub struct SceneInstanceUpdated {
+    /// The updated instance.
+    pub instance_id: InstanceId,
+    /// A weak handle to the scene of the instance.
+    pub scene: Handle<DynamicScene>,
+}
+
 impl InstanceId {
     fn new() -> Self {
         InstanceId(Uuid::new_v4())
@@ -32,6 +44,8 @@ pub struct SceneSpawner {
     spawned_scenes: HashMap<Handle<Scene>, Vec<InstanceId>>,
     spawned_dynamic_scenes: HashMap<Handle<DynamicScene>, Vec<InstanceId>>,
     spawned_instances: HashMap<InstanceId, InstanceInfo>,
+    /// The current version of each spawned dynamic scene, shared by its instances.
+    scene_snapshots: HashMap<Handle<DynamicScene>, Arc<DynamicScene>>,
     scene_asset_event_reader: ManualEventReader<AssetEvent<DynamicScene>>,
     dynamic_scenes_to_spawn: Vec<(Handle<DynamicScene>, InstanceId)>,
     scenes_to_spawn: Vec<(Handle<Scene>, InstanceId)>,
@@ -44,6 +58,8 @@ pub struct SceneSpawner {
 pub enum SceneSpawnError {
     #[error("scene contains the unregistered component `{type_name}`. consider adding `#[reflect(Component)]` to your type")]
     UnregisteredComponent { type_name: String },
+    #[error("scene contains the unregistered resource `{type_name}`. consider adding `#[reflect(Resource)]` to your type")]
+    UnregisteredResource { type_name: String },
     #[error("scene contains the unregistered type `{type_name}`. consider registering the type using `app.register_type::<T>()`")]
     UnregisteredType { type_name: String },
     #[error("scene does not exist")]
@@ -102,6 +118,7 @@ impl SceneSpawner {
                 self.despawn_instance_sync(world, &instance_id);
             }
         }
+        self.scene_snapshots.remove(&scene_handle);
         Ok(())
     }
 
@@ -119,10 +136,15 @@ impl SceneSpawner {
         scene_handle: &Handle<DynamicScene>,
     ) -> Result<(), SceneSpawnError> {
         let mut entity_map = EntityMap::default();
-        Self::spawn_dynamic_internal(world, scene_handle, &mut entity_map)?;
+        let scene = self.spawn_dynamic_internal(world, scene_handle, &mut entity_map)?;
         let instance_id = InstanceId::new();
-        self.spawned_instances
-            .insert(instance_id, InstanceInfo { entity_map });
+        self.spawned_instances.insert(
+            instance_id,
+            InstanceInfo {
+                entity_map,
+                scene: Some(scene),
+            },
+        );
         let spawned = self
             .spawned_dynamic_scenes
             .entry(scene_handle.clone())
@@ -131,11 +153,14 @@ impl SceneSpawner {
         Ok(())
     }
 
+    /// Writes a dynamic scene to the world, returning the version of the scene it was written
+    /// with.
     fn spawn_dynamic_internal(
+        &mut self,
         world: &mut World,
         scene_handle: &Handle<DynamicScene>,
         entity_map: &mut EntityMap,
-    ) -> Result<(), SceneSpawnError> {
+    ) -> Result<Arc<DynamicScene>, SceneSpawnError> {
         world.resource_scope(|world, scenes: Mut<Assets<DynamicScene>>| {
             let scene =
                 scenes
@@ -143,7 +168,13 @@ impl SceneSpawner {
                     .ok_or_else(|| SceneSpawnError::NonExistentScene {
                         handle: scene_handle.clone_weak(),
                     })?;
-            scene.write_to_world(world, entity_map)
+            let snapshot = self
+                .scene_snapshots
+                .entry(scene_handle.clone_weak())
+                .or_insert_with(|| Arc::new(scene.clone_dynamic()))
+                .clone();
+            scene.write_to_world(world, entity_map)?;
+            Ok(snapshot)
         })
     }
 
@@ -163,6 +194,7 @@ impl SceneSpawner {
     ) -> Result<InstanceId, SceneSpawnError> {
         let mut instance_info = InstanceInfo {
             entity_map: EntityMap::default(),
+            scene: None,
         };
         let type_registry = world.resource::<TypeRegistryArc>().clone();
         let type_registry = type_registry.read();
@@ -174,6 +206,8 @@ impl SceneSpawner {
                         handle: scene_handle.clone(),
                     })?;
 
+            // The entities copied with each component type that maps entities
+            let mut entities_to_map: HashMap<TypeId, Vec<Entity>> = HashMap::default();
             for archetype in scene.world.archetypes().iter() {
                 for scene_entity in archetype.entities() {
                     let entity = *instance_info
@@ -187,26 +221,34 @@ impl SceneSpawner {
                             .get_info(component_id)
                             .expect("component_ids in archetypes should have ComponentInfo");
 
-                        let reflect_component = type_registry
+                        let registration = type_registry
                             .get(component_info.type_id().unwrap())
                             .ok_or_else(|| SceneSpawnError::UnregisteredType {
                                 type_name: component_info.name().to_string(),
-                            })
-                            .and_then(|registration| {
-                                registration.data::<ReflectComponent>().ok_or_else(|| {
-                                    SceneSpawnError::UnregisteredComponent {
-                                        type_name: component_info.name().to_string(),
-                                    }
-                                })
+                            })?;
+                        let reflect_component = registration
+                            .data::<ReflectComponent>()
+                            .ok_or_else(|| SceneSpawnError::UnregisteredComponent {
+                                type_name: component_info.name().to_string(),
                             })?;
                         reflect_component.copy(&scene.world, world, *scene_entity, entity);
+
+                        if registration.data::<ReflectMapEntities>().is_some() {
+                            entities_to_map
+                                .entry(registration.type_id())
+                                .or_default()
+                                .push(entity);
+                        }
                     }
                 }
             }
-            for registration in type_registry.iter() {
-                if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
+            for (type_id, entities) in entities_to_map {
+                if let Some(map_entities_reflect) = type_registry
+                    .get(type_id)
+                    .and_then(|registration| registration.data::<ReflectMapEntities>())
+                {
                     map_entities_reflect
-                        .map_entities(world, &instance_info.entity_map)
+                        .map_specific_entities(world, &instance_info.entity_map, &entities)
                         .unwrap();
                 }
             }
@@ -220,21 +262,57 @@ impl SceneSpawner {
         })
     }
 
+    /// Updates the spawned instances of the given dynamic scenes to their current version.
+    ///
+    /// Only the changes since the version each instance was written with are written, see
+    /// [`DynamicScene::write_changes_to_world`], and a [`SceneInstanceUpdated`] event is sent for
+    /// each updated instance.
     pub fn update_spawned_scenes(
         &mut self,
         world: &mut World,
         scene_handles: &[Handle<DynamicScene>],
     ) -> Result<(), SceneSpawnError> {
         for scene_handle in scene_handles {
-            if let Some(spawned_instances) = self.spawned_dynamic_scenes.get(scene_handle) {
+            let spawned_instances = match self.spawned_dynamic_scenes.get(scene_handle) {
+                Some(spawned_instances) => spawned_instances,
+                None => continue,
+            };
+            let mut updated_instances = Vec::new();
+            world.resource_scope(|world, scenes: Mut<Assets<DynamicScene>>| {
+                let scene =
+                    scenes
+                        .get(scene_handle)
+                        .ok_or_else(|| SceneSpawnError::NonExistentScene {
+                            handle: scene_handle.clone_weak(),
+                        })?;
+                let snapshot = Arc::new(scene.clone_dynamic());
                 for instance_id in spawned_instances {
-                    if let Some(instance_info) = self.spawned_instances.get_mut(instance_id) {
-                        Self::spawn_dynamic_internal(
+                    let instance_info = match self.spawned_instances.get_mut(instance_id) {
+                        Some(instance_info) => instance_info,
+                        None => continue,
+                    };
+                    match &instance_info.scene {
+                        Some(previous) => scene.write_changes_to_world(
+                            previous,
                             world,
-                            scene_handle,
                             &mut instance_info.entity_map,
-                        )?;
+                        )?,
+                        None => scene.write_to_world(world, &mut instance_info.entity_map)?,
                     }
+                    instance_info.scene = Some(snapshot.clone());
+                    updated_instances.push(*instance_id);
+                }
+                self.scene_snapshots
+                    .insert(scene_handle.clone_weak(), snapshot);
+                Ok(())
+            })?;
+
+            if let Some(mut events) = world.get_resource_mut::<Events<SceneInstanceUpdated>>() {
+                for instance_id in updated_instances {
+                    events.send(SceneInstanceUpdated {
+                        instance_id,
+                        scene: scene_handle.clone_weak(),
+                    });
                 }
             }
         }
@@ -264,10 +342,15 @@ impl SceneSpawner {
         for (scene_handle, instance_id) in scenes_to_spawn {
             let mut entity_map = EntityMap::default();
 
-            match Self::spawn_dynamic_internal(world, &scene_handle, &mut entity_map) {
-                Ok(_) => {
-                    self.spawned_instances
-                        .insert(instance_id, InstanceInfo { entity_map });
+            match self.spawn_dynamic_internal(world, &scene_handle, &mut entity_map) {
+                Ok(scene) => {
+                    self.spawned_instances.insert(
+                        instance_id,
+                        InstanceInfo {
+                            entity_map,
+                            scene: Some(scene),
+                        },
+                    );
                     let spawned = self
                         .spawned_dynamic_scenes
                         .entry(scene_handle.clone())
@@ -354,6 +437,8 @@ pub fn scene_spawner_system(world: &mut World) {
             .iter(scene_asset_events)
         {
             if let AssetEvent::Modified { handle } = event {
+                // instances spawned from now on are written with the modified scene
+                scene_spawner.scene_snapshots.remove(handle);
                 if scene_spawner.spawned_dynamic_scenes.contains_key(handle) {
                     updated_spawned_scenes.push(handle.clone_weak());
                 }
@@ -0,0 +1,340 @@
+use crate::{DynamicScene, DynamicSceneBuilder, SceneLoaderError};
+use bevy_ecs::{
+    entity::{Entity, EntityMap},
+    world::World,
+};
+use bevy_hierarchy::{BuildWorldChildren, Children, Parent};
+use bevy_reflect::TypeRegistryArc;
+use bevy_utils::HashMap;
+use serde::{Deserialize, Serialize};
+
+/// A scene file whose references to other scenes are not resolved yet.
+///
+/// A scene may derive from a `base` scene, like a prefab variant. Its entities with the id of a
+/// base scene entity override the components of that entity: components of a type the base
+/// entity already has are applied to it, so only the overridden fields need to be listed, and
+/// other components are added. Entities with new ids are added to the scene.
+///
+/// Other scenes can also be nested in a scene as [`NestedScene`] instances.
+///
+/// Scene files are resolved by the [`SceneLoader`](crate::SceneLoader):
+///
+/// ```ron
+/// (
+///     base: "enemies/goblin.scn.ron",
+///     entities: [
+///         (
+///             entity: 0,
+///             components: [
+///                 {
+///                     "type": "game::Health",
+///                     "struct": {
+///                         "max": {
+///                             "type": "u32",
+///                             "value": 200,
+///                         },
+///                     },
+///                 },
+///             ],
+///         ),
+///     ],
+///     instances: [
+///         (scene: "weapons/axe.scn.ron", parent: Some(0)),
+///     ],
+/// )
+/// ```
+#[derive(Default)]
+pub struct SceneTemplate {
+    /// The path of the scene this scene derives from.
+    pub base: Option<String>,
+    /// The resources and entities of the scene, overriding those of the base scene.
+    pub scene: DynamicScene,
+    /// The scenes nested in this scene.
+    pub instances: Vec<NestedScene>,
+}
+
+/// An instance of a scene nested in a [`SceneTemplate`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NestedScene {
+    /// The path of the nested scene.
+    pub scene: String,
+    /// The id of the scene entity the root entities of the instance are parented to.
+    #[serde(default)]
+    pub parent: Option<u32>,
+}
+
+impl SceneTemplate {
+    /// Returns `true` if the scene references other scenes.
+    pub fn has_references(&self) -> bool {
+        self.base.is_some() || !self.instances.is_empty()
+    }
+
+    /// Returns the paths of the scenes referenced by this scene.
+    pub fn references(&self) -> impl Iterator<Item = &str> {
+        self.base
+            .iter()
+            .chain(self.instances.iter().map(|instance| &instance.scene))
+            .map(String::as_str)
+    }
+
+    /// Composes this scene with its resolved `base` scene and nested `instances`, given in the
+    /// order of [`SceneTemplate::instances`].
+    ///
+    /// The entities of this scene and of its base keep their ids, while the entities of each
+    /// nested instance get new ids following them.
+    pub fn compose(
+        self,
+        base: Option<&DynamicScene>,
+        instances: &[DynamicScene],
+        type_registry: &TypeRegistryArc,
+    ) -> Result<DynamicScene, SceneLoaderError> {
+        let mut world = World::new();
+        world.insert_resource(type_registry.clone());
+
+        // Spawn the entities with their scene id, so that the composed scene keeps them.
+        let mut entity_map = EntityMap::default();
+        for scene_entity in base
+            .iter()
+            .flat_map(|base| &base.entities)
+            .chain(&self.scene.entities)
+        {
+            let entity = Entity::from_raw(scene_entity.entity);
+            entity_map.insert(entity, spawn_at(&mut world, entity));
+        }
+
+        if let Some(base) = base {
+            base.write_to_world(&mut world, &mut entity_map)?;
+        }
+
+        let mut next_id = entity_map
+            .values()
+            .map(|entity| entity.id() + 1)
+            .max()
+            .unwrap_or(0);
+        let mut instance_roots = Vec::new();
+        for (instance, instance_scene) in self.instances.iter().zip(instances) {
+            let mut instance_map = EntityMap::default();
+            for scene_entity in &instance_scene.entities {
+                let entity = Entity::from_raw(next_id + scene_entity.entity);
+                instance_map.insert(
+                    Entity::from_raw(scene_entity.entity),
+                    spawn_at(&mut world, entity),
+                );
+            }
+            next_id = instance_map
+                .values()
+                .map(|entity| entity.id() + 1)
+                .max()
+                .unwrap_or(next_id)
+                .max(next_id);
+            instance_scene.write_to_world(&mut world, &mut instance_map)?;
+
+            if let Some(parent) = instance.parent {
+                let roots: Vec<Entity> = instance_map
+                    .values()
+                    .filter(|entity| world.get::<Parent>(*entity).is_none())
+                    .collect();
+                instance_roots.push((parent, roots));
+            }
+        }
+
+        // The scene itself is written last, to override its base and nested instances.
+        let base_children = children_of(&mut world);
+        self.scene.write_to_world(&mut world, &mut entity_map)?;
+
+        for (parent, roots) in instance_roots {
+            let parent = entity_map
+                .get(Entity::from_raw(parent))
+                .map_err(|_| SceneLoaderError::NonExistentParent { entity: parent })?;
+            world.entity_mut(parent).push_children(&roots);
+        }
+        update_children(&mut world, base_children);
+
+        let mut scene =
+            DynamicSceneBuilder::from_world_with_type_registry(&world, type_registry.clone())
+                .extract_all_entities()
+                .extract_resources()
+                .build();
+        scene
+            .entities
+            .sort_by_key(|scene_entity| scene_entity.entity);
+        Ok(scene)
+    }
+}
+
+fn spawn_at(world: &mut World, entity: Entity) -> Entity {
+    world
+        .get_or_spawn(entity)
+        .expect("scene entities are spawned in an empty world")
+        .id()
+}
+
+fn children_of(world: &mut World) -> HashMap<Entity, Vec<Entity>> {
+    let mut query = world.query::<(Entity, &Children)>();
+    query
+        .iter(world)
+        .map(|(entity, children)| (entity, children.to_vec()))
+        .collect()
+}
+
+/// Updates the [`Children`] of each entity to match the [`Parent`] of the entities, which may have
+/// been overridden.
+///
+/// Children keep their order in `base_children`, and added children follow them.
+fn update_children(world: &mut World, base_children: HashMap<Entity, Vec<Entity>>) {
+    let mut children: HashMap<Entity, Vec<Entity>> = HashMap::default();
+    let mut query = world.query::<(Entity, &Parent)>();
+    for (entity, parent) in query.iter(world) {
+        children.entry(parent.get()).or_default().push(entity);
+    }
+
+    let mut previous_children = children_of(world);
+    for (&parent, parent_children) in &mut children {
+        previous_children.remove(&parent);
+        let base = base_children.get(&parent).map_or(&[][..], Vec::as_slice);
+        parent_children.sort_by_key(|child| {
+            let position = base.iter().position(|base_child| base_child == child);
+            (position.unwrap_or(usize::MAX), child.id())
+        });
+        if world.get_entity(parent).is_some() {
+            world
+                .entity_mut(parent)
+                .insert(Children::with(parent_children));
+        }
+    }
+    for (entity, _) in previous_children {
+        world.entity_mut(entity).remove::<Children>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::prelude::*;
+    use bevy_reflect::{DynamicStruct, Reflect};
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Stats {
+        health: u32,
+        speed: u32,
+    }
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Weapon;
+
+    /// A root with a child.
+    fn base(registry: &TypeRegistryArc) -> DynamicScene {
+        let mut world = World::new();
+        world
+            .spawn()
+            .insert(Stats {
+                health: 100,
+                speed: 1,
+            })
+            .with_children(|parent| {
+                parent.spawn().insert(Stats {
+                    health: 10,
+                    speed: 1,
+                });
+            });
+        DynamicScene::from_world(&world, registry)
+    }
+
+    #[test]
+    fn overrides_and_nested_scenes() {
+        let registry = type_registry![Stats, Weapon, Parent, Children];
+
+        // a weapon on the child of the base root, and a new entity parented to the root
+        let mut world = World::new();
+        world
+            .get_or_spawn(Entity::from_raw(1))
+            .unwrap()
+            .insert(Weapon);
+        let added = world
+            .get_or_spawn(Entity::from_raw(5))
+            .unwrap()
+            .insert(Weapon)
+            .id();
+        world
+            .get_or_spawn(Entity::from_raw(0))
+            .unwrap()
+            .push_children(&[added]);
+        let mut scene = DynamicScene::from_world(&world, &registry);
+        // only the health of the root is overridden
+        let mut health = DynamicStruct::default();
+        health.set_name(std::any::type_name::<Stats>().to_string());
+        health.insert("health", 200u32);
+        scene
+            .entities
+            .iter_mut()
+            .find(|scene_entity| scene_entity.entity == 0)
+            .unwrap()
+            .components
+            .push(Box::new(health));
+
+        let template = SceneTemplate {
+            base: Some("base.scn.ron".to_string()),
+            scene,
+            instances: vec![NestedScene {
+                scene: "base.scn.ron".to_string(),
+                parent: Some(5),
+            }],
+        };
+        let scene = template
+            .compose(Some(&base(&registry)), &[base(&registry)], &registry)
+            .unwrap();
+        let ids: Vec<u32> = scene.entities.iter().map(|entity| entity.entity).collect();
+        assert_eq!(ids, vec![0, 1, 5, 6, 7]);
+
+        let mut world = World::new();
+        world.insert_resource(registry);
+        let mut entity_map = EntityMap::default();
+        scene.write_to_world(&mut world, &mut entity_map).unwrap();
+        let entity = |id| entity_map.get(Entity::from_raw(id)).unwrap();
+        let parent = |world: &World, id| world.get::<Parent>(entity(id)).map(Parent::get);
+
+        assert_eq!(
+            world.get::<Stats>(entity(0)),
+            Some(&Stats {
+                health: 200,
+                speed: 1
+            })
+        );
+        assert!(world.get::<Weapon>(entity(1)).is_some());
+        assert_eq!(
+            world.get::<Stats>(entity(1)),
+            Some(&Stats {
+                health: 10,
+                speed: 1
+            })
+        );
+        assert_eq!(parent(&world, 1), Some(entity(0)));
+        assert_eq!(parent(&world, 5), Some(entity(0)));
+        assert_eq!(
+            &**world.get::<Children>(entity(0)).unwrap(),
+            &[entity(1), entity(5)]
+        );
+        // the nested instance root is parented to the added entity
+        assert_eq!(parent(&world, 6), Some(entity(5)));
+        assert_eq!(parent(&world, 7), Some(entity(6)));
+        assert_eq!(&**world.get::<Children>(entity(5)).unwrap(), &[entity(6)]);
+    }
+
+    #[test]
+    fn nested_scene_parent_must_exist() {
+        let registry = type_registry![Stats, Weapon, Parent, Children];
+        let template = SceneTemplate {
+            instances: vec![NestedScene {
+                scene: "base.scn.ron".to_string(),
+                parent: Some(3),
+            }],
+            ..Default::default()
+        };
+        assert!(matches!(
+            template.compose(None, &[base(&registry)], &registry),
+            Err(SceneLoaderError::NonExistentParent { entity: 3 })
+        ));
+    }
+}
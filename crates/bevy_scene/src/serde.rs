@@ -1,14 +1,24 @@
-use crate::{DynamicEntity, DynamicScene};
+use crate::{
+    migration::{migrate_scene, scene_versions},
+    DynamicEntity, DynamicScene, NestedScene, SceneTemplate,
+};
 use anyhow::Result;
+#[cfg(feature = "json_schema")]
+use bevy_ecs::reflect::{ReflectComponent, ReflectResource};
+#[cfg(feature = "json_schema")]
+use bevy_reflect::serde::{registry_schema, schema_ref};
 use bevy_reflect::{
     serde::{ReflectDeserializer, ReflectSerializer},
     Reflect, TypeRegistry, TypeRegistryArc,
 };
+use bevy_utils::HashMap;
 use serde::{
     de::{DeserializeSeed, Error, MapAccess, SeqAccess, Visitor},
     ser::{SerializeSeq, SerializeStruct},
     Deserialize, Serialize,
 };
+#[cfg(feature = "json_schema")]
+use serde_json::{json, Value};
 
 pub struct SceneSerializer<'a> {
     pub scene: &'a DynamicScene,
@@ -26,8 +36,44 @@ impl<'a> Serialize for SceneSerializer<'a> {
     where
         S: serde::Serializer,
     {
-        let mut state = serializer.serialize_seq(Some(self.scene.entities.len()))?;
-        for entity in &self.scene.entities {
+        let versions = scene_versions(self.scene, &self.registry.read());
+        let len = if versions.is_empty() { 2 } else { 3 };
+        let mut state = serializer.serialize_struct(SCENE_STRUCT, len)?;
+        if versions.is_empty() {
+            state.skip_field(SCENE_FIELD_VERSIONS)?;
+        } else {
+            state.serialize_field(SCENE_FIELD_VERSIONS, &versions)?;
+        }
+        state.serialize_field(
+            SCENE_FIELD_RESOURCES,
+            &ComponentsSerializer {
+                components: &self.scene.resources,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field(
+            SCENE_FIELD_ENTITIES,
+            &EntitiesSerializer {
+                entities: &self.scene.entities,
+                registry: self.registry,
+            },
+        )?;
+        state.end()
+    }
+}
+
+pub struct EntitiesSerializer<'a> {
+    pub entities: &'a [DynamicEntity],
+    pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> Serialize for EntitiesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.entities.len()))?;
+        for entity in self.entities {
             state.serialize_element(&EntitySerializer {
                 entity,
                 registry: self.registry,
@@ -92,10 +138,171 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneDeserializer<'a> {
     where
         D: serde::Deserializer<'de>,
     {
-        Ok(DynamicScene {
-            entities: deserializer.deserialize_seq(SceneEntitySeqVisitor {
+        let template = SceneTemplateDeserializer {
+            type_registry: self.type_registry,
+        }
+        .deserialize(deserializer)?;
+        if template.has_references() {
+            return Err(Error::custom(
+                "the scene references other scenes, which only the `SceneLoader` resolves",
+            ));
+        }
+        Ok(template.scene)
+    }
+}
+
+/// Deserializes a scene file, without resolving the scenes it references.
+pub struct SceneTemplateDeserializer<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SceneTemplateDeserializer<'a> {
+    type Value = SceneTemplate;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        // scenes without resources may also be a list of entities
+        deserializer.deserialize_any(SceneVisitor {
+            type_registry: self.type_registry,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum SceneField {
+    Base,
+    Versions,
+    Resources,
+    Entities,
+    Instances,
+}
+
+pub const SCENE_STRUCT: &str = "Scene";
+pub const SCENE_FIELD_BASE: &str = "base";
+pub const SCENE_FIELD_VERSIONS: &str = "versions";
+pub const SCENE_FIELD_RESOURCES: &str = "resources";
+pub const SCENE_FIELD_ENTITIES: &str = "entities";
+pub const SCENE_FIELD_INSTANCES: &str = "instances";
+
+struct SceneVisitor<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for SceneVisitor<'a> {
+    type Value = SceneTemplate;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("scene struct or list of entities")
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        // lists of entities do not record versions, their types are at version 0
+        let mut scene = DynamicScene {
+            resources: Vec::new(),
+            entities: SceneEntitySeqVisitor {
                 type_registry: self.type_registry,
-            })?,
+            }
+            .visit_seq(seq)?,
+        };
+        migrate_scene(&mut scene, &HashMap::default(), self.type_registry)
+            .map_err(Error::custom)?;
+        Ok(SceneTemplate {
+            scene,
+            ..Default::default()
+        })
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut base = None;
+        let mut versions = None;
+        let mut resources = None;
+        let mut entities = None;
+        let mut instances = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                SceneField::Base => {
+                    if base.is_some() {
+                        return Err(Error::duplicate_field(SCENE_FIELD_BASE));
+                    }
+                    base = Some(map.next_value::<String>()?);
+                }
+                SceneField::Versions => {
+                    if versions.is_some() {
+                        return Err(Error::duplicate_field(SCENE_FIELD_VERSIONS));
+                    }
+                    versions = Some(map.next_value::<HashMap<String, u32>>()?);
+                }
+                SceneField::Resources => {
+                    if resources.is_some() {
+                        return Err(Error::duplicate_field(SCENE_FIELD_RESOURCES));
+                    }
+                    resources = Some(map.next_value_seed(ComponentVecDeserializer {
+                        registry: self.type_registry,
+                    })?);
+                }
+                SceneField::Entities => {
+                    if entities.is_some() {
+                        return Err(Error::duplicate_field(SCENE_FIELD_ENTITIES));
+                    }
+                    entities = Some(map.next_value_seed(SceneEntitySeqDeserializer {
+                        type_registry: self.type_registry,
+                    })?);
+                }
+                SceneField::Instances => {
+                    if instances.is_some() {
+                        return Err(Error::duplicate_field(SCENE_FIELD_INSTANCES));
+                    }
+                    instances = Some(map.next_value::<Vec<NestedScene>>()?);
+                }
+            }
+        }
+
+        // scenes deriving from a base scene may only add instances
+        let entities = match entities {
+            Some(entities) => entities,
+            None if base.is_some() => Vec::new(),
+            None => return Err(Error::missing_field(SCENE_FIELD_ENTITIES)),
+        };
+        let mut scene = DynamicScene {
+            resources: resources.unwrap_or_default(),
+            entities,
+        };
+        migrate_scene(
+            &mut scene,
+            &versions.unwrap_or_default(),
+            self.type_registry,
+        )
+        .map_err(Error::custom)?;
+        Ok(SceneTemplate {
+            base,
+            scene,
+            instances: instances.unwrap_or_default(),
+        })
+    }
+}
+
+struct SceneEntitySeqDeserializer<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SceneEntitySeqDeserializer<'a> {
+    type Value = Vec<DynamicEntity>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(SceneEntitySeqVisitor {
+            type_registry: self.type_registry,
         })
     }
 }
@@ -249,3 +456,227 @@ impl<'a, 'de> Visitor<'de> for ComponentSeqVisitor<'a> {
         Ok(dynamic_properties)
     }
 }
+
+/// Returns a JSON Schema document validating scene files, as written by [`SceneSerializer`], for
+/// the component and resource types of the `type_registry`.
+///
+/// External editors can use it to validate and complete scene files. The schemas of the
+/// registered types are defined like in [`registry_schema`].
+///
+/// Requires the `json_schema` feature.
+#[cfg(feature = "json_schema")]
+pub fn scene_schema(type_registry: &TypeRegistry) -> Value {
+    let mut components = Vec::new();
+    let mut resources = Vec::new();
+    for registration in type_registry.iter() {
+        if registration.data::<ReflectComponent>().is_some() {
+            components.push(registration.type_name());
+        }
+        if registration.data::<ReflectResource>().is_some() {
+            resources.push(registration.type_name());
+        }
+    }
+    components.sort_unstable();
+    resources.sort_unstable();
+    let any_of = |type_names: Vec<&str>| {
+        json!({
+            "type": "array",
+            "items": { "anyOf": type_names.into_iter().map(schema_ref).collect::<Vec<_>>() },
+        })
+    };
+
+    let mut schema = registry_schema(type_registry);
+    let document = schema.as_object_mut().unwrap();
+    document.remove("anyOf");
+    document.insert("type".to_string(), json!("object"));
+    document.insert(
+        "properties".to_string(),
+        json!({
+            SCENE_FIELD_BASE: { "type": "string" },
+            SCENE_FIELD_VERSIONS: {
+                "type": "object",
+                "additionalProperties": { "type": "integer", "minimum": 0 },
+            },
+            SCENE_FIELD_RESOURCES: any_of(resources),
+            SCENE_FIELD_ENTITIES: {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        ENTITY_FIELD_ENTITY: { "type": "integer", "minimum": 0 },
+                        ENTITY_FIELD_COMPONENTS: any_of(components),
+                    },
+                    "required": [ENTITY_FIELD_ENTITY, ENTITY_FIELD_COMPONENTS],
+                    "additionalProperties": false,
+                },
+            },
+            SCENE_FIELD_INSTANCES: {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "scene": { "type": "string" },
+                        "parent": { "type": ["integer", "null"], "minimum": 0 },
+                    },
+                    "required": ["scene"],
+                    "additionalProperties": false,
+                },
+            },
+        }),
+    );
+    document.insert("additionalProperties".to_string(), json!(false));
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SceneFilter;
+    use bevy_ecs::{entity::EntityMap, prelude::*, reflect::ReflectResource};
+    use bevy_reflect::{DynamicStruct, Reflect};
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Health(u32);
+
+    #[derive(Reflect, Default, Debug, PartialEq)]
+    #[reflect(Resource)]
+    struct Score(u32);
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Stats {
+        current: u32,
+    }
+
+    fn deserialize(registry: &TypeRegistryArc, input: &str) -> DynamicScene {
+        let mut deserializer = ron::de::Deserializer::from_str(input).unwrap();
+        SceneDeserializer {
+            type_registry: &registry.read(),
+        }
+        .deserialize(&mut deserializer)
+        .unwrap()
+    }
+
+    #[test]
+    fn migrate_renamed_type() {
+        let registry = type_registry![Health, Score];
+        {
+            let mut registry = registry.write();
+            registry.register::<Stats>();
+            registry.register_alias("game::HitPoints", std::any::TypeId::of::<Stats>());
+            let registration = registry.get_with_name_mut("game::HitPoints").unwrap();
+            registration.set_version(1);
+            registration.add_migration(0, |mut value| {
+                if let Some(stats) = value.downcast_mut::<DynamicStruct>() {
+                    stats.rename_field("hp", "current");
+                }
+                value
+            });
+        }
+
+        // a scene saved before `game::HitPoints` was renamed, without versions
+        let scene = deserialize(
+            &registry,
+            r#"(
+                entities: [
+                    (
+                        entity: 0,
+                        components: [
+                            {
+                                "type": "game::HitPoints",
+                                "struct": {
+                                    "hp": {
+                                        "type": "u32",
+                                        "value": 12,
+                                    },
+                                },
+                            },
+                        ],
+                    ),
+                ],
+            )"#,
+        );
+        assert_eq!(
+            scene.entities[0].components[0].type_name(),
+            std::any::type_name::<Stats>()
+        );
+        let mut world = World::new();
+        world.insert_resource(registry.clone());
+        scene
+            .write_to_world(&mut world, &mut EntityMap::default())
+            .unwrap();
+        let mut query = world.query::<&Stats>();
+        assert_eq!(query.single(&world), &Stats { current: 12 });
+
+        // the current version is recorded, so the scene is not migrated again
+        let ron = scene.serialize_ron(&registry).unwrap();
+        assert!(ron.contains(SCENE_FIELD_VERSIONS));
+        let scene = deserialize(&registry, &ron);
+        let mut world = World::new();
+        world.insert_resource(registry);
+        scene
+            .write_to_world(&mut world, &mut EntityMap::default())
+            .unwrap();
+        let mut query = world.query::<&Stats>();
+        assert_eq!(query.single(&world), &Stats { current: 12 });
+    }
+
+    #[cfg(feature = "json_schema")]
+    #[test]
+    fn schema() {
+        let registry = type_registry![Health, Score];
+        let schema = scene_schema(&registry.read());
+        let properties = &schema["properties"];
+        assert_eq!(
+            properties["resources"]["items"]["anyOf"],
+            json!([schema_ref(std::any::type_name::<Score>())])
+        );
+        assert_eq!(
+            properties["entities"]["items"]["properties"]["components"]["items"]["anyOf"],
+            json!([schema_ref(std::any::type_name::<Health>())])
+        );
+        assert!(schema["$defs"]
+            .get(std::any::type_name::<Health>())
+            .is_some());
+    }
+
+    #[test]
+    fn resources_round_trip() {
+        let registry = type_registry![Health, Score];
+        let mut world = World::new();
+        world.insert_resource(Score(42));
+        world.spawn().insert(Health(7));
+
+        let scene = DynamicScene::from_world_with_resource_filter(
+            &world,
+            &registry,
+            &SceneFilter::allow_all(),
+        );
+        let ron = scene.serialize_ron(&registry).unwrap();
+        let scene = deserialize(&registry, &ron);
+        assert_eq!(scene.resources.len(), 1);
+        assert_eq!(scene.entities.len(), 1);
+
+        let mut world = World::new();
+        world.insert_resource(registry.clone());
+        scene
+            .write_to_world(&mut world, &mut EntityMap::default())
+            .unwrap();
+        assert_eq!(world.resource::<Score>(), &Score(42));
+        let mut query = world.query::<&Health>();
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), vec![&Health(7)]);
+
+        let filter = SceneFilter::allow_all().deny::<Score>();
+        let scene = DynamicScene::from_world_with_resource_filter(&world, &registry, &filter);
+        assert!(scene.resources.is_empty());
+    }
+
+    #[test]
+    fn entity_list() {
+        let registry = type_registry![Health, Score];
+        let scene = deserialize(&registry, "[(entity: 3, components: [])]");
+        assert!(scene.resources.is_empty());
+        assert_eq!(scene.entities[0].entity, 3);
+    }
+}
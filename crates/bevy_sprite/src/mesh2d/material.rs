@@ -408,6 +408,7 @@ fn extract_materials_2d<M: Material2d>(
                 changed_assets.remove(handle);
                 removed.push(handle.clone_weak());
             }
+            AssetEvent::LoadedWithDependencies { .. } => {}
         }
     }
 
@@ -237,7 +237,7 @@ where
     /// results (in batch order).*
     ///
     /// See [`Iterator::fold()`](https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.fold)
-    fn fold<C, F, D>(mut self, pool: &TaskPool, init: C, f: F) -> Vec<C>
+    fn fold<C, F>(mut self, pool: &TaskPool, init: C, f: F) -> Vec<C>
     where
         F: FnMut(C, BatchIter::Item) -> C + Send + Sync + Clone,
         C: Clone + Send + Sync + 'static,
@@ -251,6 +251,30 @@ where
         })
     }
 
+    /// Reduces the items to a single one by repeatedly applying a function, first within each
+    /// batch then across the results of the batches.
+    ///
+    /// *Note that `f` must be associative for the result to be deterministic, as the items are
+    /// not combined in a fixed order.*
+    ///
+    /// See [`Iterator::reduce()`](https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.reduce)
+    fn reduce<F>(mut self, pool: &TaskPool, f: F) -> Option<BatchIter::Item>
+    where
+        F: FnMut(BatchIter::Item, BatchIter::Item) -> BatchIter::Item + Send + Sync + Clone,
+        BatchIter::Item: Send + 'static,
+    {
+        let reduce = f.clone();
+        pool.scope(|s| {
+            while let Some(batch) = self.next_batch() {
+                let newf = f.clone();
+                s.spawn(async move { batch.reduce(newf) });
+            }
+        })
+        .into_iter()
+        .flatten()
+        .reduce(reduce)
+    }
+
     /// Tests if every element of the parallel iterator matches a predicate.
     ///
     /// *Note that all is **not** short circuiting.*
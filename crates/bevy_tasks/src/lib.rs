@@ -5,7 +5,10 @@ mod slice;
 pub use slice::{ParallelSlice, ParallelSliceMut};
 
 mod task;
-pub use task::Task;
+pub use task::{Task, TaskPriority};
+
+mod metrics;
+pub use metrics::TaskPoolMetrics;
 
 #[cfg(not(target_arch = "wasm32"))]
 mod task_pool;
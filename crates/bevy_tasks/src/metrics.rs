@@ -0,0 +1,24 @@
+use std::time::Duration;
+
+/// A snapshot of the activity of a [`TaskPool`](crate::TaskPool), returned by
+/// [`TaskPool::metrics`](crate::TaskPool::metrics).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TaskPoolMetrics {
+    /// The number of threads owned by the pool.
+    pub thread_count: usize,
+    /// The number of spawned tasks that have not started running yet.
+    pub queued_tasks: usize,
+    /// The number of tasks that have started running but have not completed yet, including the
+    /// ones waiting on something else.
+    pub active_tasks: usize,
+    /// The total time spent polling the tasks of the pool since it was created, across the
+    /// threads of the pool.
+    ///
+    /// Tasks run by other threads while they wait on the pool, in [`TaskPool::scope`] or
+    /// [`TaskPool::block_on`], are not counted, so this is at most the elapsed time multiplied by
+    /// [`thread_count`](Self::thread_count).
+    ///
+    /// [`TaskPool::scope`]: crate::TaskPool::scope
+    /// [`TaskPool::block_on`]: crate::TaskPool::block_on
+    pub busy_time: Duration,
+}
@@ -4,6 +4,8 @@ use std::{
     sync::{Arc, Mutex},
 };
 
+use crate::{TaskPoolMetrics, TaskPriority};
+
 /// Used to create a TaskPool
 #[derive(Debug, Default, Clone)]
 pub struct TaskPoolBuilder {}
@@ -56,6 +58,14 @@ impl TaskPool {
         1
     }
 
+    /// Metrics are not tracked on the single threaded task pool
+    pub fn metrics(&self) -> TaskPoolMetrics {
+        TaskPoolMetrics {
+            thread_count: 1,
+            ..Default::default()
+        }
+    }
+
     /// Allows spawning non-`static futures on the thread pool. The function takes a callback,
     /// passing a scope object into it. The scope object provided to the callback can be used
     /// to spawn tasks. This function will await the completion of all tasks before returning.
@@ -113,6 +123,30 @@ impl TaskPool {
     {
         self.spawn(future)
     }
+
+    /// Spawns a static future on the JS event loop. Priorities are ignored on the single
+    /// threaded task pool, so this is the same as [`TaskPool::spawn`].
+    pub fn spawn_with_priority<T>(
+        &self,
+        _priority: TaskPriority,
+        future: impl Future<Output = T> + 'static,
+    ) -> FakeTask
+    where
+        T: 'static,
+    {
+        self.spawn(future)
+    }
+
+    /// Spawns the future created by `make_future` on the JS event loop. There is only one
+    /// thread on the single threaded task pool, so `thread_index` is ignored.
+    pub fn spawn_pinned<T, F, Fut>(&self, _thread_index: usize, make_future: F) -> FakeTask
+    where
+        F: FnOnce() -> Fut + 'static,
+        Fut: Future<Output = T> + 'static,
+        T: 'static,
+    {
+        self.spawn(async move { make_future().await })
+    }
 }
 
 #[derive(Debug)]
@@ -145,6 +179,18 @@ impl<'scope, T: Send + 'scope> Scope<'scope, T> {
         self.spawn_local(f);
     }
 
+    /// Spawns a scoped future onto the thread-local executor. Priorities are ignored on the
+    /// single threaded task pool, so this is the same as [`Scope::spawn`].
+    ///
+    /// For more information, see [`TaskPool::scope`].
+    pub fn spawn_with_priority<Fut: Future<Output = T> + 'scope + Send>(
+        &mut self,
+        _priority: TaskPriority,
+        f: Fut,
+    ) {
+        self.spawn_local(f);
+    }
+
     /// Spawns a scoped future onto the thread-local executor. The scope *must* outlive
     /// the provided future. The results of the future will be returned as a part of
     /// [`TaskPool::scope`]'s return value.
@@ -49,3 +49,18 @@ impl<T> Future for Task<T> {
         Pin::new(&mut self.0).poll(cx)
     }
 }
+
+/// The priority a task is spawned with on a [`TaskPool`](crate::TaskPool).
+///
+/// Threads of a pool always run a runnable task of the highest priority available. Lower priority
+/// tasks may be starved while higher priority ones keep being spawned.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TaskPriority {
+    /// Work that should run as soon as possible, such as tasks blocking the current frame.
+    High,
+    /// The priority of tasks spawned with [`TaskPool::spawn`](crate::TaskPool::spawn).
+    #[default]
+    Normal,
+    /// Background work, run when no higher priority task is runnable.
+    Low,
+}
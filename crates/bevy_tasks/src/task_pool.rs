@@ -1,15 +1,20 @@
 use std::{
+    cell::Cell,
     future::Future,
     mem,
+    panic::AssertUnwindSafe,
     pin::Pin,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
     thread::{self, JoinHandle},
+    time::{Duration, Instant},
 };
 
-use futures_lite::{future, pin};
-
-use crate::Task;
+use futures_lite::{future, pin, FutureExt};
 
+use crate::{Task, TaskPoolMetrics, TaskPriority};
 /// Used to create a [`TaskPool`]
 #[derive(Debug, Default, Clone)]
 #[must_use]
@@ -60,25 +65,138 @@ impl TaskPoolBuilder {
     }
 }
 
+/// The executors of a [`TaskPool`], one per [`TaskPriority`].
+#[derive(Debug, Default)]
+struct Executors<'a> {
+    high: async_executor::Executor<'a>,
+    normal: async_executor::Executor<'a>,
+    low: async_executor::Executor<'a>,
+}
+
+impl<'a> Executors<'a> {
+    fn get(&self, priority: TaskPriority) -> &async_executor::Executor<'a> {
+        match priority {
+            TaskPriority::High => &self.high,
+            TaskPriority::Normal => &self.normal,
+            TaskPriority::Low => &self.low,
+        }
+    }
+
+    /// Waits for a runnable task and runs it, favoring higher priorities.
+    async fn tick(&self) {
+        // `or` polls the first future first, so a runnable high priority task is always
+        // picked before the others.
+        self.high
+            .tick()
+            .or(self.normal.tick())
+            .or(self.low.tick())
+            .await;
+    }
+
+    /// Runs tasks on the current thread until `future` completes.
+    async fn run<T>(&self, future: impl Future<Output = T>) -> T {
+        future
+            .or(async {
+                loop {
+                    for _ in 0..200 {
+                        self.tick().await;
+                    }
+                    // Give `future` a chance to complete.
+                    future::yield_now().await;
+                }
+            })
+            .await
+    }
+}
+
+/// A job sent to a specific thread of a [`TaskPool`] by [`TaskPool::spawn_pinned`].
+type PinnedJob = Box<dyn FnOnce(&async_executor::LocalExecutor<'static>) + Send>;
+
+#[derive(Debug, Default)]
+struct MetricsCounters {
+    queued: AtomicUsize,
+    active: AtomicUsize,
+    busy_nanos: AtomicU64,
+}
+
+struct QueuedGuard(Arc<MetricsCounters>);
+
+impl Drop for QueuedGuard {
+    fn drop(&mut self) {
+        self.0.queued.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+struct ActiveGuard(Arc<MetricsCounters>);
+
+impl Drop for ActiveGuard {
+    fn drop(&mut self) {
+        self.0.active.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Wraps `future` so that it is accounted for in the [`TaskPoolMetrics`] of its pool.
+fn instrument<'a, T: 'a>(
+    counters: &Arc<MetricsCounters>,
+    future: impl Future<Output = T> + 'a,
+) -> impl Future<Output = T> + 'a {
+    counters.queued.fetch_add(1, Ordering::Relaxed);
+    let queued = QueuedGuard(Arc::clone(counters));
+    async move {
+        let counters = Arc::clone(&queued.0);
+        drop(queued);
+        counters.active.fetch_add(1, Ordering::Relaxed);
+        let _active = ActiveGuard(Arc::clone(&counters));
+
+        pin!(future);
+        future::poll_fn(|cx| {
+            // Only the time spent on the threads of the pool is counted, so that a thread waiting
+            // on the pool, which also runs its tasks, does not push it above 100% busy.
+            if !TaskPool::is_worker_of(&counters) {
+                return future.as_mut().poll(cx);
+            }
+            let start = Instant::now();
+            let poll = future.as_mut().poll(cx);
+            counters
+                .busy_nanos
+                .fetch_add(start.elapsed().as_nanos() as u64, Ordering::Relaxed);
+            poll
+        })
+        .await
+    }
+}
+
 /// A thread pool for executing tasks. Tasks are futures that are being automatically driven by
 /// the pool on threads owned by the pool.
+///
+/// Tasks can be spawned with a [`TaskPriority`]: the threads of the pool always pick a runnable
+/// task of the highest priority available.
 #[derive(Debug)]
 pub struct TaskPool {
-    /// The executor for the pool
+    /// The executors for the pool
     ///
-    /// This has to be separate from TaskPoolInner because we have to create an Arc<Executor> to
+    /// This has to be separate from TaskPoolInner because we have to create an Arc<Executors> to
     /// pass into the worker threads, and we must create the worker threads before we can create
     /// the Vec<Task<T>> contained within TaskPoolInner
-    executor: Arc<async_executor::Executor<'static>>,
+    executors: Arc<Executors<'static>>,
+    counters: Arc<MetricsCounters>,
 
     /// Inner state of the pool
     threads: Vec<JoinHandle<()>>,
+    pinned_senders: Vec<async_channel::Sender<PinnedJob>>,
     shutdown_tx: async_channel::Sender<()>,
 }
 
 impl TaskPool {
     thread_local! {
         static LOCAL_EXECUTOR: async_executor::LocalExecutor<'static> = async_executor::LocalExecutor::new();
+        /// The address of the counters of the pool owning the current thread, or 0.
+        static WORKER_OF: Cell<usize> = const { Cell::new(0) };
+    }
+
+    /// Returns `true` if the current thread belongs to the pool with the given counters.
+    fn is_worker_of(counters: &Arc<MetricsCounters>) -> bool {
+        TaskPool::WORKER_OF.with(|worker_of| worker_of.get() == Arc::as_ptr(counters) as usize)
     }
 
     /// Create a `TaskPool` with the default configuration.
@@ -93,14 +211,19 @@ impl TaskPool {
     ) -> Self {
         let (shutdown_tx, shutdown_rx) = async_channel::unbounded::<()>();
 
-        let executor = Arc::new(async_executor::Executor::new());
+        let executors = Arc::new(Executors::default());
+        let counters = Arc::<MetricsCounters>::default();
 
         let num_threads = num_threads.unwrap_or_else(num_cpus::get);
 
+        let mut pinned_senders = Vec::with_capacity(num_threads);
         let threads = (0..num_threads)
             .map(|i| {
-                let ex = Arc::clone(&executor);
+                let executors = Arc::clone(&executors);
+                let counters = Arc::clone(&counters);
                 let shutdown_rx = shutdown_rx.clone();
+                let (pinned_tx, pinned_rx) = async_channel::unbounded::<PinnedJob>();
+                pinned_senders.push(pinned_tx);
 
                 let thread_name = if let Some(thread_name) = thread_name {
                     format!("{} ({})", thread_name, i)
@@ -115,17 +238,32 @@ impl TaskPool {
 
                 thread_builder
                     .spawn(move || {
-                        let shutdown_future = ex.run(shutdown_rx.recv());
-                        // Use unwrap_err because we expect a Closed error
-                        future::block_on(shutdown_future).unwrap_err();
+                        TaskPool::WORKER_OF
+                            .with(|worker_of| worker_of.set(Arc::as_ptr(&counters) as usize));
+                        TaskPool::LOCAL_EXECUTOR.with(|local_executor| {
+                            let shutdown = async {
+                                // We expect a Closed error
+                                shutdown_rx.recv().await.unwrap_err();
+                            };
+                            let pinned_jobs = async {
+                                while let Ok(job) = pinned_rx.recv().await {
+                                    job(local_executor);
+                                }
+                            };
+                            future::block_on(
+                                local_executor.run(executors.run(shutdown.or(pinned_jobs))),
+                            );
+                        });
                     })
                     .expect("Failed to spawn thread.")
             })
             .collect();
 
         Self {
-            executor,
+            executors,
+            counters,
             threads,
+            pinned_senders,
             shutdown_tx,
         }
     }
@@ -135,10 +273,23 @@ impl TaskPool {
         self.threads.len()
     }
 
+    /// Returns a snapshot of the activity of the pool.
+    pub fn metrics(&self) -> TaskPoolMetrics {
+        TaskPoolMetrics {
+            thread_count: self.threads.len(),
+            queued_tasks: self.counters.queued.load(Ordering::Relaxed),
+            active_tasks: self.counters.active.load(Ordering::Relaxed),
+            busy_time: Duration::from_nanos(self.counters.busy_nanos.load(Ordering::Relaxed)),
+        }
+    }
+
     /// Allows spawning non-`'static` futures on the thread pool. The function takes a callback,
     /// passing a scope object into it. The scope object provided to the callback can be used
     /// to spawn tasks. This function will await the completion of all tasks before returning.
     ///
+    /// While waiting, the calling thread runs tasks of the pool, so that it contributes to the
+    /// pool's work rather than only blocking.
+    ///
     /// This is similar to `rayon::scope` and `crossbeam::scope`
     pub fn scope<'scope, F, T>(&self, f: F) -> Vec<T>
     where
@@ -150,13 +301,14 @@ impl TaskPool {
             // before this function returns. However, rust has no way of knowing
             // this so we must convert to 'static here to appease the compiler as it is unable to
             // validate safety.
-            let executor: &async_executor::Executor = &*self.executor;
-            let executor: &'scope async_executor::Executor = unsafe { mem::transmute(executor) };
+            let executors: &Executors = &*self.executors;
+            let executors: &'scope Executors<'scope> = unsafe { mem::transmute(executors) };
             let local_executor: &'scope async_executor::LocalExecutor =
                 unsafe { mem::transmute(local_executor) };
             let mut scope = Scope {
-                executor,
+                executors,
                 local_executor,
+                counters: Arc::clone(&self.counters),
                 spawned: Vec::new(),
             };
 
@@ -165,7 +317,9 @@ impl TaskPool {
             if scope.spawned.is_empty() {
                 Vec::default()
             } else if scope.spawned.len() == 1 {
-                vec![future::block_on(&mut scope.spawned[0])]
+                vec![future::block_on(
+                    local_executor.run(executors.run(&mut scope.spawned[0])),
+                )]
             } else {
                 let fut = async move {
                     let mut results = Vec::with_capacity(scope.spawned.len());
@@ -192,19 +346,22 @@ impl TaskPool {
                 // complete. (If the caller of scope() happens to be a thread in
                 // this thread pool, and we only have one thread in the pool, then
                 // simply calling future::block_on(spawned) would deadlock.)
-                let mut spawned = local_executor.spawn(fut);
-                loop {
-                    if let Some(result) = future::block_on(future::poll_once(&mut spawned)) {
-                        break result;
-                    };
-
-                    self.executor.try_tick();
-                    local_executor.try_tick();
-                }
+                let spawned = local_executor.spawn(fut);
+                future::block_on(local_executor.run(executors.run(spawned)))
             }
         })
     }
 
+    /// Blocks the current thread until `future` completes, running tasks of the pool on it in the
+    /// meantime.
+    ///
+    /// This lets a thread that has to wait, such as the main thread, lend itself to the pool
+    /// instead of idling.
+    pub fn block_on<T>(&self, future: impl Future<Output = T>) -> T {
+        TaskPool::LOCAL_EXECUTOR
+            .with(|local_executor| future::block_on(local_executor.run(self.executors.run(future))))
+    }
+
     /// Spawns a static future onto the thread pool. The returned Task is a future. It can also be
     /// cancelled and "detached" allowing it to continue running without having to be polled by the
     /// end-user.
@@ -214,7 +371,25 @@ impl TaskPool {
     where
         T: Send + 'static,
     {
-        Task::new(self.executor.spawn(future))
+        self.spawn_with_priority(TaskPriority::Normal, future)
+    }
+
+    /// Spawns a static future onto the thread pool with the given [`TaskPriority`].
+    ///
+    /// See [`TaskPool::spawn`].
+    pub fn spawn_with_priority<T>(
+        &self,
+        priority: TaskPriority,
+        future: impl Future<Output = T> + Send + 'static,
+    ) -> Task<T>
+    where
+        T: Send + 'static,
+    {
+        Task::new(
+            self.executors
+                .get(priority)
+                .spawn(instrument(&self.counters, future)),
+        )
     }
 
     /// Spawns a static future on the thread-local async executor for the current thread. The task
@@ -226,8 +401,53 @@ impl TaskPool {
     where
         T: 'static,
     {
+        let future = instrument(&self.counters, future);
         Task::new(TaskPool::LOCAL_EXECUTOR.with(|executor| executor.spawn(future)))
     }
+
+    /// Spawns a future on the thread of the pool with the given index, where it will run
+    /// entirely. The future is created on that thread by `make_future`, so it does not need
+    /// to be `Send`, which makes this suitable for thread-local APIs.
+    ///
+    /// Dropping the returned [`Task`] does not cancel the pinned future.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `thread_index` is not lower than [`TaskPool::thread_num`].
+    pub fn spawn_pinned<T, F, Fut>(&self, thread_index: usize, make_future: F) -> Task<T>
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = T> + 'static,
+        T: Send + 'static,
+    {
+        let sender = self.pinned_senders.get(thread_index).unwrap_or_else(|| {
+            panic!(
+                "Cannot pin a task to thread {} of a pool of {} threads.",
+                thread_index,
+                self.threads.len()
+            )
+        });
+        let (result_tx, result_rx) = async_channel::bounded(1);
+        let counters = Arc::clone(&self.counters);
+        let job: PinnedJob = Box::new(move |local_executor| {
+            // Like the panics of tasks, a panic of `make_future` is reported to the returned task,
+            // by dropping `result_tx`, rather than stopping the thread.
+            let future = match std::panic::catch_unwind(AssertUnwindSafe(make_future)) {
+                Ok(future) => future,
+                Err(_) => return,
+            };
+            local_executor
+                .spawn(instrument(&counters, async move {
+                    // The receiver is gone if the returned task was dropped.
+                    let _ = result_tx.send(future.await).await;
+                }))
+                .detach();
+        });
+        sender
+            .try_send(job)
+            .expect("Task pool threads are only stopped when the pool is dropped.");
+        self.spawn(async move { result_rx.recv().await.expect("The pinned task panicked.") })
+    }
 }
 
 impl Default for TaskPool {
@@ -255,8 +475,9 @@ impl Drop for TaskPool {
 /// For more information, see [`TaskPool::scope`].
 #[derive(Debug)]
 pub struct Scope<'scope, T> {
-    executor: &'scope async_executor::Executor<'scope>,
+    executors: &'scope Executors<'scope>,
     local_executor: &'scope async_executor::LocalExecutor<'scope>,
+    counters: Arc<MetricsCounters>,
     spawned: Vec<async_executor::Task<T>>,
 }
 
@@ -270,7 +491,21 @@ impl<'scope, T: Send + 'scope> Scope<'scope, T> {
     ///
     /// For more information, see [`TaskPool::scope`].
     pub fn spawn<Fut: Future<Output = T> + 'scope + Send>(&mut self, f: Fut) {
-        let task = self.executor.spawn(f);
+        self.spawn_with_priority(TaskPriority::Normal, f);
+    }
+
+    /// Spawns a scoped future onto the thread pool with the given [`TaskPriority`].
+    ///
+    /// See [`Scope::spawn`].
+    pub fn spawn_with_priority<Fut: Future<Output = T> + 'scope + Send>(
+        &mut self,
+        priority: TaskPriority,
+        f: Fut,
+    ) {
+        let task = self
+            .executors
+            .get(priority)
+            .spawn(instrument(&self.counters, f));
         self.spawned.push(task);
     }
 
@@ -281,7 +516,7 @@ impl<'scope, T: Send + 'scope> Scope<'scope, T> {
     ///
     /// For more information, see [`TaskPool::scope`].
     pub fn spawn_local<Fut: Future<Output = T> + 'scope>(&mut self, f: Fut) {
-        let task = self.local_executor.spawn(f);
+        let task = self.local_executor.spawn(instrument(&self.counters, f));
         self.spawned.push(task);
     }
 }
@@ -407,4 +642,129 @@ mod tests {
         assert!(!thread_check_failed.load(Ordering::Acquire));
         assert_eq!(count.load(Ordering::Acquire), 200);
     }
+
+    /// Spawns a task blocking the only thread of `pool` until the returned sender is dropped.
+    fn block_single_thread(pool: &TaskPool) -> (std::sync::mpsc::Sender<()>, Task<()>) {
+        let (unblock_tx, unblock_rx) = std::sync::mpsc::channel::<()>();
+        let (started_tx, started_rx) = std::sync::mpsc::channel::<()>();
+        let blocker = pool.spawn(async move {
+            started_tx.send(()).unwrap();
+            let _ = unblock_rx.recv();
+        });
+        started_rx.recv().unwrap();
+        (unblock_tx, blocker)
+    }
+
+    #[test]
+    fn test_priority() {
+        let pool = TaskPoolBuilder::new().num_threads(1).build();
+        let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        let (unblock, blocker) = block_single_thread(&pool);
+        let tasks: Vec<_> = [TaskPriority::Low, TaskPriority::Normal, TaskPriority::High]
+            .into_iter()
+            .map(|priority| {
+                let order = order.clone();
+                pool.spawn_with_priority(priority, async move {
+                    order.lock().unwrap().push(priority);
+                })
+            })
+            .collect();
+        drop(unblock);
+        future::block_on(blocker);
+        for task in tasks {
+            future::block_on(task);
+        }
+
+        assert_eq!(
+            *order.lock().unwrap(),
+            vec![TaskPriority::High, TaskPriority::Normal, TaskPriority::Low]
+        );
+    }
+
+    #[test]
+    fn test_spawn_pinned() {
+        thread_local! {
+            static VALUE: std::cell::Cell<i32> = std::cell::Cell::new(0);
+        }
+
+        let pool = TaskPoolBuilder::new().num_threads(2).build();
+        let thread_ids: Vec<_> = (0..10)
+            .map(|i| {
+                pool.spawn_pinned(1, move || async move {
+                    // `Rc` is not `Send`, so this future could not be spawned with `spawn`.
+                    let value = std::rc::Rc::new(i);
+                    VALUE.with(|cell| cell.set(cell.get() + *value));
+                    std::thread::current().id()
+                })
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(future::block_on)
+            .collect();
+
+        assert!(thread_ids.iter().all(|id| *id == thread_ids[0]));
+        let total =
+            future::block_on(pool.spawn_pinned(1, || async { VALUE.with(|cell| cell.get()) }));
+        assert_eq!(total, 45);
+    }
+
+    #[test]
+    fn test_spawn_pinned_panic() {
+        let pool = TaskPoolBuilder::new().num_threads(1).build();
+        let task = pool.spawn_pinned(0, || -> std::future::Ready<()> { panic!("make_future") });
+        assert!(std::panic::catch_unwind(AssertUnwindSafe(|| future::block_on(task))).is_err());
+
+        // the thread is still running pinned tasks
+        assert_eq!(future::block_on(pool.spawn_pinned(0, || async { 1 })), 1);
+    }
+
+    #[test]
+    fn test_busy_time_only_counts_pool_threads() {
+        let pool = TaskPoolBuilder::new().num_threads(1).build();
+        let (unblock, blocker) = block_single_thread(&pool);
+
+        // runs on the calling thread, as the only thread of the pool is blocked
+        pool.block_on(pool.spawn(async { std::thread::sleep(Duration::from_millis(50)) }));
+        let busy_time = pool.metrics().busy_time;
+        drop(unblock);
+        future::block_on(blocker);
+
+        assert!(busy_time < Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_block_on_runs_tasks() {
+        let pool = TaskPoolBuilder::new().num_threads(1).build();
+        let (unblock, blocker) = block_single_thread(&pool);
+
+        let task = pool.spawn(async { std::thread::current().id() });
+        let ran_on = pool.block_on(task);
+        drop(unblock);
+        future::block_on(blocker);
+
+        assert_eq!(ran_on, std::thread::current().id());
+    }
+
+    #[test]
+    fn test_metrics() {
+        let pool = TaskPoolBuilder::new().num_threads(2).build();
+        let (tx, rx) = async_channel::bounded::<()>(1);
+        let task = pool.spawn(async move {
+            rx.recv().await.unwrap();
+        });
+        while pool.metrics().active_tasks == 0 {
+            std::thread::yield_now();
+        }
+        let metrics = pool.metrics();
+        assert_eq!(metrics.thread_count, 2);
+        assert_eq!(metrics.active_tasks, 1);
+        assert_eq!(metrics.queued_tasks, 0);
+
+        tx.try_send(()).unwrap();
+        future::block_on(task);
+        let metrics = pool.metrics();
+        assert_eq!(metrics.active_tasks, 0);
+        assert!(metrics.busy_time > std::time::Duration::ZERO);
+    }
 }
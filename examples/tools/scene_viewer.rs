@@ -48,6 +48,7 @@ Controls:
     .insert_resource(AssetServerSettings {
         asset_folder: std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".to_string()),
         watch_for_changes: true,
+        ..default()
     })
     .insert_resource(WindowDescriptor {
         title: "bevy scene viewer".to_string(),
@@ -0,0 +1,89 @@
+use std::{fs::File, io::BufWriter, path::PathBuf};
+
+use bevy_asset::{Archive, ArchiveBuilder, ArchiveCompression};
+use clap::{ArgEnum, Parser, Subcommand};
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Build an archive from an asset folder
+    Build {
+        /// The asset folder
+        #[clap(value_parser)]
+        folder: PathBuf,
+
+        #[clap(short, long, value_parser)]
+        /// The archive to write
+        output: PathBuf,
+
+        #[clap(short, long, arg_enum, value_parser, default_value = "zstd")]
+        /// The compression of the entries
+        compression: Compression,
+    },
+    /// List the entries of an archive
+    List {
+        /// The archive
+        #[clap(value_parser)]
+        archive: PathBuf,
+    },
+}
+
+#[derive(ArgEnum, Clone, Copy, Debug)]
+enum Compression {
+    None,
+    Lz4,
+    Zstd,
+}
+
+impl From<Compression> for ArchiveCompression {
+    fn from(compression: Compression) -> Self {
+        match compression {
+            Compression::None => ArchiveCompression::None,
+            Compression::Lz4 => ArchiveCompression::Lz4,
+            Compression::Zstd => ArchiveCompression::Zstd,
+        }
+    }
+}
+
+fn main() {
+    match Args::parse().command {
+        Command::Build {
+            folder,
+            output,
+            compression,
+        } => {
+            let mut builder = ArchiveBuilder::new(compression.into());
+            builder
+                .add_folder(&folder)
+                .unwrap_or_else(|error| panic!("failed to read {:?}: {}", folder, error));
+            let file = File::create(&output)
+                .unwrap_or_else(|error| panic!("failed to create {:?}: {}", output, error));
+            builder
+                .write(BufWriter::new(file))
+                .unwrap_or_else(|error| panic!("failed to write {:?}: {}", output, error));
+            println!("packed {} files into {:?}", builder.len(), output);
+        }
+        Command::List { archive: path } => {
+            let archive = Archive::open(&path)
+                .unwrap_or_else(|error| panic!("failed to open {:?}: {}", path, error));
+            let mut paths: Vec<_> = archive.paths().collect();
+            paths.sort_unstable();
+            for path in paths {
+                let entry = archive.entry(path).unwrap();
+                println!(
+                    "{:>10} {:>10} {:<5} {:016x} {}",
+                    entry.size,
+                    entry.stored_len,
+                    format!("{:?}", entry.compression),
+                    entry.hash,
+                    path
+                );
+            }
+        }
+    }
+}